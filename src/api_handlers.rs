@@ -1,25 +1,41 @@
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     extract::{State, Query, Path},
-    http::{StatusCode, HeaderMap},
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    http::{header, StatusCode, HeaderMap},
     response::{Json, IntoResponse, Response},
+    response::sse::{Event, KeepAlive, Sse},
     Json as JsonExtractor,
 };
+use futures_util::{stream, Stream, StreamExt};
 use serde::{Deserialize};
 use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
 
 use crate::client::{RegisterRequest, RegisterResponse, ClientInfoResponse, ClientInfo};
 use crate::card::{ListAssignedCardsResponse, AssignedCardInfo, GenerateCardsRequest, GenerateCardsResponse};
 use crate::board::{Board, BOARD_ID};
 use crate::pouch::Pouch;
-use crate::score::ScoreCard;
+use crate::score::{ClaimRequest, ClaimVerdict, PrizeMode, ScoreCard};
 use crate::logging::{log, LogLevel};
 use crate::server::AppState;
-use crate::game::Game;
+use crate::game::{Game, NewGameRequest};
+use crate::autorun::{run_autorun, AutoRun};
+use crate::auth::{bearer_credential, issue_token, AuthClient, Authorized};
+use crate::role::{Action, CanDraw, CanManage, CanPlay, CanReadCards, CanViewPlayers, Role, SetRoleRequest};
+use crate::extraction::{DrawMode, ExtractRequest, ManualDraw, UndoRequest};
+use crate::journal::GameUpdate;
+use crate::serial::{card_serial, parse_serial, SERIAL_VERSION};
+use crate::card_import::{parse_cards, AssignCardsRequest, ImportFormat};
 
 const MODULE_NAME: &str = "api_handlers";
 
+// Longest wait of a snapshot request for a change of the game
+const SNAPSHOT_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
 // Response structures for JSON serialization
 #[derive(serde::Serialize)]
 pub struct ErrorResponse {
@@ -52,7 +68,7 @@ impl ApiError {
 }
 
 // Helper function to get a game from the registry by ID
-async fn get_game_from_registry(app_state: &Arc<AppState>, game_id: &str) -> Result<Arc<Game>, ApiError> {
+pub(crate) async fn get_game_from_registry(app_state: &Arc<AppState>, game_id: &str) -> Result<Arc<Game>, ApiError> {
     match app_state.game_registry.get_game(game_id) {
         Ok(Some(game)) => Ok(game),
         Ok(None) => Err(ApiError::new(StatusCode::NOT_FOUND, format!("Game with ID '{game_id}' not found"))),
@@ -60,6 +76,94 @@ async fn get_game_from_registry(app_state: &Arc<AppState>, game_id: &str) -> Res
     }
 }
 
+// Issue a token bound to the current API key of a client
+fn client_token(app_state: &AppState, client_info: &ClientInfo) -> Result<String, ApiError> {
+    issue_token(client_info, &app_state.config).map_err(|e| {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] {e}", client_info.id));
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to issue client token")
+    })
+}
+
+// Issue a new API key to a registered client, with a token bound to it
+fn client_api_key(app_state: &AppState, client_id: &str) -> Result<(String, String), ApiError> {
+    match app_state.global_client_registry.issue_api_key(client_id) {
+        Ok(Some((api_key, client_info))) => {
+            app_state.persistence.notify_change();
+            Ok((api_key, client_token(app_state, &client_info)?))
+        }
+        Ok(None) => Err(ApiError::new(StatusCode::NOT_FOUND, format!("Client with ID '{client_id}' not found"))),
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to issue API key: {e}"));
+            Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to issue API key"))
+        }
+    }
+}
+
+// Credentials returned by the registration of a client: a token, with a new API key if the client has none
+// The API key is only returned once, registering again with a key only returns a token
+fn client_credentials(app_state: &AppState, client_info: &ClientInfo) -> Result<(String, Option<String>), ApiError> {
+    if client_info.api_key_hash.is_some() {
+        Ok((client_token(app_state, client_info)?, None))
+    } else {
+        let (api_key, token) = client_api_key(app_state, &client_info.id)?;
+        Ok((token, Some(api_key)))
+    }
+}
+
+// Existing client registering again under its name
+// A taken name belongs to the client whose API key or token the request presents; without it the registration
+// is refused with 409, unless the request opts in to register a new client sharing the display name.
+// This is the only way the registration handlers get an existing client, so they never issue a token or an API key
+// for a taken name to a request that does not already hold a credential of its client
+fn registering_client(app_state: &AppState, headers: &HeaderMap, request: &RegisterRequest) -> Result<Option<ClientInfo>, ApiError> {
+    let client_name = &request.name;
+
+    // Only the bearer credential proves the identity of the client, X-Client-ID is public
+    if let Some(credential) = bearer_credential(headers) {
+        let auth = AuthClient::from_credential(app_state, credential)?;
+        if auth.name == *client_name {
+            return match app_state.global_client_registry.get(&auth.client_id) {
+                Ok(client_info) => Ok(client_info),
+                Err(e) => {
+                    log(LogLevel::Error, MODULE_NAME, &format!("Failed to access global client registry: {e}"));
+                    Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access global client registry"))
+                }
+            };
+        }
+    }
+
+    match app_state.global_client_registry.get_by_name(client_name) {
+        Ok(Some(_)) if request.allow_duplicate_name => {
+            // The names of the admins are reserved, so that no client passes for one of them
+            if is_admin_name(app_state, client_name)? {
+                log(LogLevel::Error, MODULE_NAME, &format!("Name '{client_name}' of an admin reused without its credential"));
+                return Err(ApiError::new(StatusCode::CONFLICT, format!("Client name '{client_name}' belongs to an admin of the server and cannot be shared")));
+            }
+            Ok(None)
+        }
+        Ok(Some(existing_client)) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Name '{client_name}' reused without the credential of the client", existing_client.id));
+            Err(ApiError::new(StatusCode::CONFLICT, format!("Client name '{client_name}' is already taken, send its API key or token to register as this client, or set allow_duplicate_name to register a new client with the same name")))
+        }
+        Ok(None) => Ok(None),
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to access global client registry: {e}"));
+            Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access global client registry"))
+        }
+    }
+}
+
+// Whether a name is held by one of the admins of server.conf
+fn is_admin_name(app_state: &AppState, client_name: &str) -> Result<bool, ApiError> {
+    match app_state.global_client_registry.get_all_clients() {
+        Ok(clients) => Ok(clients.iter().any(|client| client.name == client_name && app_state.config.is_admin(&client.id))),
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to access global client registry: {e}"));
+            Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access global client registry"))
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct ClientIdQuery {
     pub client_id: Option<String>,
@@ -81,9 +185,15 @@ pub struct DumpGameQuery {
     pub filename: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct SnapshotQuery {
+    pub since_version: Option<usize>,
+}
+
 pub async fn handle_join(
     Path(game_id): Path<String>,
     State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
     JsonExtractor(request): JsonExtractor<RegisterRequest>,
 ) -> Result<Json<RegisterResponse>, ApiError> {
     log(LogLevel::Info, MODULE_NAME, &format!("Client registration request for game '{game_id}': {request:?}"));
@@ -93,28 +203,39 @@ pub async fn handle_join(
     let client_name = &request.name;
     let client_type = &request.client_type;
 
-    // First, check if the client already exists globally
-    let client_info = match app_state.global_client_registry.get_by_name(client_name) {
-        Ok(Some(existing_client)) => {
-            // Client exists globally, reuse their info
-            existing_client
+    // The client type of the request is the role taken in the game, other roles are granted
+    let role = match client_type.parse::<Role>() {
+        Ok(role) if role.can_join_as() => role,
+        // Only the creator of a game owns it, board clients joining a game play in it
+        Ok(Role::Owner) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("Client '{client_name}' joins game '{game_id}' as player, the owner role is taken by creating a game"));
+            Role::Player
         }
-        Ok(None) => {
-            // Client doesn't exist globally, create new one
-            let new_client = ClientInfo::new(client_name, client_type, "");  // Empty email for now
-
-            // Add to global registry
-            if let Err(e) = app_state.global_client_registry.insert(new_client.clone()) {
-                log(LogLevel::Error, MODULE_NAME, &format!("Failed to add client to global registry: {e}"));
-                return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to register client globally"));
-            }
-
-            new_client
+        Ok(role) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Client '{client_name}' cannot join game '{game_id}' as {role}"));
+            return Err(ApiError::new(StatusCode::FORBIDDEN, format!("The {role} role cannot be taken by joining a game")));
         }
         Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("Failed to access global client registry: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access global client registry"));
+            log(LogLevel::Error, MODULE_NAME, &format!("Client '{client_name}' cannot join game '{game_id}': {e}"));
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, e));
+        }
+    };
+
+    // First, check if the client already exists globally, which needs its credential
+    let client_info = if let Some(existing_client) = registering_client(&app_state, &headers, &request)? {
+        // Client exists globally and proved its identity, reuse their info
+        existing_client
+    } else {
+        // Client doesn't exist globally, create new one
+        let new_client = ClientInfo::new(client_name, client_type, "");  // Empty email for now
+
+        // Add to global registry
+        if let Err(e) = app_state.global_client_registry.insert(new_client.clone()) {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to add client to global registry: {e}"));
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to register client globally"));
         }
+
+        new_client
     };
 
     let client_id = client_info.id.clone();
@@ -122,14 +243,24 @@ pub async fn handle_join(
     // Log with client ID now that we have it
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Processing registration for game '{game_id}'"));
 
+    // The API key and the token authenticate the requests of the client from now on
+    let (token, api_key) = client_credentials(&app_state, &client_info)?;
+
     // Check if client is already registered to this specific game
     if game.contains_client(&client_id) {
         return Ok(Json(RegisterResponse {
             client_id: client_id.clone(),
+            token,
+            api_key,
             message: format!("Client '{client_name}' already registered in game '{game_id}'"),
         }));
     }
 
+    // Spectators join at any time without cards
+    if role == Role::Spectator {
+        return join_as_spectator(&app_state, &game, client_id, (token, api_key), client_name);
+    }
+
     // Try to register the client to this specific game (will fail if numbers have been extracted)
     match game.add_client(client_id.clone()) {
         Ok(added) => {
@@ -145,38 +276,56 @@ pub async fn handle_join(
         }
     }
 
-    // Set the game-specific client type for this client in this game
-    if let Err(e) = game.set_client_type(&client_id, client_type) {
-        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set client type in game '{game_id}': {e}"));
-        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client type for game"));
+    // Set the role of this client in this game
+    if let Err(e) = game.set_role(&client_id, role) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set role in game '{game_id}': {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client role for game"));
     }
 
     // Check if client requested cards during registration, default to 1 if not specified
     let card_count = request.nocard.unwrap_or(1);
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Generating {card_count} cards during registration"));
 
-    // Generate the requested number of cards using the card manager
-    if let Ok(mut manager) = game.card_manager().lock() {
-        // Check if there's already a board owner (client with BOARD_ID card)
-        let has_board_owner = manager.get_card_assignment(BOARD_ID).is_some();
+    // Generate the requested number of cards
+    match game.assign_cards(&client_id, card_count, Some(role)) {
+        Ok(_) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Generated and assigned {card_count} cards in game '{game_id}'"));
+        }
+        Err(e) => {
+            log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Failed to assign cards in game '{game_id}': {e}"));
+        }
+    }
+    app_state.persistence.notify_change();
+
+    Ok(Json(RegisterResponse {
+        client_id,
+        token,
+        api_key,
+        message: format!("Client '{client_name}' registered successfully in game '{game_id}'"),
+    }))
+}
 
-        // If client_type is "board" but there's already a board owner, treat them as a regular player
-        let effective_client_type = if client_type == "board" && has_board_owner {
-            log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Board owner already exists, treating board client as regular player"));
-            "player"
-        } else {
-            client_type
-        };
+// Register a spectator to a game, with no cards whatever the request asked for
+fn join_as_spectator(app_state: &AppState, game: &Game, client_id: String, credentials: (String, Option<String>), client_name: &str) -> Result<Json<RegisterResponse>, ApiError> {
+    let game_id = game.id();
+    let (token, api_key) = credentials;
 
-        manager.assign_cards_with_type(&client_id, card_count, Some(effective_client_type));
-        log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Generated and assigned {card_count} cards in game '{game_id}'"));
-    } else {
-        log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Failed to acquire card manager lock in game '{game_id}'"));
+    if let Err(e) = game.add_spectator(client_id.clone()) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to join game '{game_id}' as spectator: {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e));
     }
+    if let Err(e) = game.set_role(&client_id, Role::Spectator) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set role in game '{game_id}': {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client role for game"));
+    }
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Joined game '{game_id}' as spectator"));
+    app_state.persistence.notify_change();
 
     Ok(Json(RegisterResponse {
         client_id,
-        message: format!("Client '{client_name}' registered successfully in game '{game_id}'"),
+        token,
+        api_key,
+        message: format!("Client '{client_name}' joined game '{game_id}' as spectator"),
     }))
 }
 
@@ -291,17 +440,26 @@ pub async fn handle_global_register(
     let client_type = &request.client_type;
     let email = request.email.as_deref().unwrap_or("");  // Use provided email or empty string
 
-    // Check if the client already exists globally
-    match app_state.global_client_registry.get_by_name(client_name) {
-        Ok(Some(existing_client)) => {
-            // Client already exists globally
+    // Check if the client already exists globally, which needs its credential
+    match registering_client(&app_state, &headers, &request)? {
+        Some(existing_client) => {
+            // Client already exists globally and proved its identity
             log(LogLevel::Info, MODULE_NAME, &format!("[Client: {}] Client '{}' already registered globally", existing_client.id, client_name));
+            let (token, api_key) = client_credentials(&app_state, &existing_client)?;
             Ok(Json(RegisterResponse {
                 client_id: existing_client.id.clone(),
+                token,
+                api_key,
                 message: format!("Client '{client_name}' already registered globally"),
             }))
         }
-        Ok(None) => {
+        None => {
+            // The global admin role is granted by server.conf to the ID of a registered client, a new client never holds it
+            if client_type.parse::<Role>() == Ok(Role::Admin) {
+                log(LogLevel::Error, MODULE_NAME, &format!("Client '{client_name}' cannot register as admin"));
+                return Err(ApiError::new(StatusCode::FORBIDDEN, "The admin role is granted in the admins of the server to the client ID of a registered client"));
+            }
+
             // Client doesn't exist globally, create new one
             let new_client = ClientInfo::new(client_name, client_type, email);
 
@@ -309,8 +467,11 @@ pub async fn handle_global_register(
             match app_state.global_client_registry.insert(new_client.clone()) {
                 Ok(_) => {
                     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {}] Successfully registered client '{}' globally with ID: {}", new_client.id, client_name, new_client.id));
+                    let (token, api_key) = client_credentials(&app_state, &new_client)?;
                     Ok(Json(RegisterResponse {
                         client_id: new_client.id.clone(),
+                        token,
+                        api_key,
                         message: format!("Client '{client_name}' registered successfully globally"),
                     }))
                 }
@@ -320,9 +481,48 @@ pub async fn handle_global_register(
                 }
             }
         }
+    }
+}
+
+pub async fn handle_rotatekey(
+    State(app_state): State<Arc<AppState>>,
+    auth: AuthClient,
+) -> Result<Json<RegisterResponse>, ApiError> {
+    let client_id = auth.client_id;
+
+    // The previous API key and the tokens issued with it stop authenticating the client
+    let (api_key, token) = client_api_key(&app_state, &client_id)?;
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] API key rotated"));
+
+    Ok(Json(RegisterResponse {
+        client_id,
+        token,
+        api_key: Some(api_key),
+        message: "API key rotated, the previous key and its tokens are no longer valid".to_string(),
+    }))
+}
+
+pub async fn handle_revokekey(
+    State(app_state): State<Arc<AppState>>,
+    auth: AuthClient,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let client_id = auth.client_id;
+
+    // Revocation is permanent: no credential of the client is left to register again under its name,
+    // so a client that may need its identity again rotates its key instead
+    match app_state.global_client_registry.revoke_api_key(&client_id) {
+        Ok(true) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] API key revoked"));
+            app_state.persistence.notify_change();
+            Ok(Json(json!({
+                "client_id": client_id,
+                "message": "API key revoked, the key and its tokens are no longer valid"
+            })))
+        }
+        Ok(false) => Err(ApiError::new(StatusCode::NOT_FOUND, format!("Client with ID '{client_id}' not found"))),
         Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("Failed to access global client registry: {e}"));
-            Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access global client registry"))
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to revoke API key: {e}"));
+            Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to revoke API key"))
         }
     }
 }
@@ -330,41 +530,13 @@ pub async fn handle_global_register(
 pub async fn handle_generatecards(
     State(app_state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
-    headers: HeaderMap,
+    access: Authorized<CanPlay>,
     JsonExtractor(request): JsonExtractor<GenerateCardsRequest>,
 ) -> Result<Json<GenerateCardsResponse>, ApiError> {
-    // Get client ID from headers first, so we can use it in logging
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() {
-            id.to_string()
-        } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid client ID in header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid client ID in header"));
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Client ID header (X-Client-ID) is required");
-        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Client ID header (X-Client-ID) is required"));
-    };
+    let Authorized { client_id, game, .. } = access;
 
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Generate cards request for game: {game_id}"));
 
-    let game = get_game_from_registry(&app_state, &game_id).await?;
-
-    // Verify client is registered and get their info
-    match game.get_client_info(&client_id, &app_state.global_client_registry) {
-        Ok(Some(_client_info)) => {
-            // Client is registered and found in global registry
-        }
-        Ok(None) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Not registered"));
-            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Client not registered"));
-        }
-        Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to verify registration: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client registration"));
-        }
-    }
-
     // Check if client already has cards assigned (prevent duplicate generation)
     if let Ok(manager) = game.card_manager().lock() {
         if let Some(existing_cards) = manager.get_client_cards(&client_id) {
@@ -375,22 +547,17 @@ pub async fn handle_generatecards(
         }
     }
 
-    // Get client type for proper card assignment
-    let client_type = match app_state.global_client_registry.get(&client_id) {
-        Ok(Some(info)) => Some(info.client_type.clone()),
-        _ => None,
-    };
-
-    // Generate cards using the CardAssignmentManager
-    let card_infos = if let Ok(mut manager) = game.card_manager().lock() {
-        let (cards, _) = manager.assign_cards_with_type(&client_id, request.count, client_type.as_deref());
-        cards
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Failed to acquire card manager lock");
-        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to acquire card manager lock"));
+    // Generate cards using the CardAssignmentManager, players get regular cards
+    let card_infos = match game.assign_cards(&client_id, request.count, None) {
+        Ok((cards, _)) => cards,
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &e);
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e));
+        }
     };
 
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {}] Generated {} cards for client {}", client_id, card_infos.len(), client_id));
+    app_state.persistence.notify_change();
 
     // Create response
     let response = GenerateCardsResponse {
@@ -402,43 +569,14 @@ pub async fn handle_generatecards(
 }
 
 pub async fn handle_listassignedcards(
-    State(app_state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
-    headers: HeaderMap,
+    access: Authorized<CanReadCards>,
     Query(_params): Query<ClientIdQuery>,
 ) -> Result<Json<ListAssignedCardsResponse>, ApiError> {
-    // Get client ID from headers first
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() {
-            id.to_string()
-        } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid client ID in header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid client ID in header"));
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Client ID header (X-Client-ID) is required");
-        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Client ID header (X-Client-ID) is required"));
-    };
+    let Authorized { client_id, game, .. } = access;
 
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] List assigned cards request for game: {game_id}"));
 
-    let game = get_game_from_registry(&app_state, &game_id).await?;
-
-    // Verify client is registered and get their info
-    match game.get_client_info(&client_id, &app_state.global_client_registry) {
-        Ok(Some(_client_info)) => {
-            // Client is registered and found in global registry
-        }
-        Ok(None) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Client not registered"));
-            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Client not registered"));
-        }
-        Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to verify client registration: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client registration"));
-        }
-    }
-
     // Get client's assigned cards
     let assigned_cards = if let Ok(manager) = game.card_manager().lock() {
         manager.get_client_cards(&client_id).cloned().unwrap_or_default()
@@ -462,38 +600,13 @@ pub async fn handle_listassignedcards(
 }
 
 pub async fn handle_getassignedcard(
-    State(app_state): State<Arc<AppState>>,
     Path((game_id, card_id)): Path<(String, String)>,
-    headers: HeaderMap,
+    access: Authorized<CanReadCards>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    // Get client ID from headers first
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() { id.to_string() } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid client ID in header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid client ID in header"));
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Client ID header (X-Client-ID) is required");
-        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Client ID header (X-Client-ID) is required"));
-    };
+    let Authorized { client_id, game, .. } = access;
 
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Get assigned card request for game: {game_id}, card ID: {card_id}"));
 
-    let game = get_game_from_registry(&app_state, &game_id).await?;
-
-    // Verify client is registered and get their info
-    let _client_info = match game.get_client_info(&client_id, &app_state.global_client_registry) {
-        Ok(Some(info)) => info,
-        Ok(None) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Client not registered: {client_id}"));
-            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Client not registered"));
-        }
-        Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to check client registration: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal error"));
-        }
-    };
-
     // Get the card assignment
     let card_assignment = if let Ok(manager) = game.card_manager().lock() {
         manager.get_card_assignment(&card_id).cloned()
@@ -522,6 +635,141 @@ pub async fn handle_getassignedcard(
     Ok(Json(serde_json::to_value(&card_info).unwrap()))
 }
 
+pub async fn handle_verify_card(
+    State(app_state): State<Arc<AppState>>,
+    Path(serial): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    log(LogLevel::Info, MODULE_NAME, &format!("Card verification request for serial: {serial}"));
+
+    let serial = parse_serial(&serial).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+
+    // A card is unique within a game, but the same card can show up in several games
+    let games = app_state.game_registry.all_games()
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let mut cards = Vec::new();
+    for game in games {
+        // Imported cards are found in the inventory until they are assigned
+        let card = game.card_manager().lock()
+            .ok()
+            .and_then(|manager| match manager.get_card_assignment(&serial) {
+                Some(assignment) => Some((assignment.card_data.clone(), true)),
+                None => manager.inventory_card(&serial).map(|card_data| (card_data.clone(), false)),
+            });
+        if let Some((card_data, assigned)) = card {
+            cards.push(json!({
+                "game_id": game.id(),
+                "assigned": assigned,
+                "matches": card_serial(&card_data) == serial,
+                "card_data": card_data,
+            }));
+        }
+    }
+
+    if cards.is_empty() {
+        log(LogLevel::Warning, MODULE_NAME, &format!("No card with serial {serial}"));
+        return Err(ApiError::new(StatusCode::NOT_FOUND, format!("No card with serial {serial}")));
+    }
+
+    let verified = cards.iter().all(|card| card["matches"] == true);
+    if !verified {
+        log(LogLevel::Error, MODULE_NAME, &format!("Card {serial} does not match its serial"));
+    }
+
+    Ok(Json(json!({
+        "serial": serial,
+        "version": SERIAL_VERSION,
+        "cards": cards,
+        "verified": verified,
+    })))
+}
+
+pub async fn handle_importcards(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanManage>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
+    let format = ImportFormat::from_content_type(headers.get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok()));
+
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Import cards request for game: {game_id}, format: {format:?}"));
+
+    if game.has_game_started() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "Cards cannot be imported after the first extraction"));
+    }
+
+    let serials = parse_cards(&body, format, game.board_config().rows_per_card as usize)
+        .and_then(|cards| game.import_cards(cards))
+        .map_err(|e| {
+            log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Card import rejected: {e}"));
+            ApiError::new(StatusCode::BAD_REQUEST, e)
+        })?;
+    let inventory = game.card_manager().lock()
+        .map(|manager| manager.inventory_len())
+        .unwrap_or_default();
+
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Imported {} cards into game {game_id}", serials.len()));
+    app_state.persistence.notify_change();
+
+    Ok(Json(json!({
+        "game_id": game_id,
+        "imported": serials.len(),
+        "serials": serials,
+        "inventory": inventory,
+        "message": format!("Imported {} cards", serials.len())
+    })))
+}
+
+pub async fn handle_assigncards(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanManage>,
+    JsonExtractor(request): JsonExtractor<AssignCardsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
+    let AssignCardsRequest { client_id: target_id, serials } = request;
+
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Assign cards request for game: {game_id}, client: {target_id}, cards: {}", serials.join(", ")));
+
+    let serials = serials.iter()
+        .map(|serial| parse_serial(serial))
+        .collect::<Result<Vec<String>, String>>()
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+    if serials.is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "No cards to assign"));
+    }
+
+    match game.get_role(&target_id) {
+        Ok(Some(role)) if role.allows(Action::Play) => {}
+        Ok(Some(role)) => {
+            return Err(ApiError::new(StatusCode::CONFLICT, format!("Client '{target_id}' is {role} of game '{game_id}' and cannot hold cards")));
+        }
+        Ok(None) => {
+            return Err(ApiError::new(StatusCode::NOT_FOUND, format!("Client '{target_id}' is not registered in game '{game_id}'")));
+        }
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to get the role of client {target_id}: {e}"));
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to get client role"));
+        }
+    }
+
+    // Cards already assigned or missing from the inventory conflict with the request, as does a started game
+    let cards = game.assign_inventory_cards(&target_id, &serials).map_err(|e| {
+        log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Card assignment rejected: {e}"));
+        ApiError::new(StatusCode::CONFLICT, e)
+    })?;
+
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Assigned {} cards to client {target_id} in game {game_id}", cards.len()));
+    app_state.persistence.notify_change();
+
+    Ok(Json(json!({
+        "client_id": target_id,
+        "cards": cards,
+        "message": format!("Assigned {} cards to client {target_id}", cards.len())
+    })))
+}
+
 pub async fn handle_board(
     State(app_state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
@@ -666,101 +914,92 @@ pub async fn handle_status(
         "created_at": game.created_at_string(),
         "owner": owner,
         "players": player_count.to_string(),
+        "spectators": game.spectator_count().to_string(),
         "cards": card_count.to_string(),
         "numbers_extracted": board_len,
         "scorecard": scorecard,
+        "board_config": game.board_config(),
+        "ruleset": game.ruleset(),
+        "full_card_prizes": game.full_card_prizes(),
+        "prize_mode": game.prize_mode(),
+        "false_claim_penalty": game.claim_penalty(),
+        "draw_mode": game.draw_mode(),
+        "card_distribution": game.card_distribution(),
+        "autorun": game.autorun(),
+        "draw_commitment": game.draw_commitment(),
     });
 
+    // Reveal the draw seed once no more numbers can be extracted
+    if let Some(draw_seed) = game.revealed_seed() {
+        response.as_object_mut().unwrap().insert("draw_seed".to_string(), serde_json::Value::String(draw_seed));
+    }
+
     // Add closed_at only if the game is closed
-    if status == crate::game::GameStatus::Closed {
-        // For now, use current time as placeholder - in production this should be tracked properly
-        let closed_time = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    if status == crate::game::GameStatus::Closed
+        && let Ok(Some(closed_time)) = app_state.game_registry.closed_at_string(&game_id) {
         response.as_object_mut().unwrap().insert("closed_at".to_string(), serde_json::Value::String(closed_time));
     }
 
     Ok(Json(response))
 }
 
-pub async fn handle_extract(
+pub async fn handle_snapshot(
     State(app_state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
     headers: HeaderMap,
-    Query(_params): Query<ClientIdQuery>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    // Get client ID from headers for authentication first
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() { id.to_string() } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid client ID in header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid client ID in header"));
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Client ID header (X-Client-ID) is required");
-        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Client ID header (X-Client-ID) is required"));
-    };
-
-    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Extract request for game: {game_id}"));
+    Query(params): Query<SnapshotQuery>,
+) -> Result<Response, ApiError> {
+    log(LogLevel::Info, MODULE_NAME, &format!("Snapshot request for game: {game_id} (since version {:?})", params.since_version));
 
     let game = get_game_from_registry(&app_state, &game_id).await?;
 
-    // Check if the client is registered to this game
-    if !game.contains_client(&client_id) {
-        log(LogLevel::Error, MODULE_NAME, &format!("Client {client_id} is not registered to game {game_id}"));
-        return Err(ApiError::new(StatusCode::FORBIDDEN, "Client must be registered to this game"));
+    // Long polling: wait for a change of the version the client already has
+    if let Some(since_version) = params.since_version
+        && game.version() == since_version {
+        game.changed_since(since_version, SNAPSHOT_POLL_TIMEOUT).await;
     }
 
-    // Only allow the board owner (client with BOARD_ID card assigned) to extract numbers
-    let is_board_owner = if let Ok(manager) = game.card_manager().lock() {
-        if let Some(client_cards) = manager.get_client_cards(&client_id) {
-            client_cards.contains(&BOARD_ID.to_string())
-        } else {
-            false
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, &format!("Failed to acquire card manager lock for client {client_id}"));
-        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify board ownership"));
-    };
+    let snapshot = game.snapshot()
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read game state: {e}")))?;
+    let etag = format!("\"{}\"", snapshot.version);
 
-    if !is_board_owner {
-        log(LogLevel::Error, MODULE_NAME, &format!("Unauthorized: Only the board owner can extract numbers, client ID: {client_id}"));
-        return Err(ApiError::new(StatusCode::FORBIDDEN, "Unauthorized: Only the board owner can extract numbers"));
+    let unchanged = headers.get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"));
+    if unchanged {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
     }
 
-    // Check if BINGO has been reached - if so, no more extractions allowed
-    if game.is_bingo_reached() {
-        return Err(ApiError::new(StatusCode::CONFLICT, "Game over: BINGO has been reached. No more numbers can be extracted."));
-    }
+    Ok(([(header::ETAG, etag)], Json(snapshot)).into_response())
+}
 
-    // Extract a number using the game's coordinated extraction logic
-    match game.extract_number(0, Some(&client_id)) {
-        Ok((extracted_number, _new_working_score)) => {
-            // Get current pouch and board state for response using Game methods
-            let numbers_remaining = game.pouch_length();
-            let total_extracted = game.board_length();
+pub async fn handle_undo(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanDraw>,
+    request: Option<JsonExtractor<UndoRequest>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
 
-            // Check if BINGO was reached after this extraction and dump game state if so
-            if game.is_bingo_reached() {
-                match game.dump_to_json() {
-                    Ok(dump_message) => {
-                        log(LogLevel::Info, MODULE_NAME, &format!("Game ended with BINGO! {dump_message}"));
-                    }
-                    Err(dump_error) => {
-                        log(LogLevel::Error, MODULE_NAME, &format!("Failed to dump game state: {dump_error}"));
-                    }
-                }
-            }
+    let count = request.and_then(|JsonExtractor(request)| request.count).unwrap_or(1);
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Undo request for game: {game_id}, extractions: {count}"));
 
+    match game.undo_extractions(count) {
+        Ok(undone_numbers) => {
+            log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Undid extraction of {undone_numbers:?} in game {game_id}"));
+            app_state.persistence.notify_change();
             Ok(Json(json!({
                 "success": true,
-                "extracted_number": extracted_number,
-                "numbers_remaining": numbers_remaining,
-                "total_extracted": total_extracted,
-                "message": format!("Number {} extracted successfully", extracted_number)
+                "undone_numbers": undone_numbers,
+                "numbers_remaining": game.pouch_length(),
+                "total_extracted": game.board_length(),
+                "scorecard": game.published_score(),
+                "message": format!("Undid extraction of {:?}", undone_numbers)
             })))
         }
         Err(error_msg) => {
-            // Handle extraction errors - match old behavior with proper status codes
-            log(LogLevel::Error, MODULE_NAME, &format!("Failed to extract number: {error_msg}"));
-            if error_msg.contains("empty") {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to undo extraction: {error_msg}"));
+            if error_msg.starts_with("Cannot undo") {
                 Err(ApiError::new(StatusCode::CONFLICT, error_msg))
             } else {
                 Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error_msg))
@@ -769,43 +1008,312 @@ pub async fn handle_extract(
     }
 }
 
-pub async fn handle_global_newgame(
+pub async fn handle_verify(
     State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
     headers: HeaderMap,
+    Query(_params): Query<ClientIdQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    // Get client ID from headers for authentication first
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() { id.to_string() } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid client ID in header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid client ID in header"));
+    // Get optional client ID from headers for logging
+    let client_id_opt = if let Some(header_value) = headers.get("X-Client-ID") {
+        if let Ok(id) = header_value.to_str() {
+            Some(id.to_string())
+        } else {
+            None
         }
     } else {
-        log(LogLevel::Error, MODULE_NAME, "Client ID header (X-Client-ID) is required");
-        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Client ID header (X-Client-ID) is required"));
+        None
     };
 
-    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] New game request"));
+    // Log with client ID if available
+    if let Some(client_id) = &client_id_opt {
+        log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Draw verification request for game: {game_id}"));
+    } else {
+        log(LogLevel::Info, MODULE_NAME, &format!("Draw verification request for game: {game_id}"));
+    }
+
+    let game = get_game_from_registry(&app_state, &game_id).await?;
+
+    let (draw_seed, verification) = game.verify_draw()
+        .map_err(|e| ApiError::new(StatusCode::CONFLICT, e))?;
+    let extracted = game.board().lock()
+        .map(|board| board.get_numbers().clone())
+        .unwrap_or_default();
+
+    if !verification.verified {
+        log(LogLevel::Error, MODULE_NAME, &format!("Draw verification failed for game {game_id}"));
+    }
+
+    Ok(Json(json!({
+        "game_id": game.id(),
+        "draw_seed": draw_seed,
+        "draw_commitment": game.draw_commitment(),
+        "commitment_valid": verification.commitment_valid,
+        "extracted": extracted,
+        "replayed": verification.replayed,
+        "sequence_valid": verification.sequence_valid,
+        "verified": verification.verified,
+    })))
+}
+
+pub async fn handle_extract(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanDraw>,
+    Query(_params): Query<ClientIdQuery>,
+    request: Option<JsonExtractor<ExtractRequest>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
 
-    // Verify client exists in global registry (any authenticated client can create a game)
-    match app_state.global_client_registry.get(&client_id) {
-        Ok(Some(_client_info)) => {
-            // Client exists, they can create a game
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Extract request for game: {game_id}"));
+
+    // Check if BINGO has been reached - if so, no more extractions allowed
+    if game.is_bingo_reached() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "Game over: BINGO has been reached. No more numbers can be extracted."));
+    }
+
+    // Draw from the pouch, or take the number entered by the board owner in manual mode
+    let entered_number = request.and_then(|JsonExtractor(request)| request.number);
+    // The prizes of the board card go to the owner of the game, whoever draws the numbers
+    let board_client_id = game.owner();
+    let extraction = match (game.draw_mode(), entered_number) {
+        (DrawMode::Random, None) => game.extract_number(0, board_client_id.as_deref()),
+        (DrawMode::Manual, Some(number)) => {
+            let board_config = game.board_config();
+            if number < board_config.first_number || number > board_config.last_number() {
+                return Err(ApiError::new(StatusCode::BAD_REQUEST, format!(
+                    "Number {number} is outside the range {}-{} of this game", board_config.first_number, board_config.last_number()
+                )));
+            }
+            log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Manual entry of number {number} for game: {game_id}"));
+            game.extract_number_from(&ManualDraw(number), 0, board_client_id.as_deref())
         }
-        Ok(None) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Client not found in global registry"));
-            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Client not registered"));
+        (DrawMode::Random, Some(_)) => {
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, "This game draws numbers from the pouch: numbers can only be entered in manual draw mode"));
         }
-        Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to verify client: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client"));
+        (DrawMode::Manual, None) => {
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, "This game uses manual draw entry: the drawn number is required"));
+        }
+    };
+
+    // Extract a number using the game's coordinated extraction logic
+    match extraction {
+        Ok((extracted_number, _new_working_score)) => {
+            // Get current pouch and board state for response using Game methods
+            let numbers_remaining = game.pouch_length();
+            let total_extracted = game.board_length();
+
+            // Check if BINGO was reached after this extraction and dump game state if so
+            if game.is_bingo_reached() {
+                match game.dump_to_json() {
+                    Ok(dump_message) => {
+                        log(LogLevel::Info, MODULE_NAME, &format!("Game ended with BINGO! {dump_message}"));
+                    }
+                    Err(dump_error) => {
+                        log(LogLevel::Error, MODULE_NAME, &format!("Failed to dump game state: {dump_error}"));
+                    }
+                }
+            }
+            app_state.persistence.notify_change();
+
+            Ok(Json(json!({
+                "success": true,
+                "extracted_number": extracted_number,
+                "numbers_remaining": numbers_remaining,
+                "total_extracted": total_extracted,
+                "message": format!("Number {} extracted successfully", extracted_number)
+            })))
+        }
+        Err(error_msg) => {
+            // Handle extraction errors - match old behavior with proper status codes
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to extract number: {error_msg}"));
+            if error_msg.contains("empty") || error_msg.contains("not in the pouch") {
+                Err(ApiError::new(StatusCode::CONFLICT, error_msg))
+            } else {
+                Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error_msg))
+            }
+        }
+    }
+}
+
+pub async fn handle_claim(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanPlay>,
+    JsonExtractor(request): JsonExtractor<ClaimRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
+
+    let card_id = request.card_id;
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Claim request for game: {game_id}, card ID: {card_id}, prize: {}", request.prize));
+
+    if game.prize_mode() != PrizeMode::Claim {
+        return Err(ApiError::new(StatusCode::CONFLICT, "Prizes are awarded automatically in this game"));
+    }
+    if game.is_bingo_reached() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "Game over: no more prizes can be claimed"));
+    }
+
+    // Verify the card exists and belongs to the client
+    let card_owner = if let Ok(manager) = game.card_manager().lock() {
+        manager.get_card_assignment(&card_id).map(|assignment| assignment.client_id.clone())
+    } else {
+        None
+    };
+    match card_owner {
+        Some(owner) if owner == client_id && card_id != BOARD_ID => {}
+        Some(_) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Card {card_id} not assigned to client {client_id}"));
+            return Err(ApiError::new(StatusCode::FORBIDDEN, "Card not assigned to this client"));
+        }
+        None => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Card not found: {card_id}"));
+            return Err(ApiError::new(StatusCode::NOT_FOUND, "Card not found"));
+        }
+    }
+
+    let prize_name = if let Ok(scorecard) = game.scorecard().lock() {
+        if !scorecard.game_prizes().iter().any(|prize| prize.score == request.prize) {
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Unknown prize {}", request.prize)));
+        }
+        scorecard.prize_name(request.prize)
+    } else {
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to access scorecard"));
+    };
+
+    let verdict = match game.claim_prize(&client_id, &card_id, request.prize) {
+        Ok(verdict) => verdict,
+        Err(error_msg) => {
+            // Remaining errors come from cards suspended or disqualified by earlier false claims
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Claim rejected: {error_msg}"));
+            return Err(ApiError::new(StatusCode::FORBIDDEN, error_msg));
+        }
+    };
+    let draw_index = game.board_length();
+
+    let response = match verdict {
+        ClaimVerdict::Accepted(score) => {
+            let prize_name = game.scorecard().lock().map(|scorecard| scorecard.prize_name(score)).unwrap_or(prize_name);
+            log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Claim accepted: {prize_name} with card {card_id} at draw {draw_index}"));
+
+            // Dump the game state when the claim closes the game, as for automatic prizes
+            if game.is_bingo_reached() {
+                match game.dump_to_json() {
+                    Ok(dump_message) => {
+                        log(LogLevel::Info, MODULE_NAME, &format!("Game ended with BINGO! {dump_message}"));
+                    }
+                    Err(dump_error) => {
+                        log(LogLevel::Error, MODULE_NAME, &format!("Failed to dump game state: {dump_error}"));
+                    }
+                }
+            }
+
+            json!({
+                "verdict": "accepted",
+                "prize": prize_name,
+                "score": score,
+                "draw_index": draw_index,
+                "message": format!("{prize_name} confirmed for card {card_id}")
+            })
+        }
+        ClaimVerdict::Late => json!({
+            "verdict": "late",
+            "prize": prize_name,
+            "score": request.prize,
+            "draw_index": draw_index,
+            "message": format!("{prize_name} was reached before the last extraction, the claim is too late")
+        }),
+        ClaimVerdict::Unavailable => json!({
+            "verdict": "unavailable",
+            "prize": prize_name,
+            "score": request.prize,
+            "draw_index": draw_index,
+            "message": format!("{prize_name} is no longer available for card {card_id}")
+        }),
+        ClaimVerdict::False => {
+            let penalty = game.claim_penalty();
+            log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] False claim of {prize_name} with card {card_id}, penalty: {penalty:?}"));
+            json!({
+                "verdict": "false",
+                "prize": prize_name,
+                "score": request.prize,
+                "draw_index": draw_index,
+                "penalty": penalty,
+                "message": format!("Card {card_id} has not reached {prize_name}")
+            })
         }
+    };
+    app_state.persistence.notify_change();
+
+    Ok(Json(response))
+}
+
+pub async fn handle_global_newgame(
+    State(app_state): State<Arc<AppState>>,
+    auth: AuthClient,
+    request: Option<JsonExtractor<NewGameRequest>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let client_id = auth.client_id;
+
+    // Any authenticated client can create a game
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] New game request"));
+
+    // Use the requested ruleset (classic tombola by default) and board geometry (the ruleset's own by default)
+    let request = request.map(|JsonExtractor(request)| request).unwrap_or_default();
+    let ruleset = request.ruleset.unwrap_or_default();
+    let board_config = request.board_config.unwrap_or_else(|| ruleset.ruleset().default_board_config());
+    if let Err(e) = ruleset.ruleset().validate_board(&board_config) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Invalid board geometry: {e}"));
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid board geometry: {e}")));
     }
 
+    // Complete the unattended settings with those of server.conf
+    let autorun = match request.autorun.as_ref().map(|autorun| AutoRun::from_request(&app_state.config, autorun)).transpose() {
+        Ok(autorun) => autorun,
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Invalid unattended game settings: {e}"));
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid autorun: {e}")));
+        }
+    };
+
     // Create a completely new game
-    let new_game = Game::new();
+    let new_game = Game::with_rules(board_config, ruleset);
     let new_game_id = new_game.id();
     let new_game_created_at = new_game.created_at_string();
 
+    // Award more than one full-card prize (TOMBOLA, TOMBOLINO, ...) before closing the game when requested
+    if let Some(full_card_prizes) = request.full_card_prizes
+        && let Err(e) = new_game.set_full_card_prizes(full_card_prizes) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Invalid full-card prizes: {e}"));
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid full_card_prizes: {e}")));
+    }
+
+    // Let the players claim prizes instead of awarding them automatically when requested
+    if let Err(e) = new_game.set_prize_mode(request.prize_mode.unwrap_or_default(), request.false_claim_penalty.unwrap_or_default()) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set prize mode: {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set prize mode"));
+    }
+
+    // Let the board owner enter the numbers drawn from a physical basket when requested
+    if let Err(e) = new_game.set_draw_mode(request.draw_mode.unwrap_or_default()) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set draw mode: {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set draw mode"));
+    }
+
+    // Deal the cards from complete series when requested
+    if let Some(card_distribution) = request.card_distribution
+        && let Err(e) = new_game.set_card_distribution(card_distribution) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Invalid card distribution: {e}"));
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid card_distribution: {e}")));
+    }
+
+    // Let the server draw the numbers once the start conditions are met when requested
+    if let Some(autorun) = autorun
+        && let Err(e) = new_game.set_autorun(autorun) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Invalid unattended game: {e}"));
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid autorun: {e}")));
+    }
+
     // Set the game owner to the client who created it
     if let Err(e) = new_game.set_owner(&client_id) {
         log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set game owner: {e}"));
@@ -814,19 +1322,8 @@ pub async fn handle_global_newgame(
 
     log(LogLevel::Info, MODULE_NAME, &format!("Created new game: {}", new_game.game_info()));
 
-    // Add the new game to the registry
-    let new_game_arc = Arc::new(new_game.clone());
-    match app_state.game_registry.add_game(new_game_arc.clone()) {
-        Ok(registered_id) => {
-            log(LogLevel::Info, MODULE_NAME, &format!("Registered new game in registry: {registered_id}"));
-        }
-        Err(e) => {
-            log(LogLevel::Error, MODULE_NAME, &format!("Failed to register new game in registry: {e}"));
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to register new game: {e}")));
-        }
-    }
-
     // Register the game creator as the board owner by joining them to the game and assigning BOARD_ID card
+    let new_game_arc = Arc::new(new_game.clone());
     match new_game_arc.add_client(client_id.clone()) {
         Ok(_) => {
             log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Added as board owner to game {new_game_id}"));
@@ -837,26 +1334,39 @@ pub async fn handle_global_newgame(
         }
     }
 
-    // Set the client type as board for this game
-    if let Err(e) = new_game_arc.set_client_type(&client_id, "board") {
-        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set client type as board: {e}"));
-        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client type"));
+    // The creator owns the game
+    if let Err(e) = new_game_arc.set_role(&client_id, Role::Owner) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to set the owner role: {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client role for game"));
     }
 
     // Assign the special BOARD_ID card to make them the board owner
-    if let Ok(mut manager) = new_game_arc.card_manager().lock() {
-        // Assign the special board card (BOARD_ID) to the game creator
-        manager.assign_cards_with_type(&client_id, 1, Some("board"));
-        log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Assigned BOARD_ID card as game owner"));
-    } else {
-        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to assign BOARD_ID card"));
+    if let Err(e) = new_game_arc.assign_cards(&client_id, 1, Some(Role::Owner)) {
+        log(LogLevel::Error, MODULE_NAME, &format!("[Client: {client_id}] Failed to assign BOARD_ID card: {e}"));
         return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to assign board ownership"));
     }
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Assigned BOARD_ID card as game owner"));
+
+    // Add the new game to the registry once it has its owner, so that no other client ever sees it without one
+    match app_state.game_registry.add_game(new_game_arc.clone()) {
+        Ok(registered_id) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("Registered new game in registry: {registered_id}"));
+        }
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to register new game in registry: {e}"));
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to register new game: {e}")));
+        }
+    }
+
+    if new_game_arc.autorun().is_some() {
+        tokio::spawn(run_autorun(app_state.clone(), new_game_arc.clone()));
+    }
 
     // Note: The new game is created and registered in the registry, but the current AppState.game
     // still points to the old game. In a future implementation, we could enhance this to
     // switch the active game, but for now this creates a new game that can be accessed via
     // the /gameslist endpoint and potentially switched to in the future.
+    app_state.persistence.notify_change();
 
     Ok(Json(json!({
         "success": true,
@@ -864,47 +1374,26 @@ pub async fn handle_global_newgame(
         "game_id": new_game_id,
         "created_at": new_game_created_at,
         "board_owner": client_id,
+        "board_config": board_config,
+        "ruleset": ruleset,
+        "full_card_prizes": new_game.full_card_prizes(),
+        "prize_mode": new_game.prize_mode(),
+        "false_claim_penalty": new_game.claim_penalty(),
+        "draw_mode": new_game.draw_mode(),
+        "card_distribution": new_game.card_distribution(),
+        "autorun": new_game.autorun(),
         "note": "New game created and registered. Access it via /gameslist endpoint."
     })))
 }
 
 pub async fn handle_dumpgame(
-    State(app_state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
-    headers: HeaderMap,
+    access: Authorized<CanManage>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    // Check for client authentication header first
-    let client_id = if let Some(header_value) = headers.get("X-Client-ID") {
-        if let Ok(id) = header_value.to_str() { id } else {
-            log(LogLevel::Error, MODULE_NAME, "Invalid X-Client-ID header");
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid X-Client-ID header"));
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, "Missing X-Client-ID header");
-        return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Missing X-Client-ID header"));
-    };
+    let Authorized { client_id, game, .. } = access;
 
     log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Dump game request for game: {game_id}"));
 
-    let game = get_game_from_registry(&app_state, &game_id).await?;
-
-    // Only allow the board owner (client with BOARD_ID card assigned) to dump the game
-    let is_board_owner = if let Ok(manager) = game.card_manager().lock() {
-        if let Some(client_cards) = manager.get_client_cards(client_id) {
-            client_cards.contains(&BOARD_ID.to_string())
-        } else {
-            false
-        }
-    } else {
-        log(LogLevel::Error, MODULE_NAME, &format!("Failed to acquire card manager lock for client {client_id}"));
-        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify board ownership"));
-    };
-
-    if !is_board_owner {
-        log(LogLevel::Error, MODULE_NAME, &format!("Unauthorized: Only the board owner can dump the game, client ID: {client_id}"));
-        return Err(ApiError::new(StatusCode::FORBIDDEN, "Unauthorized: Only the board owner can dump the game"));
-    }
-
     // Dump the game state to JSON
     match game.dump_to_json() {
         Ok(dump_message) => {
@@ -925,6 +1414,55 @@ pub async fn handle_dumpgame(
     }
 }
 
+pub async fn handle_setrole(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    access: Authorized<CanManage>,
+    JsonExtractor(request): JsonExtractor<SetRoleRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
+    let SetRoleRequest { client_id: target_id, role } = request;
+
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Set role request for game: {game_id}, client: {target_id}, role: {role}"));
+
+    if !role.can_be_granted() {
+        log(LogLevel::Warning, MODULE_NAME, &format!("[Client: {client_id}] Cannot grant the {role} role"));
+        return Err(ApiError::new(StatusCode::FORBIDDEN, format!("The {role} role cannot be granted")));
+    }
+
+    match game.get_role(&target_id) {
+        Ok(Some(Role::Owner)) => {
+            return Err(ApiError::new(StatusCode::CONFLICT, "The role of the owner cannot be changed"));
+        }
+        // Spectators may have joined after the first extraction, they never take part in the game
+        Ok(Some(Role::Spectator)) => {
+            return Err(ApiError::new(StatusCode::CONFLICT, "Spectators cannot be granted a role"));
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(ApiError::new(StatusCode::NOT_FOUND, format!("Client '{target_id}' is not registered in game '{game_id}'")));
+        }
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to get the role of client {target_id}: {e}"));
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to get client role"));
+        }
+    }
+
+    if let Err(e) = game.set_role(&target_id, role) {
+        log(LogLevel::Error, MODULE_NAME, &format!("Failed to set the role of client {target_id}: {e}"));
+        return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to set client role for game"));
+    }
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Granted the {role} role to client {target_id} in game {game_id}"));
+    app_state.persistence.notify_change();
+
+    Ok(Json(json!({
+        "success": true,
+        "client_id": target_id,
+        "role": role,
+        "message": format!("Client {target_id} is now {role} of game {game_id}")
+    })))
+}
+
 pub async fn handle_global_gameslist(
     State(app_state): State<Arc<AppState>>,
     headers: HeaderMap,
@@ -1016,6 +1554,109 @@ pub async fn handle_global_gameslist(
     }
 }
 
+pub async fn handle_ws(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let game = get_game_from_registry(&app_state, &game_id).await?;
+
+    // Subscribe before the upgrade so that no update is lost while the connection is set up
+    let updates = game.subscribe();
+    log(LogLevel::Info, MODULE_NAME, &format!("WebSocket connection requested for game {game_id}"));
+
+    Ok(ws.on_upgrade(move |socket| forward_game_updates(socket, updates, game_id)))
+}
+
+// Push the live updates of a game to a WebSocket client until either side closes
+async fn forward_game_updates(mut socket: WebSocket, mut updates: broadcast::Receiver<GameUpdate>, game_id: String) {
+    loop {
+        tokio::select! {
+            update = updates.recv() => {
+                let update = match update {
+                    Ok(update) => update,
+                    Err(RecvError::Lagged(missed)) => {
+                        log(LogLevel::Warning, MODULE_NAME, &format!("WebSocket client of game {game_id} missed {missed} updates"));
+                        GameUpdate::Resync
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(text) = serde_json::to_string(&update) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                // Clients only listen; anything but a close frame is ignored
+                if matches!(message, None | Some(Err(_) | Ok(Message::Close(_)))) {
+                    break;
+                }
+            }
+        }
+    }
+
+    log(LogLevel::Info, MODULE_NAME, &format!("WebSocket connection closed for game {game_id}"));
+}
+
+pub async fn handle_events(
+    State(app_state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let game = get_game_from_registry(&app_state, &game_id).await?;
+
+    // Subscribe before reading the board so that no update falls between the missed and the live ones
+    let updates = game.subscribe();
+
+    // A reconnecting client gets the updates after the draw index of the last event it received
+    let last_event_id = headers.get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<usize>().ok());
+    let missed = match last_event_id {
+        Some(draw_index) => game.updates_since(draw_index)
+            .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .0,
+        None => Vec::new(),
+    };
+    log(LogLevel::Info, MODULE_NAME, &format!("Event stream opened for game {game_id} (last event ID {last_event_id:?}, {} missed updates)", missed.len()));
+
+    // Live updates already sent as missed ones are skipped
+    let live = stream::unfold((updates, missed.clone()), |(mut updates, mut sent)| async move {
+        loop {
+            let update = match updates.recv().await {
+                Ok(update) => update,
+                Err(RecvError::Lagged(_)) => GameUpdate::Resync,
+                Err(RecvError::Closed) => return None,
+            };
+            if matches!(update, GameUpdate::Undo { .. } | GameUpdate::Resync) {
+                sent.clear();
+            } else if let Some(position) = sent.iter().position(|missed| *missed == update) {
+                sent.remove(position);
+                continue;
+            }
+            return Some((update, (updates, sent)));
+        }
+    });
+
+    let events = stream::iter(missed)
+        .chain(live)
+        .map(|update| Ok(sse_event(&update)));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+// Server-sent event for a game update, with the draw index as event ID for the Last-Event-ID resume
+fn sse_event(update: &GameUpdate) -> Event {
+    let event = Event::default()
+        .event(update.kind())
+        .data(serde_json::to_string(update).unwrap_or_default());
+    match update.draw_index() {
+        Some(draw_index) => event.id(draw_index.to_string()),
+        None => event,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1023,6 +1664,9 @@ mod tests {
     use crate::server::AppState;
     use crate::client::RegisterRequest;
     use crate::card::GenerateCardsRequest;
+    use crate::ruleset::RulesetKind;
+    use crate::defs::Number;
+    use crate::role::Permission;
     use axum::extract::{State, Query, Path};
     use axum::Json as JsonExtractor;
     use std::sync::Arc;
@@ -1030,14 +1674,18 @@ mod tests {
 
     // Helper function to create test app state
     fn create_test_app_state() -> Arc<AppState> {
+        create_test_app_state_with_config(ServerConfig::default())
+    }
+
+    fn create_test_app_state_with_config(config: ServerConfig) -> Arc<AppState> {
         use std::sync::Arc;
         use crate::client::ClientRegistry;
 
-        let config = ServerConfig::default();
         let game_registry = crate::game::GameRegistry::new();
         Arc::new(AppState {
             game_registry,
             global_client_registry: ClientRegistry::new(),
+            persistence: crate::persistence::Persistence::new(Box::new(crate::store::MemoryStore::new())),
             config
         })
     }
@@ -1048,29 +1696,90 @@ mod tests {
         game_id
     }
 
+    // Token of a registered client, issuing its API key if it has none
+    fn client_test_token(app_state: &AppState, client_id: &str) -> String {
+        let mut client_info = app_state.global_client_registry.get(client_id).unwrap().expect("Client not registered");
+        if client_info.api_key_hash.is_none() {
+            (_, client_info) = app_state.global_client_registry.issue_api_key(client_id).unwrap().unwrap();
+        }
+        issue_token(&client_info, &app_state.config).unwrap()
+    }
+
+    // Client ID listed in the admins of the test servers
+    const TEST_ADMIN_ID: &str = "00000000000000AD";
+
+    // Register the admin of the test servers under a name, returning its client ID
+    fn register_test_admin(app_state: &AppState, name: &str) -> String {
+        let admin = ClientInfo { id: TEST_ADMIN_ID.to_string(), ..ClientInfo::new(name, "player", "") };
+        app_state.global_client_registry.insert(admin).unwrap();
+        TEST_ADMIN_ID.to_string()
+    }
+
+    // Authenticate a registered client with a token, as the AuthClient extractor does
+    fn client_auth(app_state: &AppState, client_id: &str) -> AuthClient {
+        AuthClient::from_token(app_state, &client_test_token(app_state, client_id)).unwrap()
+    }
+
+    // Headers of a request sending the token of a registered client
+    fn client_headers(app_state: &AppState, client_id: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {}", client_test_token(app_state, client_id)).parse().unwrap());
+        headers
+    }
+
+    // Authorize a client for an action in a game, as the Authorized extractor does
+    async fn authorized<P: Permission>(app_state: &Arc<AppState>, game_id: &str, client_id: &str) -> Result<Authorized<P>, ApiError> {
+        Authorized::authorize(app_state, client_auth(app_state, client_id), game_id).await
+    }
+
+    // Serve the routes acting on behalf of a client and return the address of the server
+    async fn serve_authenticated_routes(app_state: &Arc<AppState>) -> std::net::SocketAddr {
+        let router = axum::Router::new()
+            .route("/register", axum::routing::post(handle_global_register))
+            .route("/newgame", axum::routing::post(handle_global_newgame))
+            .route("/{game_id}/join", axum::routing::post(handle_join))
+            .route("/{game_id}/generatecards", axum::routing::post(handle_generatecards))
+            .route("/{game_id}/listassignedcards", axum::routing::get(handle_listassignedcards))
+            .route("/{game_id}/extract", axum::routing::post(handle_extract))
+            .route("/{game_id}/dumpgame", axum::routing::post(handle_dumpgame))
+            .with_state(app_state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        address
+    }
+
+    async fn assert_unauthenticated(response: reqwest::Response) {
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let error: serde_json::Value = response.json().await.unwrap();
+        assert!(error["error"].as_str().unwrap().contains("bearer token"));
+    }
+
     // Helper function to create a test game with board client and return both game ID and board client ID
     async fn create_test_game_with_board_client(app_state: &Arc<AppState>) -> (String, String) {
-        // First, register a board client globally
+        // First, register a board client globally, sending its token if it is already registered
         let board_register_request = RegisterRequest {
             name: "TestBoard".to_string(),
             client_type: "board".to_string(),
             nocard: Some(0),
             email: None,
+            allow_duplicate_name: false,
+        };
+        let headers = match app_state.global_client_registry.get_by_name("TestBoard").unwrap() {
+            Some(board_client) => client_headers(app_state, &board_client.id),
+            None => HeaderMap::new(),
         };
 
         let global_register_result = handle_global_register(
             State(app_state.clone()),
-            HeaderMap::new(),
+            headers,
             JsonExtractor(board_register_request),
         ).await.unwrap();
 
         let board_client_id = global_register_result.0.client_id.clone();
 
         // Use the board client to create a new game via API
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
-        let newgame_result = handle_global_newgame(State(app_state.clone()), board_headers).await;
+        let newgame_result = handle_global_newgame(State(app_state.clone()), client_auth(app_state, &board_client_id), None).await;
         match newgame_result {
             Ok(response) => {
                 let game_id = response["game_id"].as_str().unwrap().to_string();
@@ -1093,10 +1802,11 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game_id = create_test_game(app_state).await; // Create a test game
-        let result = handle_join(Path(game_id), State(app_state.clone()), JsonExtractor(request)).await;
+        let result = handle_join(Path(game_id), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
         match result {
             Ok(response) => response.0.client_id,
             Err(_) => panic!("Failed to register test client"),
@@ -1110,9 +1820,10 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
-        let result = handle_join(Path(game_id.to_string()), State(app_state.clone()), JsonExtractor(request)).await;
+        let result = handle_join(Path(game_id.to_string()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
         match result {
             Ok(response) => response.0.client_id,
             Err(_) => panic!("Failed to register test client to game"),
@@ -1127,10 +1838,11 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(2),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game_id = create_test_game(&app_state).await;
-        let result = handle_join(Path(game_id.clone()), State(app_state.clone()), JsonExtractor(request)).await;
+        let result = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1146,22 +1858,114 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game_id = create_test_game(&app_state).await;
 
         // Register the client first time
-        let first_result = handle_join(Path(game_id.clone()), State(app_state.clone()), JsonExtractor(request.clone())).await;
+        let first_result = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request.clone())).await;
         assert!(first_result.is_ok());
         let first_response = first_result.unwrap();
 
-        // Try to register the same client again
-        let second_result = handle_join(Path(game_id.clone()), State(app_state.clone()), JsonExtractor(request)).await;
+        // The name cannot be taken over without the credential of the client
+        let error = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request.clone())).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+        let other_client_id = register_test_client_to_game(&app_state, "other_player", &game_id).await;
+        let error = handle_join(Path(game_id.clone()), State(app_state.clone()), client_headers(&app_state, &other_client_id), JsonExtractor(request.clone())).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        // Try to register the same client again with its API key
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {}", first_response.api_key.clone().unwrap()).parse().unwrap());
+        let second_result = handle_join(Path(game_id.clone()), State(app_state.clone()), headers, JsonExtractor(request.clone())).await;
         assert!(second_result.is_ok());
         let second_response = second_result.unwrap();
 
         assert_eq!(first_response.client_id, second_response.client_id);
         assert_eq!(second_response.message, format!("Client 'existing_player' already registered in game '{game_id}'"));
+
+        // The API key is only returned when it is issued
+        assert!(first_response.api_key.is_some());
+        assert!(second_response.api_key.is_none());
+
+        // Sharing the display name is an explicit choice, and makes another client
+        let request = RegisterRequest { allow_duplicate_name: true, ..request };
+        let third_response = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await.unwrap();
+        assert_ne!(third_response.client_id, first_response.client_id);
+        assert!(third_response.api_key.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_register_taken_name_without_credential() {
+        let app_state = create_test_app_state();
+        let game_id = get_test_game_id(&app_state).await;
+        let client_id = register_test_client_to_game(&app_state, "taken_name", &game_id).await;
+        let client_info = app_state.global_client_registry.get(&client_id).unwrap().unwrap();
+        let client_count = app_state.global_client_registry.len().unwrap();
+        let address = serve_authenticated_routes(&app_state).await;
+
+        // Neither the name nor the public client ID gets a credential of the client
+        let request = json!({ "name": "taken_name", "client_type": "player" });
+        for url in [format!("http://{address}/register"), format!("http://{address}/{game_id}/join")] {
+            for claimed_id in [None, Some(&client_id)] {
+                let mut builder = reqwest::Client::new().post(&url).json(&request);
+                if let Some(claimed_id) = claimed_id {
+                    builder = builder.header("X-Client-ID", claimed_id);
+                }
+                let response = builder.send().await.unwrap();
+                assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+                let body: serde_json::Value = response.json().await.unwrap();
+                assert!(body.get("token").is_none() && body.get("api_key").is_none() && body.get("client_id").is_none());
+            }
+        }
+
+        // No client was added and the key of the client is unchanged
+        assert_eq!(app_state.global_client_registry.len().unwrap(), client_count);
+        assert_eq!(app_state.global_client_registry.get(&client_id).unwrap().unwrap().api_key_hash, client_info.api_key_hash);
+    }
+
+    #[tokio::test]
+    async fn test_handle_rotatekey_and_revokekey() {
+        let app_state = create_test_app_state();
+        let request = RegisterRequest {
+            name: "key_holder".to_string(),
+            client_type: "player".to_string(),
+            nocard: None,
+            email: None,
+            allow_duplicate_name: false,
+        };
+        let registered = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await.unwrap();
+        let api_key = registered.api_key.clone().expect("API key issued at registration");
+
+        // The server keeps the hash of the key, not the key
+        let client_info = app_state.global_client_registry.get(&registered.client_id).unwrap().unwrap();
+        assert_eq!(client_info.api_key_hash, Some(crate::auth::hash_api_key(&api_key)));
+        assert!(!serde_json::to_string(&client_info).unwrap().contains(&api_key));
+
+        // Rotating issues a new key and invalidates the previous credentials
+        let auth = AuthClient::from_credential(&app_state, &api_key).unwrap();
+        let rotated = handle_rotatekey(State(app_state.clone()), auth).await.unwrap();
+        let new_key = rotated.api_key.clone().unwrap();
+        assert_ne!(new_key, api_key);
+        assert_eq!(AuthClient::from_credential(&app_state, &api_key).unwrap_err().status, StatusCode::UNAUTHORIZED);
+        assert_eq!(AuthClient::from_credential(&app_state, &registered.token).unwrap_err().status, StatusCode::UNAUTHORIZED);
+        assert!(AuthClient::from_credential(&app_state, &rotated.token).is_ok());
+
+        // Revoking invalidates the key and its tokens
+        let auth = AuthClient::from_credential(&app_state, &new_key).unwrap();
+        let _ = handle_revokekey(State(app_state.clone()), auth).await.unwrap();
+        assert_eq!(AuthClient::from_credential(&app_state, &new_key).unwrap_err().status, StatusCode::UNAUTHORIZED);
+        assert_eq!(AuthClient::from_credential(&app_state, &rotated.token).unwrap_err().status, StatusCode::UNAUTHORIZED);
+
+        // Revocation is permanent, the name cannot be registered again without a credential
+        let request = RegisterRequest { name: "key_holder".to_string(), client_type: "player".to_string(), nocard: None, email: None, allow_duplicate_name: false };
+        let error = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        // The public client ID still identifies the client
+        let client_info = handle_global_clientinfo_by_id(State(app_state.clone()), Path(registered.client_id.clone())).await.unwrap();
+        assert_eq!(client_info.name, "key_holder");
     }
 
     #[tokio::test]
@@ -1171,14 +1975,12 @@ mod tests {
         // Start the game by extracting a number through the API
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         let _ = handle_extract(
             State(app_state.clone()),
             Path(game_id.clone()),
-            board_headers,
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         let request = RegisterRequest {
@@ -1186,9 +1988,10 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
-        let result = handle_join(Path(game_id), State(app_state.clone()), JsonExtractor(request)).await;
+        let result = handle_join(Path(game_id), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -1265,6 +2068,7 @@ mod tests {
             client_type: "board".to_string(),
             nocard: Some(0),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let register_result = handle_global_register(
@@ -1316,24 +2120,25 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0), // No cards during registration
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game_id = create_test_game(&app_state).await;
-        let register_result = handle_join(Path(game_id.clone()), State(app_state.clone()), JsonExtractor(register_request)).await;
+        let register_result = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(register_request)).await;
         assert!(register_result.is_ok());
         let client_id = register_result.unwrap().0.client_id;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap());
-
         let request = GenerateCardsRequest { count: 3 };
 
-        let result = handle_generatecards(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            JsonExtractor(request),
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_generatecards(
+                State(app_state.clone()),
+                Path(game_id),
+                access,
+                JsonExtractor(request),
+            ).await
+        }.await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1345,43 +2150,34 @@ mod tests {
     async fn test_handle_generate_cards_missing_client_id() {
         let app_state = create_test_app_state();
         let game_id = get_test_game_id(&app_state).await;
-        let headers = HeaderMap::new(); // No X-Client-ID header
-
-        let request = GenerateCardsRequest { count: 1 };
-
-        let result = handle_generatecards(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            JsonExtractor(request),
-        ).await;
-
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::BAD_REQUEST);
-        assert!(error.message.contains("Client ID header (X-Client-ID) is required"));
+        let client_id = register_test_client_to_game(&app_state, "cards_without_token", &game_id).await;
+        let address = serve_authenticated_routes(&app_state).await;
+
+        // The client ID alone is not accepted
+        let response = reqwest::Client::new()
+            .post(format!("http://{address}/{game_id}/generatecards"))
+            .header("X-Client-ID", client_id)
+            .json(&json!({ "count": 1 }))
+            .send().await.unwrap();
+        assert_unauthenticated(response).await;
     }
 
     #[tokio::test]
     async fn test_handle_generate_cards_unregistered_client() {
         let app_state = create_test_app_state();
         let game_id = get_test_game_id(&app_state).await;
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", "invalid_client_id".parse().unwrap());
-
-        let request = GenerateCardsRequest { count: 1 };
-
-        let result = handle_generatecards(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            JsonExtractor(request),
-        ).await;
-
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
-        assert!(error.message.contains("Client not registered"));
+        let address = serve_authenticated_routes(&app_state).await;
+
+        // A valid token of a client unknown to the server
+        let token = issue_token(&ClientInfo::new("unregistered", "player", ""), &app_state.config).unwrap();
+        let response = reqwest::Client::new()
+            .post(format!("http://{address}/{game_id}/generatecards"))
+            .bearer_auth(token)
+            .json(&json!({ "count": 1 }))
+            .send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let error: serde_json::Value = response.json().await.unwrap();
+        assert!(error["error"].as_str().unwrap().contains("Client not registered"));
     }
 
     #[tokio::test]
@@ -1390,15 +2186,14 @@ mod tests {
         let game_id = get_test_game_id(&app_state).await;
         let client_id = register_test_client_to_game(&app_state, "list_test_player", &game_id).await;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_listassignedcards(
+                Path(game_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1410,19 +2205,10 @@ mod tests {
     async fn test_handle_list_assigned_cards_missing_client_id() {
         let app_state = create_test_app_state();
         let game_id = get_test_game_id(&app_state).await;
-        let headers = HeaderMap::new(); // No X-Client-ID header
-
-        let result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let address = serve_authenticated_routes(&app_state).await;
 
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::BAD_REQUEST);
-        assert!(error.message.contains("Client ID header (X-Client-ID) is required"));
+        let response = reqwest::get(format!("http://{address}/{game_id}/listassignedcards")).await.unwrap();
+        assert_unauthenticated(response).await;
     }
 
     #[tokio::test]
@@ -1431,16 +2217,15 @@ mod tests {
         let game_id = get_test_game_id(&app_state).await;
         let client_id = register_test_client_to_game(&app_state, "get_card_test_player", &game_id).await;
 
-        // Get the assigned card ID
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let list_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        // Get the assigned card ID
+        let list_result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_listassignedcards(
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(list_result.is_ok());
         let list_response = list_result.unwrap();
@@ -1448,11 +2233,13 @@ mod tests {
 
         let card_id = &list_response.0.cards[0].card_id;
 
-        let result = handle_getassignedcard(
-            State(app_state.clone()),
-            Path((game_id, card_id.clone())),
-            headers,
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_getassignedcard(
+                Path((game_id, card_id.clone())),
+                access,
+            ).await
+        }.await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1465,14 +2252,13 @@ mod tests {
         let game_id = get_test_game_id(&app_state).await;
         let client_id = register_test_client_to_game(&app_state, "get_card_test_player", &game_id).await;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let result = handle_getassignedcard(
-            State(app_state.clone()),
-            Path((game_id, "nonexistent_card_id".to_string())),
-            headers,
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_getassignedcard(
+                Path((game_id, "nonexistent_card_id".to_string())),
+                access,
+            ).await
+        }.await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -1506,21 +2292,20 @@ mod tests {
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
         // Extract some numbers using the proper API handler with the actual board client ID
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         let _ = handle_extract(
             State(app_state.clone()),
             Path(game_id.clone()),
-            headers.clone(),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         let _ = handle_extract(
             State(app_state.clone()),
             Path(game_id.clone()),
-            headers,
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         let result = handle_board(
@@ -1562,14 +2347,12 @@ mod tests {
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
         // Extract a number through the API
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         let _ = handle_extract(
             State(app_state.clone()),
             Path(game_id.clone()),
-            board_headers,
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         let result = handle_pouch(
@@ -1625,6 +2408,70 @@ mod tests {
         // Note: server field was removed from new implementation, so don't check it
     }
 
+    #[tokio::test]
+    async fn test_handle_join_ownerless_game_as_board() {
+        let app_state = create_test_app_state();
+        // Like the unattended games of server.conf, the game has no owner
+        let game = Arc::new(Game::new());
+        app_state.game_registry.add_game(game.clone()).unwrap();
+
+        for (name, client_type) in [("board_joiner", "board"), ("owner_joiner", "owner")] {
+            let request = RegisterRequest {
+                name: name.to_string(),
+                client_type: client_type.to_string(),
+                nocard: Some(1),
+                email: None,
+                allow_duplicate_name: false,
+            };
+            let client_id = handle_join(Path(game.id()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await.unwrap().0.client_id;
+            assert_eq!(game.get_role(&client_id).unwrap(), Some(Role::Player));
+        }
+        assert_eq!(game.owner(), None);
+        assert!(!game.has_board_owner());
+    }
+
+    #[tokio::test]
+    async fn test_handle_join_as_spectator() {
+        let app_state = create_test_app_state();
+        let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
+        register_test_client_to_game(&app_state, "spectated_player", &game_id).await;
+        let _ = handle_extract(
+            State(app_state.clone()),
+            Path(game_id.clone()),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
+            Query(ClientIdQuery { client_id: None }),
+            None,
+        ).await.unwrap();
+
+        // Players cannot join once a number is drawn, spectators can and get no cards
+        let join = |name: &str, client_type: &str| handle_join(
+            Path(game_id.clone()),
+            State(app_state.clone()),
+            HeaderMap::new(),
+            JsonExtractor(RegisterRequest { name: name.to_string(), client_type: client_type.to_string(), nocard: Some(3), email: None, allow_duplicate_name: false }),
+        );
+        assert_eq!(join("late_player", "player").await.unwrap_err().status, StatusCode::CONFLICT);
+        let spectator_id = join("spectator", "spectator").await.unwrap().0.client_id;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        assert!(game.card_manager().lock().unwrap().get_client_cards(&spectator_id).is_none());
+
+        // The spectator is not counted as a player, and reads the game without playing
+        let status = handle_status(State(app_state.clone()), Path(game_id.clone()), HeaderMap::new(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!((status["players"].as_str(), status["spectators"].as_str()), (Some("2"), Some("1")));
+        let players = handle_players(Path(game_id.clone()), authorized(&app_state, &game_id, &spectator_id).await.unwrap()).await.unwrap();
+        assert_eq!(players["total_players"], 2);
+        assert_eq!(players["spectators"], json!([spectator_id]));
+        assert!(players["players"].as_array().unwrap().iter().all(|player| player["client_id"] != spectator_id.as_str()));
+        assert!(authorized::<CanPlay>(&app_state, &game_id, &spectator_id).await.is_err());
+        assert!(authorized::<CanReadCards>(&app_state, &game_id, &spectator_id).await.is_err());
+
+        // Spectators cannot be turned into players or callers
+        let request = SetRoleRequest { client_id: spectator_id, role: Role::Caller };
+        let access = authorized(&app_state, &game_id, &board_client_id).await.unwrap();
+        let error = handle_setrole(State(app_state.clone()), Path(game_id), access, JsonExtractor(request)).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn test_game_owner_in_status_endpoint() {
         let app_state = create_test_app_state();
@@ -1637,14 +2484,12 @@ mod tests {
             client_type: "player".to_string(),
             registered_at: std::time::SystemTime::now(),
             email: "test@example.com".to_string(),
+            api_key_hash: None,
         };
         app_state.global_client_registry.insert(test_client_info).unwrap();
 
         // Create a new game with this client
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", test_client_id.parse().unwrap());
-
-        let newgame_result = handle_global_newgame(State(app_state.clone()), headers.clone()).await;
+        let newgame_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, test_client_id), None).await;
         assert!(newgame_result.is_ok());
 
         let newgame_response = newgame_result.unwrap();
@@ -1672,15 +2517,16 @@ mod tests {
         let app_state = create_test_app_state();
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", board_client_id.parse().unwrap()); // Board client
-
-        let result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &board_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1690,45 +2536,71 @@ mod tests {
         assert_eq!(response.0["total_extracted"], 1);
     }
 
+    #[tokio::test]
+    async fn test_handle_extract_by_caller_credits_board_to_owner() {
+        let app_state = create_test_app_state();
+        let (game_id, owner_id) = create_test_game_with_board_client(&app_state).await;
+        let request = RegisterRequest {
+            name: "cardless_caller".to_string(),
+            client_type: "player".to_string(),
+            nocard: Some(0),
+            email: None,
+            allow_duplicate_name: false,
+        };
+        let caller_id = handle_join(Path(game_id.clone()), State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await.unwrap().0.client_id;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        game.set_role(&caller_id, Role::Caller).unwrap();
+
+        // Only the board card plays, and its prizes go to the owner although the caller draws
+        while !game.is_bingo_reached() {
+            let access = authorized(&app_state, &game_id, &caller_id).await.unwrap();
+            let response = handle_extract(State(app_state.clone()), Path(game_id.clone()), access, Query(ClientIdQuery { client_id: None }), None).await.unwrap();
+            assert_eq!(response["success"], true);
+        }
+        let scorecard = game.scorecard().lock().unwrap();
+        assert!(!scorecard.score_map.is_empty());
+        for achievement in scorecard.score_map.values().flatten() {
+            assert_eq!(achievement.card_id, BOARD_ID);
+            assert_eq!(achievement.client_id, owner_id);
+        }
+    }
+
     #[tokio::test]
     async fn test_handle_extract_unauthorized() {
         let app_state = create_test_app_state();
         let game_id = get_test_game_id(&app_state).await;
         let client_id = register_test_client_to_game(&app_state, "extract_test_player", &game_id).await;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap()); // Regular client, not board
-
-        let result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status, StatusCode::FORBIDDEN);
-        assert!(error.message.contains("Unauthorized: Only the board owner can extract numbers"));
+        assert!(error.message.contains("Unauthorized: the player role cannot draw numbers"));
     }
 
     #[tokio::test]
     async fn test_handle_extract_missing_client_id() {
         let app_state = create_test_app_state();
-        let game_id = get_test_game_id(&app_state).await;
-        let headers = HeaderMap::new(); // No X-Client-ID header
-
-        let result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id),
-            headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
-
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::BAD_REQUEST);
-        assert!(error.message.contains("Client ID header (X-Client-ID) is required"));
+        let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
+        let address = serve_authenticated_routes(&app_state).await;
+
+        // The public ID of the board owner, as listed by /players, cannot be replayed
+        let response = reqwest::Client::new()
+            .post(format!("http://{address}/{game_id}/extract"))
+            .header("X-Client-ID", board_client_id)
+            .send().await.unwrap();
+        assert_unauthenticated(response).await;
+        assert_eq!(app_state.game_registry.get_game(&game_id).unwrap().unwrap().board_length(), 0);
     }
 
     #[tokio::test]
@@ -1736,23 +2608,21 @@ mod tests {
         let app_state = create_test_app_state();
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", board_client_id.parse().unwrap()); // Board client
-
         // Register a client and extract some numbers to have game state through API
         let _ = register_test_client(&app_state, "newgame_test_player").await;
 
         let _ = handle_extract(
             State(app_state.clone()),
-            Path(game_id),
-            headers.clone(),
+            Path(game_id.clone()),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         // Get initial game count
         let initial_count = app_state.game_registry.total_games().unwrap();
 
-        let result = handle_global_newgame(State(app_state.clone()), headers).await;
+        let result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &board_client_id), None).await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1772,24 +2642,429 @@ mod tests {
     async fn test_handle_newgame_unauthorized() {
         let app_state = create_test_app_state();
 
-        // Test without any client ID header (unauthenticated)
-        let headers = HeaderMap::new(); // No X-Client-ID header
+        let address = serve_authenticated_routes(&app_state).await;
+
+        // Test without any token (unauthenticated)
+        let response = reqwest::Client::new().post(format!("http://{address}/newgame")).send().await.unwrap();
+        assert_unauthenticated(response).await;
+        assert_eq!(app_state.game_registry.total_games().unwrap(), 0);
+    }
 
-        let result = handle_global_newgame(State(app_state.clone()), headers).await;
+    #[tokio::test]
+    async fn test_handle_newgame_with_board_config() {
+        let app_state = create_test_app_state();
+        let client_id = register_test_client(&app_state, "geometry_owner").await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Client-ID", client_id.parse().unwrap());
+
+        let board_config = crate::defs::BoardStruct {
+            cols_per_card: 4,
+            ..crate::defs::BOARDCONFIG
+        };
+        let request = NewGameRequest { board_config: Some(board_config), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["board_config"]["cols_per_card"], 4);
+        assert_eq!(response["ruleset"], "tombola");
+
+        // The game uses its own geometry for the pouch and in the status endpoint
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        assert_eq!(game.pouch_length(), 72);
+        let status = handle_status(State(app_state.clone()), Path(game_id.clone()), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(status["board_config"]["cols_per_card"], 4);
+
+        // Player cards follow the geometry as well
+        let player_id = register_test_client_to_game(&app_state, "geometry_player", &game_id).await;
+        let manager = game.card_manager().lock().unwrap();
+        let card_id = &manager.get_client_cards(&player_id).unwrap()[0];
+        let card = &manager.get_card_assignment(card_id).unwrap().card_data;
+        assert_eq!(card.len(), 3);
+        assert!(card.iter().all(|row| row.len() == 7 && row.iter().flatten().count() == 4));
+    }
+
+    #[tokio::test]
+    async fn test_handle_newgame_invalid_board_config() {
+        let app_state = create_test_app_state();
+        let client_id = register_test_client(&app_state, "bad_geometry_owner").await;
+        let initial_count = app_state.game_registry.total_games().unwrap();
+
+        let request = NewGameRequest {
+            board_config: Some(crate::defs::BoardStruct { rows_per_card: 0, ..crate::defs::BOARDCONFIG }),
+            ..Default::default()
+        };
+        let result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await;
 
-        assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status, StatusCode::BAD_REQUEST);
-        assert!(error.message.contains("Client ID header (X-Client-ID) is required"));
+        assert!(error.message.contains("Invalid board geometry"));
+        assert_eq!(app_state.game_registry.total_games().unwrap(), initial_count);
     }
 
     #[tokio::test]
-    async fn test_handle_dumpgame_success() {
+    async fn test_handle_newgame_with_housie_ruleset() {
+        let app_state = create_test_app_state();
+        let client_id = register_test_client(&app_state, "housie_owner").await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Client-ID", client_id.parse().unwrap());
+
+        let request = NewGameRequest { ruleset: Some(RulesetKind::Housie), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["ruleset"], "housie");
+
+        // A complete row on a player card wins ONE LINE
+        let player_id = register_test_client_to_game(&app_state, "housie_player", &game_id).await;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        let (card_id, first_row) = {
+            let manager = game.card_manager().lock().unwrap();
+            let card_id = manager.get_client_cards(&player_id).unwrap()[0].clone();
+            let first_row: Vec<Number> = manager.get_card_assignment(&card_id).unwrap().card_data[0].iter().flatten().copied().collect();
+            (card_id, first_row)
+        };
+        {
+            let mut board = game.board().lock().unwrap();
+            let mut scorecard = game.scorecard().lock().unwrap();
+            let manager = game.card_manager().lock().unwrap();
+            for &number in &first_row {
+                board.push(number, &scorecard);
+            }
+            scorecard.calculate_and_update_best_score(&board, &manager, 0, Some(&client_id));
+        }
+
+        let scoremap = handle_scoremap(State(app_state.clone()), Path(game_id.clone()), headers, Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(scoremap["ruleset"], "housie");
+        assert_eq!(scoremap["prizes"][0]["name"], "ONE LINE");
+        assert_eq!(scoremap["prizes"][2]["name"], "FULL HOUSE");
+        assert_eq!(scoremap["published_score"], 1);
+        assert_eq!(scoremap["score_map"]["1"][0]["card_id"], card_id.as_str());
+        assert!(!game.is_bingo_reached());
+    }
+
+    #[tokio::test]
+    async fn test_handle_newgame_with_bingo75_ruleset() {
+        let app_state = create_test_app_state();
+        let client_id = register_test_client(&app_state, "bingo75_owner").await;
+
+        let request = NewGameRequest { ruleset: Some(RulesetKind::Bingo75), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["ruleset"], "bingo75");
+
+        // The game uses the 75-ball board and 5x5 player cards with a free centre
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        assert_eq!(game.pouch_length(), 75);
+        let player_id = register_test_client_to_game(&app_state, "bingo75_player", &game_id).await;
+        {
+            let manager = game.card_manager().lock().unwrap();
+            let card_id = &manager.get_client_cards(&player_id).unwrap()[0];
+            let card = &manager.get_card_assignment(card_id).unwrap().card_data;
+            assert_eq!(card.len(), 5);
+            assert_eq!(card[2][2], None);
+        }
+
+        // A tombola geometry cannot host a 75-ball game
+        let request = NewGameRequest {
+            board_config: Some(crate::defs::BOARDCONFIG),
+            ruleset: Some(RulesetKind::Bingo75),
+            ..Default::default()
+        };
+        let error = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_newgame_with_full_card_prizes() {
+        let app_state = create_test_app_state();
+        let client_id = register_test_client(&app_state, "tombolino_owner").await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Client-ID", client_id.parse().unwrap());
+
+        let request = NewGameRequest { full_card_prizes: Some(2), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["full_card_prizes"], 2);
+
+        let status = handle_status(State(app_state.clone()), Path(game_id.clone()), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(status["full_card_prizes"], 2);
+        let scoremap = handle_scoremap(State(app_state.clone()), Path(game_id), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(scoremap["prizes"][5]["score"], 16);
+        assert_eq!(scoremap["prizes"][5]["name"], "TOMBOLINO");
+
+        let request = NewGameRequest { full_card_prizes: Some(0), ..Default::default() };
+        let error = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), Some(JsonExtractor(request))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_claim() {
+        let app_state = create_test_app_state();
+        let owner_id = register_test_client(&app_state, "claim_owner").await;
+
+        let request = NewGameRequest { prize_mode: Some(PrizeMode::Claim), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["prize_mode"], "claim");
+
+        let player_id = register_test_client_to_game(&app_state, "claim_player", &game_id).await;
+        let mut player_headers = HeaderMap::new();
+        player_headers.insert("X-Client-ID", player_id.parse().unwrap());
+
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        let (card_id, first_row): (String, Vec<Number>) = {
+            let manager = game.card_manager().lock().unwrap();
+            let card_id = manager.get_client_cards(&player_id).unwrap()[0].clone();
+            let first_row = manager.get_card_assignment(&card_id).unwrap().card_data[0].iter().flatten().copied().collect();
+            (card_id, first_row)
+        };
+
+        let claim = |card_id: &str, prize: Number| ClaimRequest { card_id: card_id.to_string(), prize };
+        let response = handle_claim(State(app_state.clone()), Path(game_id.clone()), authorized(&app_state, &game_id, &player_id).await.unwrap(), JsonExtractor(claim(&card_id, 2))).await.unwrap();
+        assert_eq!(response["verdict"], "false");
+        assert_eq!(response["prize"], "AMBO");
+
+        {
+            let mut board = game.board().lock().unwrap();
+            let scorecard = game.scorecard().lock().unwrap();
+            board.push(first_row[0], &scorecard);
+            board.push(first_row[1], &scorecard);
+        }
+        let response = handle_claim(State(app_state.clone()), Path(game_id.clone()), authorized(&app_state, &game_id, &player_id).await.unwrap(), JsonExtractor(claim(&card_id, 2))).await.unwrap();
+        assert_eq!(response["verdict"], "accepted");
+        assert_eq!(response["draw_index"], 2);
+        let scoremap = handle_scoremap(State(app_state.clone()), Path(game_id.clone()), player_headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(scoremap["score_map"]["2"][0]["draw_index"], 2);
+
+        // Unknown prizes, cards of other clients and the board card are rejected
+        let error = async {
+            let access = authorized(&app_state, &game_id, &player_id).await?;
+            handle_claim(State(app_state.clone()), Path(game_id.clone()), access, JsonExtractor(claim(&card_id, 42))).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        let error = async {
+            let access = authorized(&app_state, &game_id, &owner_id).await?;
+            handle_claim(State(app_state.clone()), Path(game_id.clone()), access, JsonExtractor(claim(&card_id, 3))).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        let error = async {
+            let access = authorized(&app_state, &game_id, &owner_id).await?;
+            handle_claim(State(app_state.clone()), Path(game_id), access, JsonExtractor(claim(BOARD_ID, 3))).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+
+        // Games with automatic prizes do not accept claims
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), None).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        let player_id = register_test_client_to_game(&app_state, "automatic_prize_player", &game_id).await;
+        let error = async {
+            let access = authorized(&app_state, &game_id, &player_id).await?;
+            handle_claim(State(app_state.clone()), Path(game_id), access, JsonExtractor(claim(BOARD_ID, 2))).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_handle_verify() {
         let app_state = create_test_app_state();
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
         let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", board_client_id.parse().unwrap()); // Board client
+        headers.insert("X-Client-ID", board_client_id.parse().unwrap());
+
+        let status = handle_status(State(app_state.clone()), Path(game_id.clone()), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        let commitment = status["draw_commitment"].as_str().unwrap().to_string();
+        assert_eq!(commitment.len(), 64);
+        assert!(status.get("draw_seed").is_none());
+
+        // The seed stays secret while the game is running
+        let error = handle_verify(State(app_state.clone()), Path(game_id.clone()), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        while handle_extract(State(app_state.clone()), Path(game_id.clone()), authorized(&app_state, &game_id, &board_client_id).await.unwrap(), Query(ClientIdQuery { client_id: None }), None).await.is_ok() {}
+
+        let status = handle_status(State(app_state.clone()), Path(game_id.clone()), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        let verification = handle_verify(State(app_state.clone()), Path(game_id), headers, Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(verification["draw_seed"], status["draw_seed"]);
+        assert_eq!(verification["draw_commitment"], commitment);
+        assert_eq!(verification["replayed"], verification["extracted"]);
+        assert_eq!(verification["verified"], true);
+    }
+
+    #[tokio::test]
+    async fn test_handle_verify_card() {
+        use crate::card::CardAssignment;
+        use crate::serial::serial_of;
+
+        let app_state = create_test_app_state();
+        let game_id = create_test_game(&app_state).await;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        let (cards, _) = game.assign_cards("player", 2, Some(Role::Player)).unwrap();
+        let serial = cards[0].card_id.clone();
+
+        let verification = handle_verify_card(State(app_state.clone()), Path(serial.clone())).await.unwrap();
+        assert_eq!(verification["serial"], serial);
+        assert_eq!(verification["version"], 1);
+        assert_eq!(verification["cards"][0]["game_id"], game_id);
+        assert_eq!(verification["cards"][0]["card_data"], serde_json::to_value(&cards[0].card_data).unwrap());
+        assert_eq!(verification["verified"], true);
+
+        // A serial typed from a printed card is read in its canonical form
+        let typed = format!("{}-{}", &serial[..7], &serial[7..]).to_lowercase();
+        let verification = handle_verify_card(State(app_state.clone()), Path(typed)).await.unwrap();
+        assert_eq!(verification["serial"], serial);
+
+        let mistyped = format!("{}{}", &serial[..13], if serial.ends_with('A') { 'B' } else { 'A' });
+        let error = handle_verify_card(State(app_state.clone()), Path(mistyped)).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+
+        let error = handle_verify_card(State(app_state.clone()), Path(serial_of(42))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+
+        // A card whose numbers do not give its serial is reported
+        let assignment = CardAssignment { card_id: serial_of(42), client_id: "player".to_string(), card_data: cards[1].card_data.clone() };
+        game.card_manager().lock().unwrap().restore_assignments("player", vec![assignment]);
+        let verification = handle_verify_card(State(app_state.clone()), Path(serial_of(42))).await.unwrap();
+        assert_eq!(verification["cards"][0]["matches"], false);
+        assert_eq!(verification["verified"], false);
+    }
+
+    #[tokio::test]
+    async fn test_handle_newgame_autorun() {
+        use crate::autorun::AutoRunRequest;
+        use crate::extraction::DrawMode;
+
+        let app_state = create_test_app_state();
+        let owner_id = register_test_client(&app_state, "unattended_owner").await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Client-ID", owner_id.parse().unwrap());
+
+        // Settings not given come from server.conf
+        let autorun = AutoRunRequest { min_players: Some(2), repeat: Some(true), ..Default::default() };
+        let request = NewGameRequest { autorun: Some(autorun), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), Some(JsonExtractor(request))).await.unwrap();
+        assert_eq!(response["autorun"]["min_players"], 2);
+        assert_eq!(response["autorun"]["draw_interval"], app_state.config.autorun_draw_interval);
+        assert_eq!(response["autorun"]["repeat"], true);
+
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        let status = handle_status(State(app_state.clone()), Path(game_id), headers.clone(), Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert_eq!(status["autorun"], response["autorun"]);
+        assert_eq!(status["numbers_extracted"], 0);
+
+        let autorun = AutoRunRequest { draw_interval: Some(0), ..Default::default() };
+        let request = NewGameRequest { autorun: Some(autorun), ..Default::default() };
+        let error = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), Some(JsonExtractor(request))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+
+        // The server cannot draw from a physical basket
+        let request = NewGameRequest { draw_mode: Some(DrawMode::Manual), autorun: Some(AutoRunRequest::default()), ..Default::default() };
+        let error = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), Some(JsonExtractor(request))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_extract_manual_draw() {
+        let app_state = create_test_app_state();
+        let owner_id = register_test_client(&app_state, "basket_owner").await;
+
+        let request = NewGameRequest { draw_mode: Some(DrawMode::Manual), ..Default::default() };
+        let response = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &owner_id), Some(JsonExtractor(request))).await.unwrap();
+        let game_id = response["game_id"].as_str().unwrap().to_string();
+        assert_eq!(response["draw_mode"], "manual");
+
+        let extract = async |number: Option<Number>| handle_extract(
+            State(app_state.clone()),
+            Path(game_id.clone()),
+            authorized(&app_state, &game_id, &owner_id).await.unwrap(),
+            Query(ClientIdQuery { client_id: None }),
+            Some(JsonExtractor(ExtractRequest { number })),
+        ).await;
+
+        let response = extract(Some(42)).await.unwrap();
+        assert_eq!(response["extracted_number"], 42);
+        assert_eq!(response["numbers_remaining"], 89);
+
+        // Numbers already drawn, outside the range or missing are rejected
+        assert_eq!(extract(Some(42)).await.unwrap_err().status, StatusCode::CONFLICT);
+        assert_eq!(extract(Some(91)).await.unwrap_err().status, StatusCode::BAD_REQUEST);
+        assert_eq!(extract(None).await.unwrap_err().status, StatusCode::BAD_REQUEST);
+
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        assert_eq!(game.board().lock().unwrap().get_numbers(), &vec![42]);
+        assert!(!game.pouch().lock().unwrap().numbers.contains(&42));
+
+        // Games with a random draw do not accept entered numbers
+        let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
+        let error = async {
+            let access = authorized(&app_state, &game_id, &board_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                Some(JsonExtractor(ExtractRequest { number: Some(7) })),
+            ).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_undo() {
+        let app_state = create_test_app_state();
+        let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
+        let player_id = register_test_client_to_game(&app_state, "undo_player", &game_id).await;
+
+        let mut extracted = Vec::new();
+        for _ in 0..3 {
+            let response = handle_extract(
+                State(app_state.clone()),
+                Path(game_id.clone()),
+                authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await.unwrap();
+            extracted.push(response["extracted_number"].clone());
+        }
+
+        let response = handle_undo(State(app_state.clone()), Path(game_id.clone()), authorized(&app_state, &game_id, &board_client_id).await.unwrap(), None).await.unwrap();
+        assert_eq!(response["undone_numbers"], json!([extracted[2]]));
+        assert_eq!(response["total_extracted"], 2);
+        assert_eq!(response["numbers_remaining"], 88);
+
+        // The undone number is drawn again next
+        let response = handle_extract(
+            State(app_state.clone()),
+            Path(game_id.clone()),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
+            Query(ClientIdQuery { client_id: None }),
+            None,
+        ).await.unwrap();
+        assert_eq!(response["extracted_number"], extracted[2]);
+
+        let error = async {
+            let access = authorized(&app_state, &game_id, &board_client_id).await?;
+            handle_undo(State(app_state.clone()), Path(game_id.clone()), access, Some(JsonExtractor(UndoRequest { count: Some(4) }))).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        let error = async {
+            let access = authorized(&app_state, &game_id, &player_id).await?;
+            handle_undo(State(app_state.clone()), Path(game_id.clone()), access, None).await
+        }.await.unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+
+        let response = handle_undo(State(app_state.clone()), Path(game_id.clone()), authorized(&app_state, &game_id, &board_client_id).await.unwrap(), Some(JsonExtractor(UndoRequest { count: Some(3) }))).await.unwrap();
+        assert_eq!(response["undone_numbers"], json!([extracted[2], extracted[1], extracted[0]]));
+        assert_eq!(response["total_extracted"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_dumpgame_success() {
+        let app_state = create_test_app_state();
+        let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
         // Create some game state through API
         let _ = register_test_client(&app_state, "dumpgame_test_player").await;
@@ -1797,11 +3072,15 @@ mod tests {
         let _ = handle_extract(
             State(app_state.clone()),
             Path(game_id.clone()),
-            headers.clone(),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
-        let result = handle_dumpgame(State(app_state.clone()), Path(game_id), headers).await;
+        let result = async {
+            let access = authorized(&app_state, &game_id, &board_client_id).await?;
+            handle_dumpgame(Path(game_id), access).await
+        }.await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1814,34 +3093,138 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_handle_dumpgame_unauthorized() {
-        let app_state = create_test_app_state();
-        let game_id = get_test_game_id(&app_state).await;
-        let client_id = register_test_client(&app_state, "dumpgame_test_player").await;
-
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap()); // Regular client, not board
+    async fn test_handle_dumpgame_unauthorized() {
+        let app_state = create_test_app_state();
+        let game_id = get_test_game_id(&app_state).await;
+        let client_id = register_test_client(&app_state, "dumpgame_test_player").await;
+
+        let result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_dumpgame(Path(game_id), access).await
+        }.await;
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        assert!(error.message.contains("Client must be registered to this game"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_setrole() {
+        let app_state = create_test_app_state_with_config(ServerConfig { admins: vec![TEST_ADMIN_ID.to_string()], ..ServerConfig::default() });
+        let (game_id, owner_id) = create_test_game_with_board_client(&app_state).await;
+        let player_id = register_test_client_to_game(&app_state, "setrole_player", &game_id).await;
+        let caller_id = register_test_client_to_game(&app_state, "setrole_caller", &game_id).await;
+
+        let set_role = async |client_id: &str, target_id: &str, role: Role| {
+            let access = authorized(&app_state, &game_id, client_id).await?;
+            let request = SetRoleRequest { client_id: target_id.to_string(), role };
+            handle_setrole(State(app_state.clone()), Path(game_id.clone()), access, JsonExtractor(request)).await
+        };
+
+        // Players can neither draw nor grant roles
+        assert!(authorized::<CanDraw>(&app_state, &game_id, &caller_id).await.is_err());
+        assert_eq!(set_role(&player_id, &caller_id, Role::Caller).await.unwrap_err().status, StatusCode::FORBIDDEN);
+
+        // The owner grants the caller role, which draws numbers but no longer plays
+        let response = set_role(&owner_id, &caller_id, Role::Caller).await.unwrap();
+        assert_eq!(response["role"], "caller");
+        let access = authorized(&app_state, &game_id, &caller_id).await.unwrap();
+        let response = handle_extract(State(app_state.clone()), Path(game_id.clone()), access, Query(ClientIdQuery { client_id: None }), None).await.unwrap();
+        assert_eq!(response["success"], true);
+        assert!(authorized::<CanPlay>(&app_state, &game_id, &caller_id).await.is_err());
+
+        // Only the caller and player roles are granted, to clients of the game other than the owner
+        assert_eq!(set_role(&owner_id, &player_id, Role::Admin).await.unwrap_err().status, StatusCode::FORBIDDEN);
+        assert_eq!(set_role(&owner_id, &owner_id, Role::Player).await.unwrap_err().status, StatusCode::CONFLICT);
+        assert_eq!(set_role(&owner_id, "UNKNOWN_CLIENT", Role::Player).await.unwrap_err().status, StatusCode::NOT_FOUND);
+
+        // Admins of the server manage the games they have not joined
+        let admin_id = register_test_admin(&app_state, "game_admin");
+        let access = authorized::<CanManage>(&app_state, &game_id, &admin_id).await.unwrap();
+        assert_eq!(access.role, Role::Admin);
+        let response = set_role(&admin_id, &caller_id, Role::Player).await.unwrap();
+        assert_eq!(response["role"], "player");
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        assert_eq!(game.get_role(&caller_id).unwrap(), Some(Role::Player));
+
+        // The admin role follows the client ID, a token carrying the name of an admin grants nothing
+        let mut player_info = app_state.global_client_registry.get(&player_id).unwrap().unwrap();
+        player_info.name = "game_admin".to_string();
+        let client = AuthClient::from_token(&app_state, &issue_token(&player_info, &app_state.config).unwrap()).unwrap();
+        let error = Authorized::<CanManage>::authorize(&app_state, client, &game_id).await.err().unwrap();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_handle_importcards_and_assigncards() {
+        use crate::card_import::ImportCardsRequest;
 
-        let result = handle_dumpgame(State(app_state.clone()), Path(game_id), headers).await;
+        let app_state = create_test_app_state();
+        let (game_id, owner_id) = create_test_game_with_board_client(&app_state).await;
+        let player_id = register_test_client_to_game(&app_state, "paper_player", &game_id).await;
+
+        let import = async |client_id: &str, content_type: &str, body: String| {
+            let access = authorized(&app_state, &game_id, client_id).await?;
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+            handle_importcards(State(app_state.clone()), Path(game_id.clone()), access, headers, body).await
+        };
+        let assign = async |client_id: &str, target_id: &str, serials: Vec<String>| {
+            let access = authorized(&app_state, &game_id, client_id).await?;
+            let request = AssignCardsRequest { client_id: target_id.to_string(), serials };
+            handle_assigncards(State(app_state.clone()), Path(game_id.clone()), access, JsonExtractor(request)).await
+        };
 
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::FORBIDDEN);
-        assert!(error.message.contains("Unauthorized: Only the board owner can dump the game"));
+        // A series printed elsewhere, one card row per CSV line
+        let series = crate::card::CardManagement::new().generate_card_group().unwrap();
+        let csv = series[..4].iter().flatten()
+            .map(|row| row.iter().map(|cell| cell.map(|number| number.to_string()).unwrap_or_default()).collect::<Vec<String>>().join(","))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let response = import(&owner_id, "text/csv", csv).await.unwrap();
+        assert_eq!(response["imported"], 4);
+        assert_eq!(response["inventory"], 4);
+        let serials: Vec<String> = serde_json::from_value(response["serials"].clone()).unwrap();
+
+        let json = serde_json::to_string(&ImportCardsRequest { cards: series[4..].to_vec() }).unwrap();
+        let response = import(&owner_id, "application/json", json.clone()).await.unwrap();
+        assert_eq!(response["inventory"], 6);
+
+        // Only the owner imports, and invalid or repeated cards are rejected
+        assert_eq!(import(&player_id, "application/json", json.clone()).await.unwrap_err().status, StatusCode::FORBIDDEN);
+        assert_eq!(import(&owner_id, "application/json", json).await.unwrap_err().status, StatusCode::BAD_REQUEST);
+        assert_eq!(import(&owner_id, "text/csv", "1,2,3".to_string()).await.unwrap_err().status, StatusCode::BAD_REQUEST);
+
+        // The owner hands the paper cards out by serial, typed as printed
+        let response = assign(&owner_id, &player_id, vec![serials[0].to_lowercase(), serials[1].clone()]).await.unwrap();
+        assert_eq!(response["cards"].as_array().unwrap().len(), 2);
+        assert_eq!(response["cards"][0]["card_id"], serials[0]);
+        let access = authorized(&app_state, &game_id, &player_id).await.unwrap();
+        let listed = handle_listassignedcards(Path(game_id.clone()), access, Query(ClientIdQuery { client_id: None })).await.unwrap();
+        assert!(listed.cards.iter().any(|card| card.card_id == serials[1]));
+
+        let verification = handle_verify_card(State(app_state.clone()), Path(serials[1].clone())).await.unwrap();
+        assert_eq!(verification["cards"][0]["assigned"], true);
+        let verification = handle_verify_card(State(app_state.clone()), Path(serials[2].clone())).await.unwrap();
+        assert_eq!(verification["cards"][0]["assigned"], false);
+        assert_eq!(verification["verified"], true);
+
+        assert_eq!(assign(&owner_id, &player_id, vec![serials[0].clone()]).await.unwrap_err().status, StatusCode::CONFLICT);
+        assert_eq!(assign(&owner_id, &player_id, vec!["not a serial".to_string()]).await.unwrap_err().status, StatusCode::BAD_REQUEST);
+        assert_eq!(assign(&owner_id, "UNKNOWN_CLIENT", vec![serials[2].clone()]).await.unwrap_err().status, StatusCode::NOT_FOUND);
+        assert_eq!(assign(&owner_id, &owner_id, vec![serials[2].clone()]).await.unwrap_err().status, StatusCode::CONFLICT);
+        assert_eq!(assign(&player_id, &player_id, vec![serials[2].clone()]).await.unwrap_err().status, StatusCode::FORBIDDEN);
     }
 
     #[tokio::test]
     async fn test_handle_dumpgame_missing_client_id() {
         let app_state = create_test_app_state();
         let game_id = get_test_game_id(&app_state).await;
-        let headers = HeaderMap::new(); // No X-Client-ID header
-
-        let result = handle_dumpgame(State(app_state.clone()), Path(game_id), headers).await;
+        let address = serve_authenticated_routes(&app_state).await;
 
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
-        assert!(error.message.contains("Missing X-Client-ID header"));
+        let response = reqwest::Client::new().post(format!("http://{address}/{game_id}/dumpgame")).send().await.unwrap();
+        assert_unauthenticated(response).await;
     }
 
     #[tokio::test]
@@ -1861,38 +3244,40 @@ mod tests {
         let client_id = register_test_client_to_game(&app_state, "integration_test_player", &game_id).await;
 
         // List assigned cards
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let cards_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let cards_result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_listassignedcards(
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
         assert!(cards_result.is_ok());
         let cards = cards_result.unwrap();
         assert_eq!(cards.0.cards.len(), 1);
 
         // Get specific card
         let card_id = &cards.0.cards[0].card_id;
-        let card_result = handle_getassignedcard(
-            State(app_state.clone()),
-            Path((game_id.clone(), card_id.clone())),
-            headers.clone(),
-        ).await;
+        let card_result = async {
+            let access = authorized(&app_state, &game_id, &client_id).await?;
+            handle_getassignedcard(
+                Path((game_id.clone(), card_id.clone())),
+                access,
+            ).await
+        }.await;
         assert!(card_result.is_ok());
 
         // Extract a number (as board client)
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
-        let extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            board_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let extract_result = async {
+            let access = authorized(&app_state, &game_id, &board_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(extract_result.is_ok());
 
         // Check board state
@@ -1964,15 +3349,13 @@ mod tests {
         // Create an active game via API and make it active by extracting numbers
         let (active_game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         // Extract a number to make it active
         let _ = handle_extract(
             State(app_state.clone()),
-            Path(active_game_id),
-            board_headers.clone(),
+            Path(active_game_id.clone()),
+            authorized(&app_state, &active_game_id, &board_client_id).await.unwrap(),
             Query(ClientIdQuery { client_id: None }),
+            None,
         ).await.unwrap();
 
         // Test the games list endpoint - we now have 2 games (1 new, 1 active)
@@ -2025,12 +3408,10 @@ mod tests {
 
         // Step 1: Create 3 new games via the newgame endpoint
         let mut game_ids = Vec::new();
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", original_board_client_id.parse().unwrap());
 
         for i in 1..=3 {
             println!("📝 Creating game {i}");
-            let newgame_result = handle_global_newgame(State(app_state.clone()), board_headers.clone()).await;
+            let newgame_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &original_board_client_id), None).await;
             assert!(newgame_result.is_ok(), "Failed to create game {i}");
 
             let newgame_response = newgame_result.unwrap();
@@ -2065,11 +3446,13 @@ mod tests {
                     client_type: "player".to_string(),
                     nocard: Some(6), // Request 6 cards during registration
                     email: None,
+                    allow_duplicate_name: false,
                 };
 
                 let register_result = handle_join(
                     Path(game_id.clone()),
                     State(app_state.clone()),
+                    HeaderMap::new(),
                     JsonExtractor(register_request)
                 ).await;
 
@@ -2087,12 +3470,14 @@ mod tests {
                 let mut client_headers = HeaderMap::new();
                 client_headers.insert("X-Client-ID", client_id.parse().unwrap());
 
-                let list_cards_result = handle_listassignedcards(
-                    State(app_state.clone()),
-                    Path(game_id.clone()),
-                    client_headers.clone(),
-                    Query(ClientIdQuery { client_id: None }),
-                ).await;
+                let list_cards_result = async {
+                    let access = authorized(&app_state, game_id, &client_id).await?;
+                    handle_listassignedcards(
+                        Path(game_id.clone()),
+                        access,
+                        Query(ClientIdQuery { client_id: None }),
+                    ).await
+                }.await;
 
                 assert!(list_cards_result.is_ok(), "Failed to list cards for {client_name} in game {game_id}");
                 let cards_response = list_cards_result.unwrap();
@@ -2101,11 +3486,13 @@ mod tests {
 
                 // Get one card to verify it belongs to this client
                 let card_id = &cards_response.cards[0].card_id;
-                let get_card_result = handle_getassignedcard(
-                    State(app_state.clone()),
-                    Path((game_id.clone(), card_id.clone())),
-                    client_headers,
-                ).await;
+                let get_card_result = async {
+                    let access = authorized(&app_state, game_id, &client_id).await?;
+                    handle_getassignedcard(
+                        Path((game_id.clone(), card_id.clone())),
+                        access,
+                    ).await
+                }.await;
 
                 assert!(get_card_result.is_ok(), "Failed to get card {card_id} for {client_name} in game {game_id}");
                 println!("🔍 Verified card access for {client_name} in game {}", game_index + 1);
@@ -2141,25 +3528,27 @@ mod tests {
                 println!("📊 Game {}: {} numbers extracted so far", game_index + 1, numbers_extracted);
 
                 // Try to extract a number
-                let extract_result = handle_extract(
-                    State(app_state.clone()),
-                    Path(game_id.clone()),
-                    board_headers.clone(),
-                    Query(ClientIdQuery { client_id: None }),
-                ).await;
+                let extract_result = async {
+                    let access = authorized(&app_state, game_id, &original_board_client_id).await?;
+                    handle_extract(
+                        State(app_state.clone()),
+                        Path(game_id.clone()),
+                        access,
+                        Query(ClientIdQuery { client_id: None }),
+                        None,
+                    ).await
+                }.await;
 
                 extraction_count += 1;
 
-                if extract_result.is_ok() {
-                    let extract_response = extract_result.unwrap();
+                if let Ok(extract_response) = extract_result {
                     let extracted_number = extract_response["extracted_number"].as_i64().unwrap();
                     let remaining = extract_response["numbers_remaining"].as_i64().unwrap();
                     let total = extract_response["total_extracted"].as_i64().unwrap();
 
                     println!("🎯 Game {}: Extracted number {} (Total: {}, Remaining: {})",
                              game_index + 1, extracted_number, total, remaining);
-                } else {
-                    let error = extract_result.unwrap_err();
+                } else if let Err(error) = extract_result {
                     if error.status == StatusCode::CONFLICT && error.message.contains("BINGO") {
                         println!("🏆 Game {}: BINGO REACHED! Game completed after {} extractions",
                                  game_index + 1, extraction_count - 1);
@@ -2188,12 +3577,14 @@ mod tests {
                 let mut client_headers = HeaderMap::new();
                 client_headers.insert("X-Client-ID", client_id.parse().unwrap());
 
-                let final_cards_result = handle_listassignedcards(
-                    State(app_state.clone()),
-                    Path(game_id.clone()),
-                    client_headers,
-                    Query(ClientIdQuery { client_id: None }),
-                ).await;
+                let final_cards_result = async {
+                    let access = authorized(&app_state, game_id, client_id).await?;
+                    handle_listassignedcards(
+                        Path(game_id.clone()),
+                        access,
+                        Query(ClientIdQuery { client_id: None }),
+                    ).await
+                }.await;
 
                 assert!(final_cards_result.is_ok(), "Failed to access cards for {client_name} in completed game");
                 let final_cards = final_cards_result.unwrap();
@@ -2255,6 +3646,7 @@ mod tests {
             client_type: "board".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let board_register_result = handle_global_register(
@@ -2266,17 +3658,14 @@ mod tests {
         let board_client_id = board_register_result.unwrap().client_id.clone();
 
         // Step 2: Create two new games
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         // Create first new game
-        let newgame1_result = handle_global_newgame(State(app_state.clone()), board_headers.clone()).await;
+        let newgame1_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &board_client_id), None).await;
         assert!(newgame1_result.is_ok(), "Failed to create first game");
         let game1_id = newgame1_result.unwrap()["game_id"].as_str().unwrap().to_string();
         println!("✅ Created first game: {game1_id}");
 
         // Create second new game
-        let newgame2_result = handle_global_newgame(State(app_state.clone()), board_headers.clone()).await;
+        let newgame2_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &board_client_id), None).await;
         assert!(newgame2_result.is_ok(), "Failed to create second game");
         let game2_id = newgame2_result.unwrap()["game_id"].as_str().unwrap().to_string();
         println!("✅ Created second game: {game2_id}");
@@ -2288,11 +3677,13 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(2),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let register1_result = handle_join(
             Path(game1_id.clone()),
             State(app_state.clone()),
+            HeaderMap::new(),
             JsonExtractor(register_request.clone())
         ).await;
 
@@ -2305,6 +3696,7 @@ mod tests {
         let register2_result = handle_join(
             Path(game2_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &client_id_game1),
             JsonExtractor(register_request.clone())
         ).await;
 
@@ -2350,16 +3742,15 @@ mod tests {
         println!("🔍 VERIFIED: Global clientinfo by ID works correctly");
 
         // Step 7: Verify client can access cards in both games
-        let mut client_headers = HeaderMap::new();
-        client_headers.insert("X-Client-ID", client_id_game1.parse().unwrap());
-
         // Check cards in game1
-        let cards1_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game1_id.clone()),
-            client_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let cards1_result = async {
+            let access = authorized(&app_state, &game1_id, &client_id_game1).await?;
+            handle_listassignedcards(
+                Path(game1_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(cards1_result.is_ok(), "Failed to list cards in game1");
         let cards1_response = cards1_result.unwrap();
@@ -2367,12 +3758,14 @@ mod tests {
         println!("🃏 VERIFIED: Client has 2 cards in game1");
 
         // Check cards in game2
-        let cards2_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game2_id.clone()),
-            client_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let cards2_result = async {
+            let access = authorized(&app_state, &game2_id, &client_id_game1).await?;
+            handle_listassignedcards(
+                Path(game2_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(cards2_result.is_ok(), "Failed to list cards in game2");
         let cards2_response = cards2_result.unwrap();
@@ -2386,11 +3779,13 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let different_register_result = handle_join(
             Path(game1_id.clone()),
             State(app_state.clone()),
+            HeaderMap::new(),
             JsonExtractor(different_register_request)
         ).await;
 
@@ -2442,6 +3837,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0), // Not used in global registration
             email: None,
+            allow_duplicate_name: false,
         };
 
         let result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
@@ -2468,6 +3864,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0),
             email: Some("test@example.com".to_string()),
+            allow_duplicate_name: false,
         };
 
         let result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request)).await;
@@ -2495,6 +3892,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0),
             email: Some("first@example.com".to_string()),
+            allow_duplicate_name: false,
         };
 
         let result1 = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request1)).await;
@@ -2508,9 +3906,14 @@ mod tests {
             client_type: "admin".to_string(), // Different type
             nocard: Some(0),
             email: Some("second@example.com".to_string()), // Different email
+            allow_duplicate_name: false,
         };
 
-        let result2 = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request2)).await;
+        // Without the credential of the client the name is taken
+        let error = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(request2.clone())).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        let result2 = handle_global_register(State(app_state.clone()), client_headers(&app_state, &first_client_id), JsonExtractor(request2)).await;
         assert!(result2.is_ok());
         let response2 = result2.unwrap();
 
@@ -2528,7 +3931,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_handle_global_register_different_client_types() {
-        let app_state = create_test_app_state();
+        let app_state = create_test_app_state_with_config(ServerConfig { admins: vec![TEST_ADMIN_ID.to_string()], ..ServerConfig::default() });
 
         // Register a player
         let player_request = RegisterRequest {
@@ -2536,33 +3939,56 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let player_result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(player_request)).await;
         assert!(player_result.is_ok());
 
-        // Register an admin
-        let admin_request = RegisterRequest {
-            name: "test_admin".to_string(),
-            client_type: "admin".to_string(),
+        // Register a board
+        let board_request = RegisterRequest {
+            name: "test_board".to_string(),
+            client_type: "board".to_string(),
             nocard: Some(0),
-            email: Some("admin@company.com".to_string()),
+            email: Some("board@company.com".to_string()),
+            allow_duplicate_name: false,
         };
 
-        let admin_result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(admin_request)).await;
-        assert!(admin_result.is_ok());
+        let board_result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(board_request)).await;
+        assert!(board_result.is_ok());
 
         // Verify both are in registry with correct types
         let player_info = app_state.global_client_registry.get_by_name("test_player").unwrap().unwrap();
         assert_eq!(player_info.client_type, "player");
         assert_eq!(player_info.email, "");
 
-        let admin_info = app_state.global_client_registry.get_by_name("test_admin").unwrap().unwrap();
-        assert_eq!(admin_info.client_type, "admin");
-        assert_eq!(admin_info.email, "admin@company.com");
+        let board_info = app_state.global_client_registry.get_by_name("test_board").unwrap().unwrap();
+        assert_eq!(board_info.client_type, "board");
+        assert_eq!(board_info.email, "board@company.com");
 
         // Ensure they have different IDs
-        assert_ne!(player_info.id, admin_info.id);
+        assert_ne!(player_info.id, board_info.id);
+
+        // The admin role is bound to client IDs in server.conf, a new client cannot register as admin
+        let intruder_request = RegisterRequest {
+            name: "test_intruder".to_string(),
+            client_type: "admin".to_string(),
+            nocard: Some(0),
+            email: None,
+            allow_duplicate_name: false,
+        };
+        let error = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(intruder_request.clone())).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+
+        // The name of an admin is reserved, even for a client opting in to share its name
+        let admin_id = register_test_admin(&app_state, "test_admin");
+        let impostor_request = RegisterRequest { name: "test_admin".to_string(), client_type: "player".to_string(), allow_duplicate_name: true, ..intruder_request };
+        let error = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(impostor_request.clone())).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::CONFLICT);
+
+        // The admin itself registers again with its credential
+        let response = handle_global_register(State(app_state.clone()), client_headers(&app_state, &admin_id), JsonExtractor(impostor_request)).await.unwrap();
+        assert_eq!(response.client_id, admin_id);
     }
 
     #[tokio::test]
@@ -2576,6 +4002,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(0),
             email: Some("integration@test.com".to_string()),
+            allow_duplicate_name: false,
         };
 
         let global_result = handle_global_register(State(app_state.clone()), HeaderMap::new(), JsonExtractor(global_request)).await;
@@ -2589,9 +4016,10 @@ mod tests {
             client_type: "player".to_string(), // Same type
             nocard: Some(2), // Request cards for game
             email: None, // Different email (should be ignored)
+            allow_duplicate_name: false,
         };
 
-        let game_result = handle_join(Path(game_id.clone()), State(app_state.clone()), JsonExtractor(game_request)).await;
+        let game_result = handle_join(Path(game_id.clone()), State(app_state.clone()), client_headers(&app_state, &global_client_id), JsonExtractor(game_request)).await;
         assert!(game_result.is_ok());
         let game_response = game_result.unwrap();
 
@@ -2626,6 +4054,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let global_register_result = handle_global_register(
@@ -2637,15 +4066,12 @@ mod tests {
         let client_id = global_register_result.unwrap().client_id.clone();
 
         // Create game1 using TestPlayer (they become board owner)
-        let mut client_headers = HeaderMap::new();
-        client_headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let game1_result = handle_global_newgame(State(app_state.clone()), client_headers.clone()).await;
+        let game1_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), None).await;
         assert!(game1_result.is_ok());
         let game1_id = game1_result.unwrap()["game_id"].as_str().unwrap().to_string();
 
         // Create game2 using TestPlayer (they become board owner)
-        let game2_result = handle_global_newgame(State(app_state.clone()), client_headers.clone()).await;
+        let game2_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), None).await;
         assert!(game2_result.is_ok());
         let game2_id = game2_result.unwrap()["game_id"].as_str().unwrap().to_string();
 
@@ -2655,39 +4081,46 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game1_register_result = handle_join(
             Path(game1_id.clone()),
             State(app_state.clone()),
+            HeaderMap::new(),
             JsonExtractor(other_player_request)
         ).await;
         assert!(game1_register_result.is_ok());
         let other_client_id = game1_register_result.unwrap().0.client_id;
 
         // Test extraction authorization
-        let mut other_headers = HeaderMap::new();
-        other_headers.insert("X-Client-ID", other_client_id.parse().unwrap());
-
         // OtherPlayer should NOT be able to extract from game1 (where TestPlayer is board owner)
-        let game1_extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game1_id),
-            other_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let game1_extract_result = async {
+            let access = authorized(&app_state, &game1_id, &other_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game1_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(game1_extract_result.is_err());
         let error = game1_extract_result.unwrap_err();
         assert_eq!(error.status, StatusCode::FORBIDDEN);
-        assert!(error.message.contains("Unauthorized: Only the board owner can extract numbers"));
+        assert!(error.message.contains("Unauthorized: the player role cannot draw numbers"));
 
         // TestPlayer SHOULD be able to extract from game2 (where they are the board owner)
-        let game2_extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game2_id),
-            client_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let game2_extract_result = async {
+            let access = authorized(&app_state, &game2_id, &client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game2_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(game2_extract_result.is_ok());
         let response = game2_extract_result.unwrap();
         assert_eq!(response.0["success"], true);
@@ -2704,6 +4137,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let global_register_result = handle_global_register(
@@ -2715,15 +4149,12 @@ mod tests {
         let client_id = global_register_result.unwrap().client_id.clone();
 
         // Create game1 using TestPlayer (they become board owner)
-        let mut client_headers = HeaderMap::new();
-        client_headers.insert("X-Client-ID", client_id.parse().unwrap());
-
-        let game1_result = handle_global_newgame(State(app_state.clone()), client_headers.clone()).await;
+        let game1_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), None).await;
         assert!(game1_result.is_ok());
         let game1_id = game1_result.unwrap()["game_id"].as_str().unwrap().to_string();
 
         // Create game2 using TestPlayer (they become board owner)
-        let game2_result = handle_global_newgame(State(app_state.clone()), client_headers.clone()).await;
+        let game2_result = handle_global_newgame(State(app_state.clone()), client_auth(&app_state, &client_id), None).await;
         assert!(game2_result.is_ok());
         let game2_id = game2_result.unwrap()["game_id"].as_str().unwrap().to_string();
 
@@ -2733,37 +4164,40 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let game1_register_result = handle_join(
             Path(game1_id.clone()),
             State(app_state.clone()),
+            HeaderMap::new(),
             JsonExtractor(other_player_request)
         ).await;
         assert!(game1_register_result.is_ok());
         let other_client_id = game1_register_result.unwrap().0.client_id;
 
         // Test dumpgame authorization
-        let mut other_headers = HeaderMap::new();
-        other_headers.insert("X-Client-ID", other_client_id.parse().unwrap());
-
         // OtherPlayer should NOT be able to dump game1 (where TestPlayer is board owner)
-        let game1_dump_result = handle_dumpgame(
-            State(app_state.clone()),
-            Path(game1_id),
-            other_headers,
-        ).await;
+        let game1_dump_result = async {
+            let access = authorized(&app_state, &game1_id, &other_client_id).await?;
+            handle_dumpgame(
+                Path(game1_id),
+                access,
+            ).await
+        }.await;
         assert!(game1_dump_result.is_err());
         let error = game1_dump_result.unwrap_err();
         assert_eq!(error.status, StatusCode::FORBIDDEN);
-        assert!(error.message.contains("Unauthorized: Only the board owner can dump the game"));
+        assert!(error.message.contains("Unauthorized: the player role cannot manage the game"));
 
         // TestPlayer SHOULD be able to dump game2 (where they are the board owner)
-        let game2_dump_result = handle_dumpgame(
-            State(app_state.clone()),
-            Path(game2_id),
-            client_headers,
-        ).await;
+        let game2_dump_result = async {
+            let access = authorized(&app_state, &game2_id, &client_id).await?;
+            handle_dumpgame(
+                Path(game2_id),
+                access,
+            ).await
+        }.await;
         assert!(game2_dump_result.is_ok());
         let response = game2_dump_result.unwrap();
         assert_eq!(response.0["success"], true);
@@ -2779,6 +4213,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: None,
             email: Some("creator@example.com".to_string()),
+            allow_duplicate_name: false,
         };
 
         let register_result = handle_global_register(
@@ -2794,12 +4229,10 @@ mod tests {
         assert!(register_response.0.message.contains("registered successfully globally"));
 
         // Step 2: User creates a new game and becomes board owner
-        let mut creator_headers = HeaderMap::new();
-        creator_headers.insert("X-Client-ID", creator_client_id.parse().unwrap());
-
         let newgame_result = handle_global_newgame(
             State(app_state.clone()),
-            creator_headers.clone(),
+            client_auth(&app_state, &creator_client_id),
+            None,
         ).await;
 
         assert!(newgame_result.is_ok());
@@ -2810,12 +4243,14 @@ mod tests {
         assert!(newgame_response.0["message"].as_str().unwrap().contains("You are now the board owner"));
 
         // Step 3: Verify the creator is registered to the game and has BOARD_ID card
-        let listcards_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            creator_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let listcards_result = async {
+            let access = authorized(&app_state, &game_id, &creator_client_id).await?;
+            handle_listassignedcards(
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(listcards_result.is_ok());
         let cards_response = listcards_result.unwrap();
@@ -2827,12 +4262,16 @@ mod tests {
         assert!(has_board_card, "Creator should have BOARD_ID card assigned");
 
         // Step 4: Board owner successfully extracts a number
-        let extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            creator_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let extract_result = async {
+            let access = authorized(&app_state, &game_id, &creator_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(extract_result.is_ok());
         let extract_response = extract_result.unwrap();
@@ -2847,6 +4286,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(2),
             email: None,
+            allow_duplicate_name: false,
         };
 
         // First register them globally
@@ -2863,6 +4303,7 @@ mod tests {
         let player2_join_result = handle_join(
             Path(game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &player2_client_id),
             JsonExtractor(player2_request),
         ).await;
 
@@ -2874,7 +4315,8 @@ mod tests {
         // Step 6: Create a fresh game to test regular player access
         let fresh_game_result = handle_global_newgame(
             State(app_state.clone()),
-            creator_headers.clone(),
+            client_auth(&app_state, &creator_client_id),
+            None,
         ).await;
 
         assert!(fresh_game_result.is_ok());
@@ -2886,62 +4328,73 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let player2_fresh_join = handle_join(
             Path(fresh_game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &player2_client_id),
             JsonExtractor(player2_fresh_request),
         ).await;
 
         assert!(player2_fresh_join.is_ok());
 
         // Step 7: Regular player cannot extract numbers
-        let mut player2_headers = HeaderMap::new();
-        player2_headers.insert("X-Client-ID", player2_client_id.parse().unwrap());
-
-        let player2_extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(fresh_game_id.clone()),
-            player2_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let player2_extract_result = async {
+            let access = authorized(&app_state, &fresh_game_id, &player2_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(fresh_game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(player2_extract_result.is_err());
         let extract_error = player2_extract_result.unwrap_err();
         assert_eq!(extract_error.status, StatusCode::FORBIDDEN);
-        assert!(extract_error.message.contains("Only the board owner can extract numbers"));
+        assert!(extract_error.message.contains("the player role cannot draw numbers"));
 
         // Step 8: Board owner can extract from fresh game
-        let creator_extract_fresh = handle_extract(
-            State(app_state.clone()),
-            Path(fresh_game_id.clone()),
-            creator_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let creator_extract_fresh = async {
+            let access = authorized(&app_state, &fresh_game_id, &creator_client_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(fresh_game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(creator_extract_fresh.is_ok());
         let fresh_extract_response = creator_extract_fresh.unwrap();
         assert_eq!(fresh_extract_response.0["success"], true);
 
         // Step 9: Regular player cannot dump game
-        let player2_dump_result = handle_dumpgame(
-            State(app_state.clone()),
-            Path(fresh_game_id.clone()),
-            player2_headers,
-        ).await;
+        let player2_dump_result = async {
+            let access = authorized(&app_state, &fresh_game_id, &player2_client_id).await?;
+            handle_dumpgame(
+                Path(fresh_game_id.clone()),
+                access,
+            ).await
+        }.await;
 
         assert!(player2_dump_result.is_err());
         let dump_error = player2_dump_result.unwrap_err();
         assert_eq!(dump_error.status, StatusCode::FORBIDDEN);
-        assert!(dump_error.message.contains("Only the board owner can dump the game"));
+        assert!(dump_error.message.contains("the player role cannot manage the game"));
 
         // Step 10: Board owner can dump game
-        let creator_dump_result = handle_dumpgame(
-            State(app_state.clone()),
-            Path(fresh_game_id),
-            creator_headers,
-        ).await;
+        let creator_dump_result = async {
+            let access = authorized(&app_state, &fresh_game_id, &creator_client_id).await?;
+            handle_dumpgame(
+                Path(fresh_game_id),
+                access,
+            ).await
+        }.await;
 
         assert!(creator_dump_result.is_ok());
         let dump_response = creator_dump_result.unwrap();
@@ -2958,13 +4411,15 @@ mod tests {
             client_type: "player".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let user2_request = RegisterRequest {
             name: "creator2".to_string(),
-            client_type: "admin".to_string(),
+            client_type: "player".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let user1_result = handle_global_register(
@@ -2984,22 +4439,18 @@ mod tests {
         let user2_id = user2_result.unwrap().0.client_id;
 
         // Each user creates their own game
-        let mut user1_headers = HeaderMap::new();
-        user1_headers.insert("X-Client-ID", user1_id.parse().unwrap());
-
-        let mut user2_headers = HeaderMap::new();
-        user2_headers.insert("X-Client-ID", user2_id.parse().unwrap());
-
         let game1_result = handle_global_newgame(
             State(app_state.clone()),
-            user1_headers.clone(),
+            client_auth(&app_state, &user1_id),
+            None,
         ).await;
         assert!(game1_result.is_ok());
         let game1_id = game1_result.unwrap().0["game_id"].as_str().unwrap().to_string();
 
         let game2_result = handle_global_newgame(
             State(app_state.clone()),
-            user2_headers.clone(),
+            client_auth(&app_state, &user2_id),
+            None,
         ).await;
         assert!(game2_result.is_ok());
         let game2_id = game2_result.unwrap().0["game_id"].as_str().unwrap().to_string();
@@ -3008,37 +4459,53 @@ mod tests {
         assert_ne!(game1_id, game2_id);
 
         // Each user can extract from their own game
-        let user1_extract = handle_extract(
-            State(app_state.clone()),
-            Path(game1_id.clone()),
-            user1_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user1_extract = async {
+            let access = authorized(&app_state, &game1_id, &user1_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game1_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(user1_extract.is_ok());
 
-        let user2_extract = handle_extract(
-            State(app_state.clone()),
-            Path(game2_id.clone()),
-            user2_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user2_extract = async {
+            let access = authorized(&app_state, &game2_id, &user2_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game2_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(user2_extract.is_ok());
 
         // But users cannot extract from each other's games
-        let user1_extract_game2 = handle_extract(
-            State(app_state.clone()),
-            Path(game2_id),
-            user1_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user1_extract_game2 = async {
+            let access = authorized(&app_state, &game2_id, &user1_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game2_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(user1_extract_game2.is_err());
 
-        let user2_extract_game1 = handle_extract(
-            State(app_state.clone()),
-            Path(game1_id),
-            user2_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user2_extract_game1 = async {
+            let access = authorized(&app_state, &game1_id, &user2_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game1_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
         assert!(user2_extract_game1.is_err());
     }
 
@@ -3052,6 +4519,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: None,
             email: None,
+            allow_duplicate_name: false,
         };
 
         let user1_result = handle_global_register(
@@ -3062,12 +4530,10 @@ mod tests {
         assert!(user1_result.is_ok());
         let user1_id = user1_result.unwrap().0.client_id;
 
-        let mut user1_headers = HeaderMap::new();
-        user1_headers.insert("X-Client-ID", user1_id.parse().unwrap());
-
         let game_result = handle_global_newgame(
             State(app_state.clone()),
-            user1_headers.clone(),
+            client_auth(&app_state, &user1_id),
+            None,
         ).await;
         assert!(game_result.is_ok());
         let game_id = game_result.unwrap().0["game_id"].as_str().unwrap().to_string();
@@ -3078,6 +4544,7 @@ mod tests {
             client_type: "board".to_string(), // Even trying to register as board
             nocard: Some(1),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let user2_global_result = handle_global_register(
@@ -3091,20 +4558,20 @@ mod tests {
         let user2_join_result = handle_join(
             Path(game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &user2_id),
             JsonExtractor(user2_request),
         ).await;
         assert!(user2_join_result.is_ok());
 
         // Verify user2 does NOT have BOARD_ID card
-        let mut user2_headers = HeaderMap::new();
-        user2_headers.insert("X-Client-ID", user2_id.parse().unwrap());
-
-        let user2_cards_result = handle_listassignedcards(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            user2_headers.clone(),
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user2_cards_result = async {
+            let access = authorized(&app_state, &game_id, &user2_id).await?;
+            handle_listassignedcards(
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+            ).await
+        }.await;
 
         assert!(user2_cards_result.is_ok());
         let user2_cards = user2_cards_result.unwrap();
@@ -3113,25 +4580,33 @@ mod tests {
         assert!(!has_board_card, "Second user should NOT have BOARD_ID card");
 
         // User2 cannot extract numbers even though they registered as "board" type
-        let user2_extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id.clone()),
-            user2_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user2_extract_result = async {
+            let access = authorized(&app_state, &game_id, &user2_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id.clone()),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(user2_extract_result.is_err());
         let extract_error = user2_extract_result.unwrap_err();
         assert_eq!(extract_error.status, StatusCode::FORBIDDEN);
-        assert!(extract_error.message.contains("Only the board owner can extract numbers"));
+        assert!(extract_error.message.contains("the player role cannot draw numbers"));
 
         // Original owner still can extract
-        let user1_extract_result = handle_extract(
-            State(app_state.clone()),
-            Path(game_id),
-            user1_headers,
-            Query(ClientIdQuery { client_id: None }),
-        ).await;
+        let user1_extract_result = async {
+            let access = authorized(&app_state, &game_id, &user1_id).await?;
+            handle_extract(
+                State(app_state.clone()),
+                Path(game_id),
+                access,
+                Query(ClientIdQuery { client_id: None }),
+                None,
+            ).await
+        }.await;
 
         assert!(user1_extract_result.is_ok());
         let extract_response = user1_extract_result.unwrap();
@@ -3146,13 +4621,9 @@ mod tests {
         let (game_id, board_client_id) = create_test_game_with_board_client(&app_state).await;
 
         // Step 2: Test players endpoint with only board client
-        let mut board_headers = HeaderMap::new();
-        board_headers.insert("X-Client-ID", board_client_id.parse().unwrap());
-
         let initial_players_result = handle_players(
             Path(game_id.clone()),
-            State(app_state.clone()),
-            board_headers.clone(),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
         ).await;
 
         assert!(initial_players_result.is_ok());
@@ -3164,7 +4635,7 @@ mod tests {
         let initial_players = initial_response.0["players"].as_array().unwrap();
         assert_eq!(initial_players.len(), 1);
         assert_eq!(initial_players[0]["client_id"], board_client_id);
-        assert_eq!(initial_players[0]["client_type"], "board");
+        assert_eq!(initial_players[0]["role"], "owner");
         assert_eq!(initial_players[0]["card_count"], 0); // Board client shows 0 cards (BOARD_ID filtered out)
 
         // Step 3: Register multiple players with different card counts
@@ -3173,6 +4644,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(6),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let player2_request = RegisterRequest {
@@ -3180,6 +4652,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(12),
             email: None,
+            allow_duplicate_name: false,
         };
 
         let player3_request = RegisterRequest {
@@ -3187,6 +4660,7 @@ mod tests {
             client_type: "player".to_string(),
             nocard: Some(3),
             email: None,
+            allow_duplicate_name: false,
         };
 
         // Register players globally first
@@ -3218,6 +4692,7 @@ mod tests {
         let player1_join = handle_join(
             Path(game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &player1_id),
             JsonExtractor(player1_request),
         ).await;
         assert!(player1_join.is_ok());
@@ -3225,6 +4700,7 @@ mod tests {
         let player2_join = handle_join(
             Path(game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &player2_id),
             JsonExtractor(player2_request),
         ).await;
         assert!(player2_join.is_ok());
@@ -3232,6 +4708,7 @@ mod tests {
         let player3_join = handle_join(
             Path(game_id.clone()),
             State(app_state.clone()),
+            client_headers(&app_state, &player3_id),
             JsonExtractor(player3_request),
         ).await;
         assert!(player3_join.is_ok());
@@ -3239,8 +4716,7 @@ mod tests {
         // Step 4: Test players endpoint with all players
         let full_players_result = handle_players(
             Path(game_id.clone()),
-            State(app_state.clone()),
-            board_headers.clone(),
+            authorized(&app_state, &game_id, &board_client_id).await.unwrap(),
         ).await;
 
         assert!(full_players_result.is_ok());
@@ -3252,8 +4728,8 @@ mod tests {
         let all_players = full_response.0["players"].as_array().unwrap();
         assert_eq!(all_players.len(), 4);
 
-        // Step 5: Verify sorting (board client should be first)
-        assert_eq!(all_players[0]["client_type"], "board");
+        // Step 5: Verify sorting (the owner should be first)
+        assert_eq!(all_players[0]["role"], "owner");
         assert_eq!(all_players[0]["client_id"], board_client_id);
         assert_eq!(all_players[0]["card_count"], 0); // Board client shows 0 cards (BOARD_ID filtered out)
 
@@ -3264,19 +4740,19 @@ mod tests {
 
         for player in all_players.iter() {
             let client_id = player["client_id"].as_str().unwrap();
-            let client_type = player["client_type"].as_str().unwrap();
+            let role = player["role"].as_str().unwrap();
             let card_count = player["card_count"].as_u64().unwrap();
 
             if client_id == player1_id {
-                assert_eq!(client_type, "player");
+                assert_eq!(role, "player");
                 assert_eq!(card_count, 6);
                 found_player1 = true;
             } else if client_id == player2_id {
-                assert_eq!(client_type, "player");
+                assert_eq!(role, "player");
                 assert_eq!(card_count, 12);
                 found_player2 = true;
             } else if client_id == player3_id {
-                assert_eq!(client_type, "player");
+                assert_eq!(role, "player");
                 assert_eq!(card_count, 3);
                 found_player3 = true;
             }
@@ -3287,27 +4763,18 @@ mod tests {
         assert!(found_player3, "Player3 should be in the response");
 
         // Step 7: Test error handling for non-existent game
-        let nonexistent_game_result = handle_players(
-            Path("game_nonexistent".to_string()),
-            State(app_state.clone()),
-            board_headers.clone(),
-        ).await;
-
-        assert!(nonexistent_game_result.is_err());
-        let error = nonexistent_game_result.unwrap_err();
+        let Err(error) = authorized::<CanViewPlayers>(&app_state, "game_nonexistent", &board_client_id).await else {
+            panic!("Players of a non-existent game should not be listed");
+        };
         assert_eq!(error.status, StatusCode::NOT_FOUND);
-        assert!(error.message.contains("Game 'game_nonexistent' not found"));
+        assert!(error.message.contains("Game with ID 'game_nonexistent' not found"));
 
         // Step 8: Test with empty game (create a new game with no additional players)
         let (empty_game_id, empty_board_id) = create_test_game_with_board_client(&app_state).await;
 
-        let mut empty_board_headers = HeaderMap::new();
-        empty_board_headers.insert("X-Client-ID", empty_board_id.parse().unwrap());
-
         let empty_players_result = handle_players(
             Path(empty_game_id.clone()),
-            State(app_state.clone()),
-            empty_board_headers,
+            authorized(&app_state, &empty_game_id, &empty_board_id).await.unwrap(),
         ).await;
 
         assert!(empty_players_result.is_ok());
@@ -3319,92 +4786,159 @@ mod tests {
         let empty_players = empty_response.0["players"].as_array().unwrap();
         assert_eq!(empty_players.len(), 1);
         assert_eq!(empty_players[0]["client_id"], empty_board_id);
-        assert_eq!(empty_players[0]["client_type"], "board");
+        assert_eq!(empty_players[0]["role"], "owner");
         assert_eq!(empty_players[0]["card_count"], 0); // Board client shows 0 cards (BOARD_ID filtered out)
 
-        // Step 9: Test authentication requirements
-        // Test with missing client ID header
-        let missing_client_id_result = handle_players(
-            Path(game_id.clone()),
-            State(app_state.clone()),
-            HeaderMap::new(),
-        ).await;
+        // Step 9: Test authorization requirements
+        // Test with a client not registered to the game
+        let outsider_id = register_test_client(&app_state, "outsider").await;
+        let Err(unregistered_error) = authorized::<CanViewPlayers>(&app_state, &game_id, &outsider_id).await else {
+            panic!("Clients of other games should not list the players");
+        };
+        assert_eq!(unregistered_error.status, StatusCode::FORBIDDEN);
+        assert!(unregistered_error.message.contains("Client must be registered to this game"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_ws_pushes_extractions() {
+        use futures_util::StreamExt;
+        use tokio_tungstenite::tungstenite;
 
-        assert!(missing_client_id_result.is_err());
-        let auth_error = missing_client_id_result.unwrap_err();
-        assert_eq!(auth_error.status, StatusCode::BAD_REQUEST);
-        assert!(auth_error.message.contains("Client ID header (X-Client-ID) is required"));
+        let app_state = create_test_app_state();
+        let game_id = create_test_game(&app_state).await;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
 
-        // Test with unregistered client ID
-        let mut unregistered_headers = HeaderMap::new();
-        unregistered_headers.insert("X-Client-ID", "UNREGISTERED_CLIENT_ID".parse().unwrap());
+        let router = axum::Router::new()
+            .route("/{game_id}/ws", axum::routing::get(handle_ws))
+            .with_state(app_state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
 
-        let unregistered_client_result = handle_players(
-            Path(game_id.clone()),
-            State(app_state.clone()),
-            unregistered_headers,
-        ).await;
+        let url = crate::clients::live_updates::websocket_url(&format!("http://{address}"), &game_id);
+        let (mut stream, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
 
-        assert!(unregistered_client_result.is_err());
-        let unregistered_error = unregistered_client_result.unwrap_err();
-        assert_eq!(unregistered_error.status, StatusCode::FORBIDDEN);
-        assert!(unregistered_error.message.contains("Client 'UNREGISTERED_CLIENT_ID' is not registered in game"));
+        let (number, _) = game.extract_number(0, None).unwrap();
+        let message = stream.next().await.unwrap().unwrap();
+        let tungstenite::Message::Text(text) = message else {
+            panic!("Expected a text message, got {message:?}");
+        };
+        let update: GameUpdate = serde_json::from_str(&text).unwrap();
+        assert_eq!(update, GameUpdate::Extraction { number, draw_index: 1 });
+
+        // Spectators joining the running game are announced like the players
+        game.add_spectator("spectator".to_string()).unwrap();
+        let message = stream.next().await.unwrap().unwrap();
+        let tungstenite::Message::Text(text) = message else {
+            panic!("Expected a text message, got {message:?}");
+        };
+        let update: GameUpdate = serde_json::from_str(&text).unwrap();
+        assert_eq!(update, GameUpdate::Join { client_id: "spectator".to_string() });
+
+        // Unknown games are rejected before the upgrade
+        let unknown = crate::clients::live_updates::websocket_url(&format!("http://{address}"), "game_unknown");
+        assert!(tokio_tungstenite::connect_async(unknown.as_str()).await.is_err());
     }
-}
 
-pub async fn handle_players(
-    Path(game_id): Path<String>,
-    State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    log(LogLevel::Info, MODULE_NAME, &format!("Request received: GET /{game_id}/players"));
-
-    // Extract and validate client ID from headers
-    let client_id = match headers.get("X-Client-ID") {
-        Some(client_id_header) => {
-            match client_id_header.to_str() {
-                Ok(client_id_str) => client_id_str.to_string(),
-                Err(_) => {
-                    let error_msg = "Invalid Client ID header format";
-                    log(LogLevel::Warning, MODULE_NAME, error_msg);
-                    return Err(ApiError::new(StatusCode::BAD_REQUEST, error_msg.to_string()));
+    #[tokio::test]
+    async fn test_handle_events_resumes_from_last_event_id() {
+        let app_state = create_test_app_state();
+        let game_id = create_test_game(&app_state).await;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        let first = game.extract_number(0, None).unwrap().0;
+        let second = game.extract_number(0, None).unwrap().0;
+
+        let router = axum::Router::new()
+            .route("/{game_id}/events", axum::routing::get(handle_events))
+            .with_state(app_state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let mut response = reqwest::Client::new()
+            .get(format!("http://{address}/{game_id}/events"))
+            .header("Last-Event-ID", "1")
+            .send().await.unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        // Read the stream until the event with the given ID
+        async fn read_until(response: &mut reqwest::Response, received: &mut String, id: &str) {
+            tokio::time::timeout(std::time::Duration::from_secs(5), async {
+                while !received.contains(&format!("id: {id}\n")) {
+                    let chunk = response.chunk().await.unwrap().unwrap();
+                    received.push_str(&String::from_utf8_lossy(&chunk));
                 }
-            }
-        }
-        None => {
-            let error_msg = "Client ID header (X-Client-ID) is required";
-            log(LogLevel::Warning, MODULE_NAME, error_msg);
-            return Err(ApiError::new(StatusCode::BAD_REQUEST, error_msg.to_string()));
+            }).await.unwrap();
         }
-    };
 
-    // Get the game
-    let game = match state.game_registry.get_game(&game_id) {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            let error_msg = format!("Game '{game_id}' not found");
-            log(LogLevel::Warning, MODULE_NAME, &error_msg);
-            return Err(ApiError::new(StatusCode::NOT_FOUND, error_msg));
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to access game '{game_id}': {e}");
-            log(LogLevel::Error, MODULE_NAME, &error_msg);
-            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error_msg));
-        }
-    };
+        let mut received = String::new();
+        read_until(&mut response, &mut received, "2").await;
+        assert!(!received.contains(&format!("\"number\":{first},")));
+        assert!(received.contains(&format!("event: extraction\ndata: {{\"type\":\"extraction\",\"number\":{second},\"draw_index\":2}}")));
+
+        // Then the live updates follow
+        let third = game.extract_number(0, None).unwrap().0;
+        read_until(&mut response, &mut received, "3").await;
+        assert!(received.contains(&format!("\"number\":{third},\"draw_index\":3")));
+        assert_eq!(received.matches("id: 2\n").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_handle_snapshot_etag_and_long_poll() {
+        let app_state = create_test_app_state();
+        let game_id = create_test_game(&app_state).await;
+        let game = app_state.game_registry.get_game(&game_id).unwrap().unwrap();
+        game.extract_number(0, None).unwrap();
 
-    // Verify the client is registered in this game
-    if !game.contains_client(&client_id) {
-        let error_msg = format!("Client '{client_id}' is not registered in game '{game_id}'");
-        log(LogLevel::Warning, MODULE_NAME, &error_msg);
-        return Err(ApiError::new(StatusCode::FORBIDDEN, error_msg));
+        let snapshot = |headers: HeaderMap, since_version: Option<usize>| handle_snapshot(
+            State(app_state.clone()),
+            Path(game_id.clone()),
+            headers,
+            Query(SnapshotQuery { since_version }),
+        );
+
+        let response = snapshot(HeaderMap::new(), None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let first: crate::game::GameSnapshot = serde_json::from_slice(&body).unwrap();
+        assert_eq!(etag, format!("\"{}\"", first.version).as_str());
+        assert_eq!(first.board.len() + first.pouch_size, 90);
+
+        // Nothing changed since the ETag
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = snapshot(headers.clone(), None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // A long poll on the current version returns with the next change
+        let extracting = game.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            extracting.extract_number(0, None).unwrap();
+        });
+        let response = snapshot(headers, Some(first.version)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let second: crate::game::GameSnapshot = serde_json::from_slice(&body).unwrap();
+        assert!(second.version > first.version);
+        assert_eq!(second.board.len(), 2);
     }
+}
+
+pub async fn handle_players(
+    Path(game_id): Path<String>,
+    access: Authorized<CanViewPlayers>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Authorized { client_id, game, .. } = access;
 
-    // Get all client types for this game
-    let client_types = match game.get_all_client_types() {
-        Ok(types) => types,
+    log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Request received: GET /{game_id}/players"));
+
+    // Get the roles of all the clients of this game
+    let client_roles = match game.get_all_roles() {
+        Ok(roles) => roles,
         Err(e) => {
-            let error_msg = format!("Failed to get client types for game '{game_id}': {e}");
+            let error_msg = format!("Failed to get client roles for game '{game_id}': {e}");
             log(LogLevel::Error, MODULE_NAME, &error_msg);
             return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error_msg));
         }
@@ -3420,39 +4954,36 @@ pub async fn handle_players(
             return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error_msg));
         }
     };
-    // Build player information with card counts
+    // Build player information with card counts, the spectators are listed apart
     let mut players_data = Vec::new();
+    let mut spectators = Vec::new();
+
+    for client_role in client_roles {
+        if client_role.role == Role::Spectator {
+            spectators.push(client_role.client_id);
+            continue;
+        }
 
-    for client_type_info in client_types {
         // Count cards for this client (excluding board cards)
-        let card_count = if let Some(client_cards) = card_manager_lock.get_client_cards(&client_type_info.client_id) {
+        let card_count = if let Some(client_cards) = card_manager_lock.get_client_cards(&client_role.client_id) {
             client_cards.iter().filter(|card_id| **card_id != BOARD_ID).count()
         } else {
             0
         };
 
-        let player_info = json!({
-            "client_id": client_type_info.client_id,
-            "client_type": client_type_info.client_type,
-            "card_count": card_count
-        });
-
-        players_data.push(player_info);
+        players_data.push((client_role.role, client_role.client_id, card_count));
     }
 
-    // Sort by client type (board clients first) then by client_id
-    players_data.sort_by(|a, b| {
-        let type_a = a["client_type"].as_str().unwrap_or("");
-        let type_b = b["client_type"].as_str().unwrap_or("");
-        let id_a = a["client_id"].as_str().unwrap_or("");
-        let id_b = b["client_id"].as_str().unwrap_or("");
-
-        match (type_a, type_b) {
-            ("board", "player") => std::cmp::Ordering::Less,
-            ("player", "board") => std::cmp::Ordering::Greater,
-            _ => id_a.cmp(id_b),
-        }
-    });
+    // Sort by role (owner first) then by client_id
+    players_data.sort();
+    spectators.sort();
+    let players_data: Vec<serde_json::Value> = players_data.into_iter()
+        .map(|(role, client_id, card_count)| json!({
+            "client_id": client_id,
+            "role": role,
+            "card_count": card_count
+        }))
+        .collect();
 
     let total_players = players_data.len();
     let total_cards: usize = players_data
@@ -3460,13 +4991,15 @@ pub async fn handle_players(
         .map(|p| p["card_count"].as_u64().unwrap_or(0) as usize)
         .sum();
 
-    log(LogLevel::Info, MODULE_NAME, &format!("Players list for game '{game_id}': {total_players} players, {total_cards} total cards"));
+    log(LogLevel::Info, MODULE_NAME, &format!("Players list for game '{game_id}': {total_players} players, {} spectators, {total_cards} total cards", spectators.len()));
 
     let response = json!({
         "game_id": game_id,
         "total_players": total_players,
         "total_cards": total_cards,
-        "players": players_data
+        "players": players_data,
+        "total_spectators": spectators.len(),
+        "spectators": spectators
     });
 
     Ok(Json(response))
@@ -0,0 +1,338 @@
+// src/auth.rs
+// Authentication of the clients with secret API keys and JSON Web Tokens (HS256).
+// Registration issues an API key, of which the server only keeps the SHA-256, and a token signed with the secret
+// of server.conf, carrying the client ID with its name, email and the ID of the API key it was issued with.
+// Either one is the bearer credential of the requests: rotating or revoking the key also invalidates its tokens.
+// Handlers acting on behalf of a client take it from the `AuthClient` extractor instead of the public client ID.
+// Handlers acting on a game take `Authorized`, which also checks that the role of the client in the game allows the action.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{FromRequestParts, Path};
+use axum::http::{header, request::Parts, HeaderMap, StatusCode};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::api_handlers::{get_game_from_registry, ApiError};
+use crate::client::ClientInfo;
+use crate::config::ServerConfig;
+use crate::game::Game;
+use crate::logging::{log, LogLevel};
+use crate::role::{Permission, Role};
+use crate::server::AppState;
+
+const MODULE_NAME: &str = "auth";
+
+/// Prefix telling API keys from tokens
+pub const API_KEY_PREFIX: &str = "tbk_";
+
+/// Generate a random API key with 256 bits of entropy
+#[must_use] pub fn generate_api_key() -> String {
+    let bytes = rand::rng().random::<[u8; 32]>();
+    format!("{API_KEY_PREFIX}{}", to_hex(&bytes))
+}
+
+/// Hash of an API key, as stored by the server
+#[must_use] pub fn hash_api_key(api_key: &str) -> String {
+    to_hex(&Sha256::digest(api_key.as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Claims of the token of a client
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Claims {
+    /// Client ID
+    pub sub: String,
+    pub name: String,
+    pub email: String,
+    /// Issue and expiry times, in seconds since the Unix epoch
+    pub iat: u64,
+    pub exp: u64,
+    /// ID of the API key of the client when the token was issued
+    #[serde(default)]
+    pub kid: Option<String>,
+}
+
+/// Issue the token of a registered client, valid for `jwt_lifetime` seconds
+pub fn issue_token(client: &ClientInfo, config: &ServerConfig) -> Result<String, String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Invalid system time: {e}"))?
+        .as_secs();
+    let claims = Claims {
+        sub: client.id.clone(),
+        name: client.name.clone(),
+        email: client.email.clone(),
+        iat: now,
+        exp: now + config.jwt_lifetime,
+        kid: client.key_id().map(str::to_string),
+    };
+    encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(config.jwt_secret.as_bytes()))
+        .map_err(|e| format!("Failed to sign token: {e}"))
+}
+
+/// Check the signature and the expiry of a token and return its claims
+pub fn verify_token(token: &str, config: &ServerConfig) -> Result<Claims, String> {
+    decode::<Claims>(token, &DecodingKey::from_secret(config.jwt_secret.as_bytes()), &Validation::new(Algorithm::HS256))
+        .map(|data| data.claims)
+        .map_err(|e| format!("Invalid token: {e}"))
+}
+
+/// Client authenticated by the bearer token of the request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthClient {
+    pub client_id: String,
+    pub name: String,
+    pub email: String,
+}
+
+impl AuthClient {
+    /// Authenticate a bearer credential, an API key or a token
+    pub fn from_credential(app_state: &AppState, credential: &str) -> Result<Self, ApiError> {
+        if credential.starts_with(API_KEY_PREFIX) {
+            Self::from_api_key(app_state, credential)
+        } else {
+            Self::from_token(app_state, credential)
+        }
+    }
+
+    /// Authenticate the current API key of a client
+    pub fn from_api_key(app_state: &AppState, api_key: &str) -> Result<Self, ApiError> {
+        match app_state.global_client_registry.get_by_api_key(api_key) {
+            Ok(Some(client)) => Ok(Self {
+                client_id: client.id,
+                name: client.name,
+                email: client.email,
+            }),
+            Ok(None) => {
+                log(LogLevel::Error, MODULE_NAME, "Rejected API key: unknown or revoked");
+                Err(ApiError::new(StatusCode::UNAUTHORIZED, "Invalid API key"))
+            }
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("Failed to verify API key: {e}"));
+                Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client"))
+            }
+        }
+    }
+
+    /// Authenticate a token issued to a client still registered on the server, with its current API key
+    pub fn from_token(app_state: &AppState, token: &str) -> Result<Self, ApiError> {
+        let claims = verify_token(token, &app_state.config).map_err(|e| {
+            log(LogLevel::Error, MODULE_NAME, &format!("Rejected token: {e}"));
+            ApiError::new(StatusCode::UNAUTHORIZED, e)
+        })?;
+
+        match app_state.global_client_registry.get(&claims.sub) {
+            Ok(Some(client)) if client.key_id().is_some() && client.key_id() == claims.kid.as_deref() => Ok(Self {
+                client_id: claims.sub,
+                name: claims.name,
+                email: claims.email,
+            }),
+            Ok(Some(_)) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Token of a rotated or revoked API key", claims.sub));
+                Err(ApiError::new(StatusCode::UNAUTHORIZED, "Token of a rotated or revoked API key"))
+            }
+            Ok(None) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Token of a client not registered", claims.sub));
+                Err(ApiError::new(StatusCode::UNAUTHORIZED, "Client not registered"))
+            }
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Failed to verify client: {e}", claims.sub));
+                Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client"))
+            }
+        }
+    }
+}
+
+/// Bearer credential of the `Authorization` header, if any
+#[must_use] pub fn bearer_credential(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+impl FromRequestParts<Arc<AppState>> for AuthClient {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, app_state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let Some(credential) = bearer_credential(&parts.headers) else {
+            log(LogLevel::Error, MODULE_NAME, "Authorization header with a bearer token is required");
+            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Authorization header with a bearer token is required"));
+        };
+        Self::from_credential(app_state, credential)
+    }
+}
+
+/// Client authenticated by its token whose role in the game of the request allows the action of `P`
+pub struct Authorized<P> {
+    pub client_id: String,
+    pub role: Role,
+    pub game: Arc<Game>,
+    permission: PhantomData<P>,
+}
+
+impl<P: Permission> Authorized<P> {
+    /// Check the role of an authenticated client in a game
+    /// Global admins, authenticated by their client ID, are allowed everything, other clients must have joined the game
+    pub async fn authorize(app_state: &Arc<AppState>, client: AuthClient, game_id: &str) -> Result<Self, ApiError> {
+        let game = get_game_from_registry(app_state, game_id).await?;
+
+        let role = if app_state.config.is_admin(&client.client_id) {
+            Some(Role::Admin)
+        } else {
+            game.get_role(&client.client_id).map_err(|e| {
+                log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Failed to read role: {e}", client.client_id));
+                ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Failed to verify client role")
+            })?
+        };
+        let Some(role) = role else {
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] Not registered to game {game_id}", client.client_id));
+            return Err(ApiError::new(StatusCode::FORBIDDEN, "Client must be registered to this game"));
+        };
+
+        if !role.allows(P::ACTION) {
+            let error_msg = format!("Unauthorized: the {role} role cannot {}", P::ACTION.as_str());
+            log(LogLevel::Error, MODULE_NAME, &format!("[Client: {}] {error_msg} in game {game_id}", client.client_id));
+            return Err(ApiError::new(StatusCode::FORBIDDEN, error_msg));
+        }
+
+        Ok(Self {
+            client_id: client.client_id,
+            role,
+            game,
+            permission: PhantomData,
+        })
+    }
+}
+
+impl<P: Permission + Send> FromRequestParts<Arc<AppState>> for Authorized<P> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, app_state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let client = AuthClient::from_request_parts(parts, app_state).await?;
+        let Path(params) = Path::<HashMap<String, String>>::from_request_parts(parts, app_state).await
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.body_text()))?;
+        let Some(game_id) = params.get("game_id") else {
+            return Err(ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Route without a game ID"));
+        };
+        Self::authorize(app_state, client, game_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use crate::client::ClientRegistry;
+    use crate::game::GameRegistry;
+    use crate::persistence::Persistence;
+    use crate::store::MemoryStore;
+
+    fn app_state() -> Arc<AppState> {
+        Arc::new(AppState {
+            game_registry: GameRegistry::new(),
+            global_client_registry: ClientRegistry::new(),
+            config: ServerConfig::default(),
+            persistence: Persistence::new(Box::new(MemoryStore::new())),
+        })
+    }
+
+    async fn extract(app_state: &Arc<AppState>, authorization: Option<&str>) -> Result<AuthClient, ApiError> {
+        let mut request = Request::builder();
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+        AuthClient::from_request_parts(&mut parts, app_state).await
+    }
+
+    #[test]
+    fn test_token_claims() {
+        let config = ServerConfig::default();
+        let client = ClientInfo::new("Alice", "player", "alice@example.com");
+        let token = issue_token(&client, &config).unwrap();
+
+        let claims = verify_token(&token, &config).unwrap();
+        assert_eq!(claims.sub, client.id);
+        assert_eq!(claims.name, "Alice");
+        assert_eq!(claims.email, "alice@example.com");
+        assert_eq!(claims.exp, claims.iat + config.jwt_lifetime);
+
+        // Another secret, a tampered token and an expired token are rejected
+        let other_server = ServerConfig { jwt_secret: "another secret".to_string(), ..config.clone() };
+        assert!(verify_token(&token, &other_server).is_err());
+        let (payload_end, signature) = token.rsplit_once('.').unwrap();
+        assert!(verify_token(&format!("{payload_end}.{}", signature.chars().rev().collect::<String>()), &config).is_err());
+        let expired = Claims { iat: 1, exp: 2, ..claims };
+        let expired = encode(&Header::new(Algorithm::HS256), &expired, &EncodingKey::from_secret(config.jwt_secret.as_bytes())).unwrap();
+        assert!(verify_token(&expired, &config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auth_client_extractor() {
+        let app_state = app_state();
+        let mut client = ClientInfo::new("Bob", "player", "");
+        let _ = client.new_api_key();
+        let token = issue_token(&client, &app_state.config).unwrap();
+
+        // The client ID alone does not authenticate
+        let error = extract(&app_state, None).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+        let error = extract(&app_state, Some(&client.id)).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+
+        // The token of a client unknown to the server is rejected
+        let error = extract(&app_state, Some(&format!("Bearer {token}"))).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+        assert!(error.message.contains("Client not registered"));
+
+        app_state.global_client_registry.insert(client.clone()).unwrap();
+        let auth = extract(&app_state, Some(&format!("Bearer {token}"))).await.unwrap();
+        assert_eq!(auth, AuthClient { client_id: client.id, name: "Bob".to_string(), email: String::new() });
+
+        let error = extract(&app_state, Some("Bearer not.a.token")).await.unwrap_err();
+        assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_authentication() {
+        let app_state = app_state();
+        let client = ClientInfo::new("Carol", "player", "");
+        app_state.global_client_registry.insert(client.clone()).unwrap();
+        let (api_key, client) = app_state.global_client_registry.issue_api_key(&client.id).unwrap().unwrap();
+        let token = issue_token(&client, &app_state.config).unwrap();
+
+        // Only the hash of the key is kept, and the key authenticates the client
+        assert!(api_key.starts_with(API_KEY_PREFIX) && api_key.len() == API_KEY_PREFIX.len() + 64);
+        assert_eq!(client.api_key_hash, Some(hash_api_key(&api_key)));
+        let auth = extract(&app_state, Some(&format!("Bearer {api_key}"))).await.unwrap();
+        assert_eq!(auth.client_id, client.id);
+        assert!(extract(&app_state, Some(&format!("Bearer {token}"))).await.is_ok());
+
+        // Rotating the key invalidates the previous key and its tokens
+        let (new_key, client) = app_state.global_client_registry.issue_api_key(&client.id).unwrap().unwrap();
+        assert_ne!(new_key, api_key);
+        for credential in [&api_key, &token] {
+            let error = extract(&app_state, Some(&format!("Bearer {credential}"))).await.unwrap_err();
+            assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+        }
+        let new_token = issue_token(&client, &app_state.config).unwrap();
+        assert!(extract(&app_state, Some(&format!("Bearer {new_key}"))).await.is_ok());
+        assert!(extract(&app_state, Some(&format!("Bearer {new_token}"))).await.is_ok());
+
+        // Revoking the key invalidates it with its tokens
+        assert!(app_state.global_client_registry.revoke_api_key(&client.id).unwrap());
+        for credential in [&new_key, &new_token] {
+            let error = extract(&app_state, Some(&format!("Bearer {credential}"))).await.unwrap_err();
+            assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// src/autorun.rs
+// Unattended games: the server waits for the start conditions of a game, draws a number
+// every few seconds until the game closes, and optionally opens an identical follow-up game.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ServerConfig;
+use crate::game::Game;
+use crate::logging::{log, LogLevel};
+use crate::server::AppState;
+
+const MODULE_NAME: &str = "autorun";
+
+// Longest wait between two checks of the start conditions
+const START_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings of an unattended game
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AutoRun {
+    /// Players holding cards needed before the first draw
+    pub min_players: usize,
+    /// Cards assigned needed before the first draw
+    pub min_cards: usize,
+    /// Earliest first draw: an RFC 3339 time, or HH:MM (UTC) for the next occurrence after the creation of the game
+    pub start_time: Option<String>,
+    /// Seconds between two draws
+    pub draw_interval: u64,
+    /// Open an identical game when this one closes
+    pub repeat: bool,
+}
+
+/// Unattended settings requested with `/newgame`; those not given come from `server.conf`
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AutoRunRequest {
+    pub min_players: Option<usize>,
+    pub min_cards: Option<usize>,
+    pub start_time: Option<String>,
+    pub draw_interval: Option<u64>,
+    pub repeat: Option<bool>,
+}
+
+impl AutoRun {
+    /// Settings of `server.conf`
+    #[must_use] pub fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            min_players: config.autorun_min_players,
+            min_cards: config.autorun_min_cards,
+            start_time: config.autorun_start_time.clone(),
+            draw_interval: config.autorun_draw_interval,
+            repeat: config.autorun_repeat,
+        }
+    }
+
+    /// Settings of a request completed with those of `server.conf`
+    pub fn from_request(config: &ServerConfig, request: &AutoRunRequest) -> Result<Self, String> {
+        let defaults = Self::from_config(config);
+        let autorun = Self {
+            min_players: request.min_players.unwrap_or(defaults.min_players),
+            min_cards: request.min_cards.unwrap_or(defaults.min_cards),
+            start_time: request.start_time.clone().or(defaults.start_time),
+            draw_interval: request.draw_interval.unwrap_or(defaults.draw_interval),
+            repeat: request.repeat.unwrap_or(defaults.repeat),
+        };
+        autorun.validate()?;
+        Ok(autorun)
+    }
+
+    /// Check that the settings can be run
+    pub fn validate(&self) -> Result<(), String> {
+        if self.draw_interval == 0 {
+            return Err("draw_interval must be at least 1 second".to_string());
+        }
+        self.start_at(SystemTime::now()).map(|_| ())
+    }
+
+    /// Earliest first draw of a game created at the given time
+    pub fn start_at(&self, created_at: SystemTime) -> Result<Option<SystemTime>, String> {
+        self.start_time.as_deref()
+            .map(|start_time| parse_start_time(start_time, created_at))
+            .transpose()
+    }
+
+    /// Whether the start conditions of the game are met at the given time
+    #[must_use] pub fn is_ready(&self, game: &Game, now: SystemTime) -> bool {
+        let started = match self.start_at(game.created_at()) {
+            Ok(start_at) => start_at.is_none_or(|start_at| now >= start_at),
+            Err(_) => true,
+        };
+        started && game.players_with_cards() >= self.min_players && game.card_count() >= self.min_cards
+    }
+}
+
+/// Parse a start time: an RFC 3339 time, or HH:MM (UTC) for its next occurrence from `after`
+pub fn parse_start_time(start_time: &str, after: SystemTime) -> Result<SystemTime, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(start_time) {
+        return Ok(time.into());
+    }
+
+    let time = NaiveTime::parse_from_str(start_time, "%H:%M")
+        .map_err(|_| format!("Invalid start time '{start_time}': expected an RFC 3339 time or HH:MM"))?;
+    let after: DateTime<Utc> = after.into();
+    let mut start = after.date_naive().and_time(time).and_utc();
+    if start < after {
+        start += chrono::Duration::days(1);
+    }
+    Ok(start.into())
+}
+
+/// Run an unattended game until it closes, then its follow-up games if it repeats
+/// The task ends when the game leaves the registry
+pub async fn run_autorun(app_state: Arc<AppState>, mut game: Arc<Game>) {
+    loop {
+        let Some(autorun) = game.autorun() else {
+            return;
+        };
+        let game_id = game.id();
+        log(LogLevel::Info, MODULE_NAME, &format!("Unattended game {game_id} waiting to start: {autorun:?}"));
+
+        if !wait_for_start(&app_state, &game, &autorun).await || !draw_until_ended(&app_state, &game, &autorun).await {
+            log(LogLevel::Info, MODULE_NAME, &format!("Unattended game {game_id} removed, stopping"));
+            return;
+        }
+        log(LogLevel::Info, MODULE_NAME, &format!("Unattended game {game_id} ended after {} draws", game.board_length()));
+
+        if !autorun.repeat {
+            return;
+        }
+        let next = match game.follow_up().and_then(|next| {
+            let next = Arc::new(next);
+            app_state.game_registry.add_game(next.clone()).map(|_| next)
+        }) {
+            Ok(next) => next,
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("Failed to open the game following {game_id}: {e}"));
+                return;
+            }
+        };
+        log(LogLevel::Info, MODULE_NAME, &format!("Opened game {} following {game_id}", next.id()));
+        app_state.persistence.notify_change();
+        game = next;
+    }
+}
+
+/// Resume the unattended games of the registry, and open the unattended game of `server.conf` if none is running
+pub fn start_autorun(app_state: &Arc<AppState>) {
+    let mut running = 0;
+    for game in app_state.game_registry.all_games().unwrap_or_default() {
+        if game.autorun().is_some() && !game.is_game_ended() {
+            tokio::spawn(run_autorun(app_state.clone(), game));
+            running += 1;
+        }
+    }
+
+    if !app_state.config.autorun || running > 0 {
+        return;
+    }
+    let game = Game::new();
+    let opened = AutoRun::from_request(&app_state.config, &AutoRunRequest::default())
+        .and_then(|autorun| game.set_autorun(autorun))
+        .and_then(|()| app_state.game_registry.add_game(Arc::new(game.clone())));
+    match opened {
+        Ok(game_id) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("Opened unattended game {game_id}"));
+            app_state.persistence.notify_change();
+            tokio::spawn(run_autorun(app_state.clone(), Arc::new(game)));
+        }
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to open the unattended game of server.conf: {e}"));
+        }
+    }
+}
+
+fn is_registered(app_state: &AppState, game: &Game) -> bool {
+    matches!(app_state.game_registry.get_game(&game.id()), Ok(Some(_)))
+}
+
+// Wait until the game can start; false if it left the registry meanwhile
+async fn wait_for_start(app_state: &AppState, game: &Game, autorun: &AutoRun) -> bool {
+    loop {
+        if !is_registered(app_state, game) {
+            return false;
+        }
+        // The owner may also have drawn the first number by hand
+        if game.has_game_started() || autorun.is_ready(game, SystemTime::now()) {
+            return true;
+        }
+        // Joins and card assignments change the version of the game
+        game.changed_since(game.version(), START_CHECK_INTERVAL).await;
+    }
+}
+
+// Draw every `draw_interval` seconds until the game ends; false if it left the registry meanwhile
+async fn draw_until_ended(app_state: &AppState, game: &Game, autorun: &AutoRun) -> bool {
+    let owner = game.owner();
+    loop {
+        if !is_registered(app_state, game) {
+            return false;
+        }
+        if game.is_game_ended() {
+            return true;
+        }
+
+        match game.extract_number(0, owner.as_deref()) {
+            Ok((number, _)) => {
+                log(LogLevel::Info, MODULE_NAME, &format!("Unattended game {} drew number {number}", game.id()));
+                if game.is_bingo_reached() {
+                    match game.dump_to_json() {
+                        Ok(dump_message) => {
+                            log(LogLevel::Info, MODULE_NAME, &format!("Game ended with BINGO! {dump_message}"));
+                        }
+                        Err(dump_error) => {
+                            log(LogLevel::Error, MODULE_NAME, &format!("Failed to dump game state: {dump_error}"));
+                        }
+                    }
+                }
+                app_state.persistence.notify_change();
+            }
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("Unattended game {} failed to draw: {e}", game.id()));
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(autorun.draw_interval)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientRegistry;
+    use crate::game::GameRegistry;
+    use crate::persistence::Persistence;
+    use crate::store::MemoryStore;
+
+    fn autorun(min_players: usize, min_cards: usize, start_time: Option<&str>) -> AutoRun {
+        AutoRun {
+            min_players,
+            min_cards,
+            start_time: start_time.map(str::to_string),
+            draw_interval: 5,
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_start_time() {
+        let after: SystemTime = DateTime::parse_from_rfc3339("2026-10-16T20:30:00Z").unwrap().into();
+        let at = |time: &str| -> SystemTime { DateTime::parse_from_rfc3339(time).unwrap().into() };
+
+        assert_eq!(parse_start_time("2026-10-17T08:00:00+02:00", after), Ok(at("2026-10-17T06:00:00Z")));
+        assert_eq!(parse_start_time("21:00", after), Ok(at("2026-10-16T21:00:00Z")));
+        assert_eq!(parse_start_time("20:30", after), Ok(at("2026-10-16T20:30:00Z")));
+        // A time already past today starts tomorrow
+        assert_eq!(parse_start_time("08:15", after), Ok(at("2026-10-17T08:15:00Z")));
+        assert!(parse_start_time("tonight", after).is_err());
+    }
+
+    #[test]
+    fn test_autorun_start_conditions() {
+        let game = Game::new();
+        let now = SystemTime::now();
+        assert!(autorun(0, 0, None).is_ready(&game, now));
+        assert!(!autorun(1, 0, None).is_ready(&game, now));
+
+        game.assign_cards("player1", 2, None).unwrap();
+        assert!(autorun(1, 2, None).is_ready(&game, now));
+        assert!(!autorun(2, 2, None).is_ready(&game, now));
+        assert!(!autorun(1, 3, None).is_ready(&game, now));
+
+        let later = now + Duration::from_secs(3600);
+        let start_time = DateTime::<Utc>::from(later).to_rfc3339();
+        assert!(!autorun(1, 0, Some(&start_time)).is_ready(&game, now));
+        assert!(autorun(1, 0, Some(&start_time)).is_ready(&game, later));
+    }
+
+    #[test]
+    fn test_autorun_from_request() {
+        let config = ServerConfig::default();
+        let request = AutoRunRequest { min_players: Some(3), draw_interval: Some(2), ..AutoRunRequest::default() };
+        let autorun = AutoRun::from_request(&config, &request).unwrap();
+        assert_eq!((autorun.min_players, autorun.min_cards, autorun.draw_interval), (3, config.autorun_min_cards, 2));
+
+        let zero_interval = AutoRunRequest { draw_interval: Some(0), ..AutoRunRequest::default() };
+        assert!(AutoRun::from_request(&config, &zero_interval).is_err());
+        let bad_start = AutoRunRequest { start_time: Some("noon".to_string()), ..AutoRunRequest::default() };
+        assert!(AutoRun::from_request(&config, &bad_start).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_autorun_plays_and_repeats() {
+        let app_state = Arc::new(AppState {
+            game_registry: GameRegistry::new(),
+            global_client_registry: ClientRegistry::new(),
+            config: ServerConfig::default(),
+            persistence: Persistence::new(Box::new(MemoryStore::new())),
+        });
+
+        let game = Arc::new(Game::new());
+        game.set_autorun(AutoRun { repeat: true, ..autorun(1, 1, None) }).unwrap();
+        app_state.game_registry.add_game(game.clone()).unwrap();
+        let task = tokio::spawn(run_autorun(app_state.clone(), game.clone()));
+
+        // Nothing is drawn before the start conditions are met
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert!(!game.has_game_started());
+
+        game.assign_cards("player1", 1, None).unwrap();
+        tokio::time::sleep(Duration::from_secs(12)).await;
+        assert_eq!(game.board_length(), 3);
+
+        // The game is drawn to its end and followed by an identical one waiting for its players
+        tokio::time::sleep(Duration::from_secs(90 * 5)).await;
+        assert!(game.is_bingo_reached());
+        let games = app_state.game_registry.all_games().unwrap();
+        assert_eq!(games.len(), 2);
+        let next = games.into_iter().find(|next| next.id() != game.id()).unwrap();
+        assert_eq!(next.autorun(), game.autorun());
+        assert!(!next.has_game_started());
+
+        // Removing the game stops the task
+        app_state.game_registry.remove_game(&next.id()).unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert!(task.is_finished());
+    }
+}
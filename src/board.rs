@@ -55,6 +55,13 @@ impl Board {
         score
     }
 
+    /// Remove the last extracted number, marking the numbers of the board's highest prize left in the scorecard
+    pub fn pop(&mut self, scorecard: &ScoreCard) -> Option<Number> {
+        let number = self.numbers.pop()?;
+        self.marked_numbers = scorecard.board_marked_numbers().into_iter().collect();
+        Some(number)
+    }
+
     pub fn push_simple(&mut self, entry: Number) {
         self.numbers.push(entry);
     }
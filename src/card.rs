@@ -1,12 +1,12 @@
-use crate::defs::{Number, FIRSTNUMBER, LASTNUMBER, CARDSNUMBER, BOARDCONFIG};
+use crate::defs::{BoardStruct, Number, BINGO75_LETTERS, BOARDCONFIG};
 use crate::client::ClientRegistry;
 use crate::board::{BOARD_ID, board_client_id};
 use crate::game::GameStatus;
+use crate::ruleset::{CardLayout, RulesetKind};
+use crate::serial;
 
 use std::collections::HashSet;
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::Hasher;
 use rand::seq::SliceRandom;
 use rand::rng;
 use serde::{Deserialize, Serialize};
@@ -52,8 +52,25 @@ pub struct CardAssignment {
     pub card_data: Card,
 }
 
+/// Cards generated for a client: the responses, the card IDs of the client and the assignments to store
+pub type GeneratedCards = (Vec<CardInfo>, Vec<String>, Vec<CardAssignment>);
+
+/// How the cards of a game are dealt, every card being unique in the game either way
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CardDistribution {
+    /// Cards drawn for each request
+    #[default]
+    Random,
+    /// Cards dealt in order from complete series ("cartelle"), each series covering every number once
+    Series,
+}
+
 #[derive(Debug, Clone)]
-pub struct CardManagement;
+pub struct CardManagement {
+    board_config: BoardStruct,
+    ruleset: RulesetKind,
+}
 
 #[derive(Debug, Clone)]
 pub struct CardWithId {
@@ -61,7 +78,7 @@ pub struct CardWithId {
     pub card: Card,
 }
 
-pub type Card = Vec<Vec<Option<Number>>>;  // BOARDCONFIG.rows_per_card rows × (LASTNUMBER/10) columns
+pub type Card = Vec<Vec<Option<Number>>>;  // rows_per_card rows × card_columns() columns
 
 impl Default for CardManagement {
     fn default() -> Self {
@@ -71,18 +88,32 @@ impl Default for CardManagement {
 
 impl CardManagement {
     #[must_use] pub fn new() -> Self {
-        Self
+        Self::with_config(BOARDCONFIG)
     }
 
-    #[must_use] pub fn generate_card_group(&self) -> Vec<Card> {
-        let columns = ((LASTNUMBER - FIRSTNUMBER + 1) / 10) as usize;  // Dynamic column calculation
-        let numbers_per_card = ((LASTNUMBER - FIRSTNUMBER + 1) / CARDSNUMBER) as usize;
+    /// Card generator for the given board geometry
+    #[must_use] pub fn with_config(board_config: BoardStruct) -> Self {
+        Self::with_rules(board_config, RulesetKind::default())
+    }
 
-        // Step 1: Calculate number distribution per column per card
-        let distribution = self.calculate_column_distribution(numbers_per_card, columns);
+    /// Card generator for the given board geometry and the card layout of the ruleset
+    #[must_use] pub fn with_rules(board_config: BoardStruct, ruleset: RulesetKind) -> Self {
+        Self { board_config, ruleset }
+    }
 
-        // Step 2: Create allocation matrix with anti-adjacency
-        let allocation_matrix = self.create_allocation_matrix(distribution);
+    #[must_use] pub fn board_config(&self) -> &BoardStruct {
+        &self.board_config
+    }
+
+    pub fn generate_card_group(&self) -> Result<Vec<Card>, String> {
+        const MAX_RETRIES: usize = 100;
+        let mut rng = rng();
+
+        // Step 1 and 2: Decide how many numbers of each column go on each card
+        let allocation_matrix = (0..MAX_RETRIES)
+            .map(|_| self.create_allocation_matrix(&self.calculate_column_sizes()))
+            .find(|allocation_matrix| self.is_valid_allocation(allocation_matrix))
+            .ok_or_else(|| format!("Could not allocate the numbers of a card series after {MAX_RETRIES} attempts"))?;
 
         // Step 3: Distribute actual numbers
         let cards = self.distribute_numbers(&allocation_matrix);
@@ -90,104 +121,83 @@ impl CardManagement {
         // Step 4: Position numbers in cards respecting row constraints
         let mut cards = self.position_numbers_in_cards(cards);
 
-        // Step 5: Randomize the order of the 6 cards
-        let mut rng = rng();
+        // Step 5: Randomize the order of the cards in the group
         cards.shuffle(&mut rng);
 
-        cards
+        Ok(cards)
     }
 
-    fn calculate_column_distribution(&self, numbers_per_card: usize, columns: usize) -> (usize, usize) {
-        // Calculate how many columns must have 2 numbers vs 1 number
-        // Equation: a×2 + b×1 = numbers_per_card, where a+b = columns
-        let columns_with_2_numbers = numbers_per_card - columns;
-        let columns_with_1_number = columns - columns_with_2_numbers;
-        (columns_with_2_numbers, columns_with_1_number)
+    // Numbers available in each card column (9, 10, ..., 10, 11 on the classic layout)
+    fn calculate_column_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.board_config.card_columns()];
+        for number in self.board_config.first_number..=self.board_config.last_number() {
+            sizes[self.board_config.card_column_of(number)] += 1;
+        }
+        sizes
     }
 
-    fn create_allocation_matrix(&self, distribution: (usize, usize)) -> Vec<Vec<Number>> {
-        let columns = ((LASTNUMBER - FIRSTNUMBER + 1) / 10) as usize;
-        let (_columns_with_2_numbers, _columns_with_1_number) = distribution;
-        let mut matrix = vec![vec![2 as Number; columns]; CARDSNUMBER as usize];
-
-        // Anti-adjacency pattern for columns with 1 number
-        let single_number_pattern = [
-            vec![0, 3, 6],  // Card 1: columns 1,4,7
-            vec![1, 4, 7],  // Card 2: columns 2,5,8
-            vec![2, 5, 8],  // Card 3: columns 3,6,9
-            vec![0, 4, 8],  // Card 4: columns 1,5,9
-            vec![1, 5, 6],  // Card 5: columns 2,6,7
-            vec![2, 3, 7],  // Card 6: columns 3,4,8
-        ];
+    fn create_allocation_matrix(&self, column_sizes: &[usize]) -> Vec<Vec<Number>> {
+        let columns = column_sizes.len();
+        let cards_number = self.board_config.cards_number() as usize;
+        let rows = self.board_config.rows_per_card as usize;
+        let mut matrix = vec![vec![0 as Number; columns]; cards_number];
+        let mut needed = vec![self.board_config.numbers_per_card() as usize; cards_number];
+        let mut rng = rng();
 
-        // Apply pattern for columns with 1 number
-        for (card_idx, positions) in single_number_pattern.iter().enumerate() {
-            for &pos in positions {
-                if pos < columns {  // Verify position is valid
-                    matrix[card_idx][pos] = 1;
+        for (col, &size) in column_sizes.iter().enumerate() {
+            let columns_left_after = columns - col - 1;
+
+            for _ in 0..size {
+                // Shuffle first so that ties are broken randomly by the stable sort below
+                let mut candidates: Vec<usize> = (0..cards_number)
+                    .filter(|&card| needed[card] > 0 && (matrix[card][col] as usize) < rows)
+                    .collect();
+                candidates.shuffle(&mut rng);
+
+                // Anti-adjacency: cards that can no longer complete themselves in later columns go first,
+                // then cards with fewer numbers in this column, then the ones still needing the most numbers
+                candidates.sort_by_key(|&card| {
+                    let urgent = needed[card] > rows * columns_left_after;
+                    (!urgent, matrix[card][col], std::cmp::Reverse(needed[card]))
+                });
+
+                if let Some(&card) = candidates.first() {
+                    matrix[card][col] += 1;
+                    needed[card] -= 1;
                 }
             }
         }
 
-        // With uniform distribution strategy:
-        // We'll temporarily borrow number 90 from column 8 and assign it to column 0
-        // This makes all columns have exactly 10 numbers each for uniform allocation
-        // Number 90 will be moved back to column 8 during the final positioning phase
-
-        // Now all columns have 10 numbers each, so we can use uniform allocation
-        // Each card gets 15 numbers total, distributed as 6 columns with 2 numbers + 3 columns with 1 number
-
         matrix
     }
 
+    fn is_valid_allocation(&self, allocation_matrix: &[Vec<Number>]) -> bool {
+        let numbers_per_card = self.board_config.numbers_per_card() as usize;
+        allocation_matrix.iter().all(|card| {
+            card.iter().map(|&count| count as usize).sum::<usize>() == numbers_per_card
+                && card.iter().all(|&count| count <= self.board_config.rows_per_card)
+        })
+    }
+
     fn distribute_numbers(&self, allocation_matrix: &[Vec<Number>]) -> Vec<Vec<Vec<Number>>> {
-        let columns = ((LASTNUMBER - FIRSTNUMBER + 1) / 10) as usize;
-        let mut cards_numbers = vec![vec![Vec::new(); columns]; CARDSNUMBER as usize];
+        let columns = self.board_config.card_columns();
+        let mut cards_numbers = vec![vec![Vec::new(); columns]; allocation_matrix.len()];
         let mut rng = rng();
 
-        // Uniform distribution strategy:
-        // - Column 0 normally has 9 numbers (1-9)
-        // - Column 8 normally has 11 numbers (80-90)
-        // - To achieve uniform allocation, we temporarily borrow number 90 from column 8
-        //   and assign it to column 0, making both columns have 10 numbers each
-        // - Number 90 will be moved back to column 8 in the positioning phase
-
-        // For each column, distribute its numbers according to tombola rules
+        // For each column, distribute its numbers according to the allocation matrix
         for col in 0..columns {
-            let mut column_numbers: Vec<Number> = if col == 0 {
-                // First column: 1-9 + temporarily borrow 90 from column 8
-                let mut numbers = (1..=9).collect::<Vec<Number>>();
-                numbers.push(90);  // Temporarily add 90 to make uniform distribution
-                numbers
-            } else if col == 8 {
-                // Last column (9th): 80-89 (excluding 90 which is temporarily in column 0)
-                (80..=89).collect()
-            } else {
-                // Other columns: Calculate range dynamically
-                let start = col * 10;
-                let end = start + 9;
-                (start as Number..=end as Number).collect()
-            };
+            let mut column_numbers: Vec<Number> = (self.board_config.first_number..=self.board_config.last_number())
+                .filter(|&number| self.board_config.card_column_of(number) == col)
+                .collect();
 
             column_numbers.shuffle(&mut rng);
 
             let mut number_index = 0;
-            for card in 0..CARDSNUMBER as usize {
-                let quantity = allocation_matrix[card][col] as usize;
-                if number_index + quantity <= column_numbers.len() {
-                    // Use slice operations for better performance
-                    let numbers_to_add = &column_numbers[number_index..number_index + quantity];
-                    cards_numbers[card][col].extend_from_slice(numbers_to_add);
-                    number_index += quantity;
-                } else {
-                    // Fallback to individual pushes if we don't have enough numbers
-                    for _ in 0..quantity {
-                        if number_index < column_numbers.len() {
-                            cards_numbers[card][col].push(column_numbers[number_index]);
-                            number_index += 1;
-                        }
-                    }
-                }
+            for (card, allocation) in allocation_matrix.iter().enumerate() {
+                let quantity = std::cmp::min(allocation[col] as usize, column_numbers.len() - number_index);
+                cards_numbers[card][col].extend_from_slice(&column_numbers[number_index..number_index + quantity]);
+                number_index += quantity;
+
                 // Sort numbers in each column of each card
                 cards_numbers[card][col].sort_unstable();
             }
@@ -196,26 +206,13 @@ impl CardManagement {
         cards_numbers
     }
 
-    fn position_numbers_in_cards(&self, mut cards_numbers: Vec<Vec<Vec<Number>>>) -> Vec<Card> {
-        let columns = ((LASTNUMBER - FIRSTNUMBER + 1) / 10) as usize;
+    fn position_numbers_in_cards(&self, cards_numbers: Vec<Vec<Vec<Number>>>) -> Vec<Card> {
+        let columns = self.board_config.card_columns();
         let mut rng = rng();
-
-        // Move number 90 from column 0 back to column 8
-        // This completes the uniform distribution strategy by putting 90 in its correct final position
-        for card_numbers in cards_numbers.iter_mut().take(CARDSNUMBER as usize) {
-            // Find and remove 90 from column 0
-            if let Some(pos) = card_numbers[0].iter().position(|&x| x == 90) {
-                card_numbers[0].remove(pos);
-                // Add 90 to column 8 and keep it sorted
-                card_numbers[8].push(90);
-                card_numbers[8].sort_unstable();
-            }
-        }
-
         let mut cards = Vec::new();
 
         for card_numbers in &cards_numbers {
-            let mut card = vec![vec![None; columns]; BOARDCONFIG.rows_per_card as usize];
+            let mut card = vec![vec![None; columns]; self.board_config.rows_per_card as usize];
 
             // Create a strategy to ensure each row has exactly cols_per_card numbers
             let row_assignment = self.calculate_row_assignments(card_numbers, columns);
@@ -243,36 +240,107 @@ impl CardManagement {
 
     fn calculate_row_assignments(&self, card_numbers: &[Vec<Number>], columns: usize) -> Vec<Vec<usize>> {
         let mut row_assignments = vec![Vec::new(); columns];
-        let mut row_counts = vec![0; BOARDCONFIG.rows_per_card as usize];
+        let mut row_counts = vec![0; self.board_config.rows_per_card as usize];
 
-        // Process each column and assign its numbers to rows
-        for col in 0..columns {
-            let numbers_in_column = card_numbers[col].len();
+        // Fill the fullest columns first, each one into distinct rows with the fewest numbers.
+        // Keeping the rows balanced this way always ends with cols_per_card numbers per row.
+        let mut column_order: Vec<usize> = (0..columns).collect();
+        column_order.sort_by_key(|&col| std::cmp::Reverse(card_numbers[col].len()));
+
+        for col in column_order {
+            let mut rows_by_count: Vec<usize> = (0..row_counts.len()).collect();
+            rows_by_count.sort_by_key(|&row| row_counts[row]);
 
-            // Assign each number in this column to the row with the fewest numbers
-            for _ in 0..numbers_in_column {
-                // Find the row with the minimum count
-                let min_count = *row_counts.iter().min().unwrap();
-                let target_row = row_counts.iter().position(|&count| count == min_count).unwrap();
+            let mut target_rows: Vec<usize> = rows_by_count.into_iter()
+                .take(card_numbers[col].len())
+                .collect();
+            target_rows.sort_unstable();
 
-                row_assignments[col].push(target_row);
-                row_counts[target_row] += 1;
+            for &row in &target_rows {
+                row_counts[row] += 1;
             }
+            row_assignments[col] = target_rows;
         }
 
         row_assignments
     }
 
-    #[must_use] pub fn generate_cards(&self, requested_cards: usize) -> Vec<CardWithId> {
+    /// Generate a 75-ball bingo card: five numbers from each B-I-N-G-O column range and a free centre square
+    #[must_use] pub fn generate_bingo75_card(&self) -> Card {
+        let mut rng = rng();
+        let size = BINGO75_LETTERS.len();
+        let span = self.board_config.total_numbers() / size;
+        let first_number = self.board_config.first_number as usize;
+
+        // Column c takes its numbers from first_number + c*span .. first_number + (c+1)*span (1-15, 16-30, ...)
+        let columns: Vec<Vec<Number>> = (0..size)
+            .map(|col| {
+                let mut range: Vec<Number> = (first_number + col * span..first_number + (col + 1) * span)
+                    .map(|number| number as Number)
+                    .collect();
+                range.shuffle(&mut rng);
+                range.truncate(size);
+                range.sort_unstable();
+                range
+            })
+            .collect();
+
+        (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|col| if row == size / 2 && col == size / 2 { None } else { Some(columns[col][row]) })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Generate independent 75-ball cards with unique IDs, none of them taken
+    fn generate_bingo75_cards(&self, requested_cards: usize, taken: &HashSet<u64>) -> Result<Vec<CardWithId>, String> {
+        const MAX_RETRIES: usize = 100;
+        let mut ids = taken.clone();
+        let mut cards = Vec::new();
+        let mut attempt = 0;
+
+        while cards.len() < requested_cards {
+            let card = self.generate_bingo75_card();
+            let id = self.generate_card_id(&card);
+            if ids.insert(id) {
+                cards.push(CardWithId { id, card });
+                attempt = 0;
+            } else {
+                attempt += 1;
+                if attempt >= MAX_RETRIES {
+                    return Err(format!("Could not generate a card different from the other cards after {MAX_RETRIES} attempts"));
+                }
+            }
+        }
+
+        Ok(cards)
+    }
+
+    pub fn generate_cards(&self, requested_cards: usize) -> Result<Vec<CardWithId>, String> {
+        self.generate_unique_cards(requested_cards, &HashSet::new())
+    }
+
+    /// Generate cards whose IDs are all different from each other and from the `taken` ones
+    pub fn generate_unique_cards(&self, requested_cards: usize, taken: &HashSet<u64>) -> Result<Vec<CardWithId>, String> {
+        // 75-ball cards are not built in series, every card is drawn on its own
+        if !self.has_series() {
+            return self.generate_bingo75_cards(requested_cards, taken);
+        }
+        const MAX_RETRIES: usize = 100;
+
         let mut all_cards = Vec::new();
         let mut remaining_cards = requested_cards;
         let mut rng = rng();
-        let mut global_ids = HashSet::new();
+        let mut global_ids = taken.clone();
         let mut total_regenerations = 0;
 
-        // Generate complete blocks of CARDSNUMBER cards
-        while remaining_cards > CARDSNUMBER as usize {
-            let block = self.generate_card_group_with_ids();
+        let cards_number = self.board_config.cards_number() as usize;
+
+        // Generate complete blocks of cards_number cards
+        while remaining_cards > cards_number {
+            let block = self.generate_card_group_with_ids()?;
 
             // Check for global duplicates across all generated blocks
             let mut has_global_duplicates = false;
@@ -286,6 +354,9 @@ impl CardManagement {
             if has_global_duplicates {
                 println!("Global duplicate ID detected across blocks, regenerating block");
                 total_regenerations += 1;
+                if total_regenerations >= MAX_RETRIES {
+                    return Err(format!("Could not generate cards different from the other cards after {MAX_RETRIES} attempts"));
+                }
                 continue; // Regenerate this block
             }
 
@@ -295,13 +366,13 @@ impl CardManagement {
             }
 
             all_cards.extend(block);
-            remaining_cards -= CARDSNUMBER as usize;
+            remaining_cards -= cards_number;
         }
 
         // Handle remaining cards
         if remaining_cards > 0 {
             loop {
-                let mut final_block = self.generate_card_group_with_ids();
+                let mut final_block = self.generate_card_group_with_ids()?;
 
                 // Check for global duplicates
                 let mut has_global_duplicates = false;
@@ -315,11 +386,14 @@ impl CardManagement {
                 if has_global_duplicates {
                     println!("Global duplicate ID detected in final block, regenerating");
                     total_regenerations += 1;
+                    if total_regenerations >= MAX_RETRIES {
+                        return Err(format!("Could not generate cards different from the other cards after {MAX_RETRIES} attempts"));
+                    }
                     continue; // Regenerate this block
                 }
 
                 // If we need fewer cards than a complete block, randomly select them
-                if remaining_cards < CARDSNUMBER as usize {
+                if remaining_cards < cards_number {
                     final_block.shuffle(&mut rng);
                     final_block.truncate(remaining_cards);
                 }
@@ -333,36 +407,77 @@ impl CardManagement {
             println!("Total block regenerations due to global duplicates: {total_regenerations}");
         }
 
-        all_cards
+        Ok(all_cards)
     }
 
-    fn generate_card_id(&self, card: &Card) -> u64 {
-        let mut hasher = DefaultHasher::new();
-
-        // Hash the card content in a deterministic way
-        for row in card {
-            for cell in row {
-                match cell {
-                    Some(number) => {
-                        hasher.write_u8(*number);
-                    }
-                    None => {
-                        hasher.write_u8(0); // Use 0 to represent None
-                    }
+    /// Whether the cards of the layout are built in series covering every number of the board
+    #[must_use] pub fn has_series(&self) -> bool {
+        self.ruleset.ruleset().card_layout() != CardLayout::Bingo75
+    }
+
+    /// Generate a complete series of cards, none of them taken
+    pub fn generate_series(&self, taken: &HashSet<u64>) -> Result<Vec<CardWithId>, String> {
+        const MAX_RETRIES: usize = 100;
+        for _ in 0..MAX_RETRIES {
+            let series = self.generate_card_group_with_ids()?;
+            if series.iter().all(|card_with_id| !taken.contains(&card_with_id.id)) {
+                return Ok(series);
+            }
+            println!("Taken card ID detected in series, regenerating");
+        }
+        Err(format!("Could not generate a series of cards different from the other cards after {MAX_RETRIES} attempts"))
+    }
+
+    /// ID of a card, derived from its content
+    #[must_use] pub fn card_id(&self, card: &Card) -> u64 {
+        self.generate_card_id(card)
+    }
+
+    /// Check that a card made elsewhere follows the rules of the generated ones:
+    /// the rows of the card, `cols_per_card` numbers in each row and every number in the column of its range
+    pub fn validate_card(&self, card: &Card) -> Result<(), String> {
+        let config = &self.board_config;
+        if card.len() != config.rows_per_card as usize {
+            return Err(format!("A card has {} rows, got {}", config.rows_per_card, card.len()));
+        }
+
+        let mut numbers = HashSet::new();
+        for (row_index, row) in card.iter().enumerate() {
+            let row_number = row_index + 1;
+            if row.len() != config.card_columns() {
+                return Err(format!("Row {row_number} has {} cells instead of {}", row.len(), config.card_columns()));
+            }
+            let count = row.iter().flatten().count();
+            if count != config.cols_per_card as usize {
+                return Err(format!("Row {row_number} has {count} numbers instead of {}", config.cols_per_card));
+            }
+            for (col, number) in row.iter().enumerate().filter_map(|(col, cell)| cell.map(|number| (col, number))) {
+                if !(config.first_number..=config.last_number()).contains(&number) {
+                    return Err(format!("Number {number} is out of the range {}-{}", config.first_number, config.last_number()));
+                }
+                if config.card_column_of(number) != col {
+                    return Err(format!("Number {number} is in column {} instead of column {}", col + 1, config.card_column_of(number) + 1));
+                }
+                if !numbers.insert(number) {
+                    return Err(format!("Number {number} appears twice"));
                 }
             }
         }
+        Ok(())
+    }
 
-        hasher.finish()
+    fn generate_card_id(&self, card: &Card) -> u64 {
+        // Stable hash of the card content, see the serial module
+        serial::card_id(card)
     }
 
-    #[must_use] pub fn generate_card_group_with_ids(&self) -> Vec<CardWithId> {
+    pub fn generate_card_group_with_ids(&self) -> Result<Vec<CardWithId>, String> {
         const MAX_RETRIES: usize = 100;
         let mut attempt = 0;
 
         loop {
             attempt += 1;
-            let cards = self.generate_card_group();
+            let cards = self.generate_card_group()?;
             let mut cards_with_ids = Vec::new();
             let mut seen_ids = HashSet::new();
             let mut has_duplicates = false;
@@ -383,18 +498,11 @@ impl CardManagement {
                     let id = self.generate_card_id(&card);
                     cards_with_ids.push(CardWithId { id, card });
                 }
-                return cards_with_ids;
+                return Ok(cards_with_ids);
             }
 
             if attempt >= MAX_RETRIES {
-                eprintln!("Warning: Could not generate unique card IDs after {MAX_RETRIES} attempts");
-                eprintln!("Proceeding with potentially duplicate IDs");
-                // Create CardWithId structs even with duplicates
-                for card in cards {
-                    let id = self.generate_card_id(&card);
-                    cards_with_ids.push(CardWithId { id, card });
-                }
-                return cards_with_ids;
+                return Err(format!("Could not generate unique card IDs after {MAX_RETRIES} attempts"));
             }
 
             println!("Duplicate card ID detected, regenerating group (attempt {attempt})");
@@ -403,8 +511,8 @@ impl CardManagement {
 
 
 
-    /// Generate cards and handle complete assignment process
-    #[must_use] pub fn generate_and_assign_cards(&self, count: u32, client_id: &str, client_type: Option<&str>) -> (Vec<CardInfo>, Vec<String>, Vec<CardAssignment>) {
+    /// Generate cards different from the `taken` ones and handle complete assignment process
+    pub fn generate_and_assign_cards(&self, count: u32, client_id: &str, client_type: Option<&str>, taken: &HashSet<u64>) -> Result<GeneratedCards, String> {
         // Check if this is a board client
         let is_board_client = client_type == Some("board");
 
@@ -412,9 +520,14 @@ impl CardManagement {
             // For board clients, generate a special board card with BOARD_ID
             self.generate_board_card()
         } else {
-            self.generate_cards(count as usize)
+            self.generate_unique_cards(count as usize, taken)?
         };
 
+        Ok(Self::assign_generated_cards(cards_with_ids, client_id, is_board_client))
+    }
+
+    /// Build the responses and the assignments of cards generated for a client
+    #[must_use] pub fn assign_generated_cards(cards_with_ids: Vec<CardWithId>, client_id: &str, is_board_client: bool) -> GeneratedCards {
         let mut card_infos = Vec::new();
         let mut client_card_ids = Vec::new();
         let mut assignments = Vec::new();
@@ -424,7 +537,7 @@ impl CardManagement {
                 // Use the constant BOARD_ID for board clients
                 BOARD_ID.to_string()
             } else {
-                format!("{:016X}", card_with_id.id)
+                serial::serial_of(card_with_id.id)
             };
 
             // Add to client's card list (clone needed for multiple uses)
@@ -462,16 +575,16 @@ impl CardManagement {
     // Create the board card data representing the entire game board
     fn create_board_card(&self) -> Card {
         let mut card = Vec::new();
-        let total_rows = BOARDCONFIG.cards_per_col as usize * BOARDCONFIG.rows_per_card as usize;
-        let total_cols = BOARDCONFIG.cards_per_row as usize * BOARDCONFIG.cols_per_card as usize;
+        let total_rows = self.board_config.cards_per_col as usize * self.board_config.rows_per_card as usize;
+        let total_cols = self.board_config.numbers_per_row() as usize;
 
         for row in 0..total_rows {
             let mut card_row = Vec::new();
             for col in 0..total_cols {
                 // Calculate the number for this position
-                let number = FIRSTNUMBER + (row * total_cols + col) as Number;
-                if number <= LASTNUMBER {
-                    card_row.push(Some(number));
+                let number = self.board_config.first_number as usize + row * total_cols + col;
+                if number <= self.board_config.last_number() as usize {
+                    card_row.push(Some(number as Number));
                 } else {
                     card_row.push(None);
                 }
@@ -488,6 +601,18 @@ impl CardManagement {
 pub struct CardAssignmentManager {
     assignments: HashMap<String, CardAssignment>,
     client_cards: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    board_config: BoardStruct,
+    #[serde(default)]
+    ruleset: RulesetKind,
+    #[serde(default)]
+    distribution: CardDistribution,
+    #[serde(default)]
+    series_pool: Vec<Card>,  // Cards of the current series not dealt yet
+    #[serde(skip)]
+    opened_series: Vec<Card>,  // Cards of the series opened since the game last journaled them
+    #[serde(default)]
+    inventory: HashMap<String, Card>,  // Imported cards not assigned yet, by serial
 }
 
 impl Default for CardAssignmentManager {
@@ -498,19 +623,59 @@ impl Default for CardAssignmentManager {
 
 impl CardAssignmentManager {
     #[must_use] pub fn new() -> Self {
+        Self::with_config(BOARDCONFIG)
+    }
+
+    /// Create a manager that generates cards for the given board geometry
+    #[must_use] pub fn with_config(board_config: BoardStruct) -> Self {
+        Self::with_rules(board_config, RulesetKind::default())
+    }
+
+    /// Create a manager that generates cards for the given board geometry and ruleset
+    #[must_use] pub fn with_rules(board_config: BoardStruct, ruleset: RulesetKind) -> Self {
         Self {
             assignments: HashMap::new(),
             client_cards: HashMap::new(),
+            board_config,
+            ruleset,
+            distribution: CardDistribution::default(),
+            series_pool: Vec::new(),
+            opened_series: Vec::new(),
+            inventory: HashMap::new(),
+        }
+    }
+
+    #[must_use] pub fn board_config(&self) -> &BoardStruct {
+        &self.board_config
+    }
+
+    #[must_use] pub fn distribution(&self) -> CardDistribution {
+        self.distribution
+    }
+
+    /// Set how the next cards are dealt
+    pub fn set_distribution(&mut self, distribution: CardDistribution) -> Result<(), String> {
+        if distribution == CardDistribution::Series && !CardManagement::with_rules(self.board_config, self.ruleset).has_series() {
+            return Err(format!("The cards of the {} ruleset are not built in series", self.ruleset.as_str()));
         }
+        self.distribution = distribution;
+        Ok(())
     }
 
-    pub fn assign_cards(&mut self, client_id: &str, count: u32) -> (Vec<CardInfo>, Vec<String>) {
+    pub fn assign_cards(&mut self, client_id: &str, count: u32) -> Result<(Vec<CardInfo>, Vec<String>), String> {
         self.assign_cards_with_type(client_id, count, None)
     }
 
-    pub fn assign_cards_with_type(&mut self, client_id: &str, count: u32, client_type: Option<&str>) -> (Vec<CardInfo>, Vec<String>) {
-        let card_management = CardManagement::new();
-        let (card_infos, client_card_ids, assignments) = card_management.generate_and_assign_cards(count, client_id, client_type);
+    /// Assign new cards to a client, each one different from every card of the game
+    pub fn assign_cards_with_type(&mut self, client_id: &str, count: u32, client_type: Option<&str>) -> Result<(Vec<CardInfo>, Vec<String>), String> {
+        let card_management = CardManagement::with_rules(self.board_config, self.ruleset);
+        let taken = self.taken_card_ids(&card_management);
+        let (card_infos, client_card_ids, assignments) = if client_type != Some("board") && self.distribution == CardDistribution::Series {
+            let cards = self.deal_from_series(&card_management, count as usize, taken)?;
+            CardManagement::assign_generated_cards(cards, client_id, false)
+        } else {
+            card_management.generate_and_assign_cards(count, client_id, client_type, &taken)?
+        };
 
         // Store assignments
         for assignment in assignments {
@@ -521,7 +686,128 @@ impl CardAssignmentManager {
         // Store client's card IDs (clone needed since we return it too)
         self.client_cards.insert(client_id.to_string(), client_card_ids.clone());
 
-        (card_infos, client_card_ids)
+        Ok((card_infos, client_card_ids))
+    }
+
+    // IDs of the content of the cards assigned in the game, waiting in the current series or in the inventory
+    fn taken_card_ids(&self, card_management: &CardManagement) -> HashSet<u64> {
+        self.assignments.values()
+            .filter(|assignment| assignment.card_id != BOARD_ID)
+            .map(|assignment| &assignment.card_data)
+            .chain(&self.series_pool)
+            .chain(self.inventory.values())
+            .map(|card| card_management.card_id(card))
+            .collect()
+    }
+
+    // Deal cards in order from the current series, starting a new series when it runs out
+    // The cards are dealt from a copy of the pool, kept only once every card is dealt
+    fn deal_from_series(&mut self, card_management: &CardManagement, count: usize, mut taken: HashSet<u64>) -> Result<Vec<CardWithId>, String> {
+        let mut pool = self.series_pool.clone();
+        let mut opened = Vec::new();
+        let mut cards = Vec::with_capacity(count);
+        while cards.len() < count {
+            if pool.is_empty() {
+                let series = card_management.generate_series(&taken)?;
+                taken.extend(series.iter().map(|card_with_id| card_with_id.id));
+                pool = series.into_iter().map(|card_with_id| card_with_id.card).collect();
+                opened.extend(pool.iter().cloned());
+            }
+            let card = pool.remove(0);
+            cards.push(CardWithId { id: card_management.card_id(&card), card });
+        }
+        self.series_pool = pool;
+        self.opened_series.extend(opened);
+        Ok(cards)
+    }
+
+    /// Cards of the current series not dealt yet
+    #[must_use] pub fn series_pool(&self) -> &[Card] {
+        &self.series_pool
+    }
+
+    /// Cards of the series opened by the last assignments, each series once
+    pub fn take_opened_series(&mut self) -> Vec<Card> {
+        std::mem::take(&mut self.opened_series)
+    }
+
+    /// Add the cards of a series opened elsewhere to the cards waiting to be dealt
+    pub fn open_series(&mut self, cards: Vec<Card>) {
+        self.series_pool.extend(cards);
+    }
+
+    /// Store card assignments generated elsewhere, as `assign_cards_with_type` stores the ones it generates
+    pub fn restore_assignments(&mut self, client_id: &str, assignments: Vec<CardAssignment>) {
+        let client_card_ids = assignments.iter().map(|assignment| assignment.card_id.clone()).collect();
+        self.series_pool.retain(|card| assignments.iter().all(|assignment| assignment.card_data != *card));
+        for assignment in assignments {
+            self.inventory.remove(&assignment.card_id);
+            self.assignments.insert(assignment.card_id.clone(), assignment);
+        }
+        self.client_cards.insert(client_id.to_string(), client_card_ids);
+    }
+
+    /// Add cards made elsewhere, such as pre-printed ones, to the inventory of the game, returning their serials.
+    /// No card is added unless every card is valid and different from the other cards of the game.
+    pub fn import_cards(&mut self, cards: Vec<Card>) -> Result<Vec<String>, String> {
+        let card_management = CardManagement::with_rules(self.board_config, self.ruleset);
+        if !card_management.has_series() {
+            return Err(format!("The cards of the {} ruleset cannot be imported", self.ruleset.as_str()));
+        }
+
+        let mut taken = self.taken_card_ids(&card_management);
+        for (index, card) in cards.iter().enumerate() {
+            card_management.validate_card(card).map_err(|e| format!("Card {}: {e}", index + 1))?;
+            if !taken.insert(card_management.card_id(card)) {
+                return Err(format!("Card {}: the card is already in the game", index + 1));
+            }
+        }
+
+        let serials = cards.iter().map(serial::card_serial).collect();
+        for card in cards {
+            self.inventory.insert(serial::card_serial(&card), card);
+        }
+        Ok(serials)
+    }
+
+    /// Assign cards of the inventory to a client by serial, after the cards the client already holds.
+    /// Returns the assignments of every card of the client.
+    pub fn assign_inventory_cards(&mut self, client_id: &str, serials: &[String]) -> Result<Vec<CardAssignment>, String> {
+        let mut unique = HashSet::new();
+        for serial in serials {
+            if !unique.insert(serial) {
+                return Err(format!("Card {serial} is requested twice"));
+            }
+            if self.assignments.contains_key(serial) {
+                return Err(format!("Card {serial} is already assigned"));
+            }
+            if !self.inventory.contains_key(serial) {
+                return Err(format!("Card {serial} is not in the inventory of the game"));
+            }
+        }
+
+        let client_card_ids = self.client_cards.entry(client_id.to_string()).or_default();
+        for serial in serials {
+            if let Some(card_data) = self.inventory.remove(serial) {
+                let assignment = CardAssignment { card_id: serial.clone(), client_id: client_id.to_string(), card_data };
+                self.assignments.insert(serial.clone(), assignment);
+                client_card_ids.push(serial.clone());
+            }
+        }
+
+        Ok(client_card_ids.iter()
+            .filter_map(|card_id| self.assignments.get(card_id).cloned())
+            .collect())
+    }
+
+    /// Imported card not assigned yet
+    #[must_use] pub fn inventory_card(&self, serial: &str) -> Option<&Card> {
+        self.inventory.get(serial)
+    }
+
+    /// Number of imported cards not assigned yet
+    #[must_use] pub fn inventory_len(&self) -> usize {
+        self.inventory.len()
     }
 
     /// Enhanced card assignment that respects game state and client's current card status
@@ -572,9 +858,7 @@ impl CardAssignmentManager {
         }
 
         // Generate new cards
-        let (card_infos, client_card_ids) = self.assign_cards_with_type(client_id, count, client_type);
-
-        Ok((card_infos, client_card_ids))
+        self.assign_cards_with_type(client_id, count, client_type)
     }
 
     #[must_use] pub fn client_owns_card(&self, client_id: &str, card_id: &str) -> bool {
@@ -750,4 +1034,224 @@ mod tests {
         assert_eq!(assigned_cards.len(), 1, "Should have exactly one card assigned");
         assert_eq!(assigned_cards[0].assigned_to, client_id);
     }
+
+    fn assert_valid_card_group(cards: &[Card], config: &BoardStruct) {
+        assert_eq!(cards.len(), config.cards_number() as usize, "A group should contain one full series of cards");
+
+        let mut all_numbers: Vec<Number> = Vec::new();
+        for card in cards {
+            assert_eq!(card.len(), config.rows_per_card as usize, "Card should have rows_per_card rows");
+            for row in card {
+                assert_eq!(row.len(), config.card_columns(), "Row should have card_columns() cells");
+                assert_eq!(row.iter().flatten().count(), config.cols_per_card as usize, "Row should have cols_per_card numbers");
+                for (col, cell) in row.iter().enumerate() {
+                    if let Some(number) = cell {
+                        assert_eq!(config.card_column_of(*number), col, "Number {number} is in the wrong column");
+                        all_numbers.push(*number);
+                    }
+                }
+            }
+        }
+
+        all_numbers.sort_unstable();
+        let expected: Vec<Number> = (config.first_number..=config.last_number()).collect();
+        assert_eq!(all_numbers, expected, "A series should contain every number exactly once");
+    }
+
+    #[test]
+    fn test_generate_card_group_classic_geometry() {
+        let card_management = CardManagement::new();
+        for _ in 0..20 {
+            assert_valid_card_group(&card_management.generate_card_group().unwrap(), &BOARDCONFIG);
+        }
+    }
+
+    #[test]
+    fn test_generate_card_group_custom_geometry() {
+        let config = BoardStruct {
+            cols_per_card: 4,
+            rows_per_card: 3,
+            cards_per_row: 2,
+            cards_per_col: 3,
+            first_number: 10,
+            ..BOARDCONFIG
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.last_number(), 81);
+
+        let card_management = CardManagement::with_config(config);
+        for _ in 0..20 {
+            assert_valid_card_group(&card_management.generate_card_group().unwrap(), &config);
+        }
+
+        // The board card covers the whole custom range
+        let mut manager = CardAssignmentManager::with_config(config);
+        let (cards, _) = manager.assign_cards_with_type("board_client", 1, Some("board")).unwrap();
+        let board_numbers: Vec<Number> = cards[0].card_data.iter().flatten().flatten().copied().collect();
+        assert_eq!(board_numbers, (10..=81).collect::<Vec<Number>>());
+    }
+
+    #[test]
+    fn test_generate_bingo75_cards() {
+        let card_management = CardManagement::with_rules(crate::defs::BINGO75_BOARDCONFIG, RulesetKind::Bingo75);
+        let cards = card_management.generate_cards(4).unwrap();
+        assert_eq!(cards.len(), 4);
+
+        for card_with_id in &cards {
+            let card = &card_with_id.card;
+            assert_eq!(card.len(), 5);
+            assert!(card.iter().all(|row| row.len() == 5));
+            assert_eq!(card[2][2], None, "Centre square must be free");
+
+            // Each column stays within its B-I-N-G-O range
+            for (col, range_start) in [1, 16, 31, 46, 61].into_iter().enumerate() {
+                for number in card.iter().filter_map(|row| row[col]) {
+                    assert!((range_start..range_start + 15).contains(&number));
+                }
+            }
+            let mut numbers: Vec<Number> = card.iter().flatten().flatten().copied().collect();
+            numbers.sort_unstable();
+            numbers.dedup();
+            assert_eq!(numbers.len(), 24);
+        }
+    }
+
+    #[test]
+    fn test_cards_unique_in_game() {
+        let mut manager = CardAssignmentManager::new();
+        manager.assign_cards_with_type("board_client", 1, Some("board")).unwrap();
+        for i in 0..10 {
+            manager.assign_cards(&format!("client_{i}"), 6).unwrap();
+        }
+
+        let card_management = CardManagement::new();
+        let ids: HashSet<u64> = manager.assignments.values()
+            .filter(|assignment| assignment.card_id != BOARD_ID)
+            .map(|assignment| card_management.card_id(&assignment.card_data))
+            .collect();
+        assert_eq!(ids.len(), 60, "Every card of the game should be different");
+    }
+
+    #[test]
+    fn test_series_distribution() {
+        let mut manager = CardAssignmentManager::new();
+        manager.set_distribution(CardDistribution::Series).unwrap();
+        assert_eq!(manager.distribution(), CardDistribution::Series);
+
+        // Two clients share the first series, in the order of their requests
+        manager.assign_cards("alice", 4).unwrap();
+        manager.assign_cards("bob", 2).unwrap();
+        let series: Vec<Card> = ["alice", "bob"].iter()
+            .flat_map(|client_id| manager.get_client_assigned_cards(client_id))
+            .map(|info| manager.get_card_assignment(&info.card_id).unwrap().card_data.clone())
+            .collect();
+        assert_valid_card_group(&series, &BOARDCONFIG);
+        assert!(manager.series_pool.is_empty());
+
+        // The next request opens a new series, kept for the following clients
+        manager.assign_cards("carol", 1).unwrap();
+        assert_eq!(manager.series_pool.len(), 5);
+
+        let mut bingo75 = CardAssignmentManager::with_rules(crate::defs::BINGO75_BOARDCONFIG, RulesetKind::Bingo75);
+        assert!(bingo75.set_distribution(CardDistribution::Series).is_err());
+        assert_eq!(bingo75.distribution(), CardDistribution::Random);
+    }
+
+    #[test]
+    fn test_exhausted_geometry_fails_without_losing_cards() {
+        // Ten cards of a single number: every series holds all of them, so no second series exists
+        let config = BoardStruct { cols_per_card: 1, rows_per_card: 1, cards_per_row: 10, cards_per_col: 1, ..BOARDCONFIG };
+        assert!(config.validate().is_ok());
+
+        let mut manager = CardAssignmentManager::with_config(config);
+        manager.set_distribution(CardDistribution::Series).unwrap();
+        manager.assign_cards("alice", 6).unwrap();
+        let pool = manager.series_pool().to_vec();
+        assert!(manager.assign_cards("bob", 5).is_err());
+        assert_eq!(manager.series_pool(), pool.as_slice(), "A failed deal should keep the undealt cards");
+        assert!(manager.get_client_assigned_cards("bob").is_empty());
+        manager.assign_cards("bob", 4).unwrap();
+
+        let mut random = CardAssignmentManager::with_config(config);
+        random.assign_cards("alice", 10).unwrap();
+        assert!(random.assign_cards("bob", 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_card() {
+        let card_management = CardManagement::new();
+        for card in card_management.generate_card_group().unwrap() {
+            assert!(card_management.validate_card(&card).is_ok());
+        }
+
+        let card: Card = vec![
+            vec![Some(1), None, Some(23), None, Some(45), None, Some(67), None, Some(89)],
+            vec![None, Some(12), None, Some(34), None, Some(56), None, Some(78), Some(90)],
+            vec![Some(5), Some(17), Some(29), None, Some(41), None, None, Some(73), None],
+        ];
+        assert!(card_management.validate_card(&card).is_ok());
+        assert!(card_management.validate_card(&card[..2].to_vec()).is_err());
+
+        let mut wrong_column = card.clone();
+        wrong_column[0].swap(0, 1);
+        assert_eq!(card_management.validate_card(&wrong_column), Err("Number 1 is in column 2 instead of column 1".to_string()));
+
+        let mut short_row = card.clone();
+        short_row[1][1] = None;
+        assert_eq!(card_management.validate_card(&short_row), Err("Row 2 has 4 numbers instead of 5".to_string()));
+
+        let mut repeated = card.clone();
+        repeated[2][0] = Some(1);
+        assert_eq!(card_management.validate_card(&repeated), Err("Number 1 appears twice".to_string()));
+
+        let mut out_of_range = card.clone();
+        out_of_range[0][8] = Some(91);
+        assert!(card_management.validate_card(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_import_and_assign_inventory_cards() {
+        let mut manager = CardAssignmentManager::new();
+        let series = CardManagement::new().generate_card_group().unwrap();
+        let serials = manager.import_cards(series.clone()).unwrap();
+        assert_eq!(serials, series.iter().map(serial::card_serial).collect::<Vec<String>>());
+        assert_eq!(manager.inventory_len(), 6);
+        assert_eq!(manager.inventory_card(&serials[0]), Some(&series[0]));
+
+        // Nothing is imported from a set with a card already in the game or an invalid card
+        assert_eq!(manager.import_cards(vec![series[1].clone()]), Err("Card 1: the card is already in the game".to_string()));
+        let mut invalid = series[0].clone();
+        invalid[0].reverse();
+        assert!(manager.import_cards(vec![invalid]).is_err());
+        assert_eq!(manager.inventory_len(), 6);
+
+        // The imported cards are assigned by serial, after the cards of the client
+        manager.assign_cards("alice", 1).unwrap();
+        let assigned = manager.assign_inventory_cards("alice", &serials[..2]).unwrap();
+        assert_eq!(assigned.len(), 3);
+        assert_eq!(assigned[1].card_data, series[0]);
+        assert_eq!(manager.get_client_cards("alice").unwrap()[1..], serials[..2]);
+        assert_eq!(manager.inventory_len(), 4);
+        assert_eq!(manager.inventory_card(&serials[0]), None);
+
+        assert_eq!(manager.assign_inventory_cards("bob", &serials[1..3]).unwrap_err(), format!("Card {} is already assigned", serials[1]));
+        assert!(manager.assign_inventory_cards("bob", &[serials[2].clone(), serials[2].clone()]).is_err());
+        assert!(manager.assign_inventory_cards("bob", &[serial::serial_of(42)]).is_err());
+        assert_eq!(manager.inventory_len(), 4);
+        assert!(manager.get_client_cards("bob").is_none());
+
+        let mut bingo75 = CardAssignmentManager::with_rules(crate::defs::BINGO75_BOARDCONFIG, RulesetKind::Bingo75);
+        assert!(bingo75.import_cards(CardManagement::with_rules(crate::defs::BINGO75_BOARDCONFIG, RulesetKind::Bingo75).generate_cards(1).unwrap().into_iter().map(|card| card.card).collect()).is_err());
+    }
+
+    #[test]
+    fn test_board_geometry_validation() {
+        assert!(BOARDCONFIG.validate().is_ok());
+        assert!(BoardStruct { cols_per_card: 0, ..BOARDCONFIG }.validate().is_err());
+        assert!(BoardStruct { first_number: 0, ..BOARDCONFIG }.validate().is_err());
+        // 3 x 5 x 6 x 6 = 540 numbers do not fit in a Number
+        assert!(BoardStruct { cards_per_row: 6, cards_per_col: 6, ..BOARDCONFIG }.validate().is_err());
+        // 20 numbers give only 2 card columns for 5 numbers per row
+        assert!(BoardStruct { rows_per_card: 1, cards_per_row: 2, cards_per_col: 2, ..BOARDCONFIG }.validate().is_err());
+    }
 }
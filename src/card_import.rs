@@ -0,0 +1,140 @@
+// src/card_import.rs
+// Import of pre-printed card series, such as the paper cards sold at a physical event.
+// The cards are read from CSV or JSON, checked against the rules of the generated cards,
+// and kept in the inventory of the game until the owner assigns them to players by serial.
+//
+// CSV: one line per card row with one cell per card column, empty or 0 for the blank cells,
+// every `rows_per_card` lines making a card. Cells are separated by commas or semicolons,
+// and empty lines and lines starting with '#' are skipped.
+// JSON: {"cards": [card, ...]}, each card being an array of rows as the `card_data` of the API.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::Card;
+use crate::defs::Number;
+
+/// Format of an imported card file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// Format of a request body, CSV for `text/csv` and JSON otherwise
+    #[must_use] pub fn from_content_type(content_type: Option<&str>) -> Self {
+        match content_type {
+            Some(content_type) if content_type.trim_start().starts_with("text/csv") => ImportFormat::Csv,
+            _ => ImportFormat::Json,
+        }
+    }
+
+    /// Format of a file, CSV for the `.csv` extension and JSON otherwise
+    #[must_use] pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => ImportFormat::Csv,
+            _ => ImportFormat::Json,
+        }
+    }
+
+    #[must_use] pub fn content_type(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "text/csv",
+            ImportFormat::Json => "application/json",
+        }
+    }
+}
+
+/// Cards of a JSON import
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportCardsRequest {
+    pub cards: Vec<Card>,
+}
+
+/// Request of the owner assigning imported cards to a client of the game
+#[derive(Debug, Deserialize)]
+pub struct AssignCardsRequest {
+    pub client_id: String,
+    pub serials: Vec<String>,
+}
+
+/// Read the cards of an import, leaving their validation to `CardAssignmentManager::import_cards`
+pub fn parse_cards(text: &str, format: ImportFormat, rows_per_card: usize) -> Result<Vec<Card>, String> {
+    let cards = match format {
+        ImportFormat::Csv => parse_csv(text, rows_per_card)?,
+        ImportFormat::Json => serde_json::from_str::<ImportCardsRequest>(text)
+            .map_err(|e| format!("Invalid JSON card file: {e}"))?
+            .cards,
+    };
+    if cards.is_empty() {
+        return Err("No cards to import".to_string());
+    }
+    Ok(cards)
+}
+
+fn parse_csv(text: &str, rows_per_card: usize) -> Result<Vec<Card>, String> {
+    let mut rows = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let row = line.split([',', ';'])
+            .map(|cell| parse_cell(cell).map_err(|e| format!("Line {}: {e}", index + 1)))
+            .collect::<Result<Vec<Option<Number>>, String>>()?;
+        rows.push(row);
+    }
+
+    if rows_per_card == 0 || rows.len() % rows_per_card != 0 {
+        return Err(format!("The CSV file has {} card rows, not a multiple of the {rows_per_card} rows of a card", rows.len()));
+    }
+    Ok(rows.chunks(rows_per_card).map(<[Vec<Option<Number>>]>::to_vec).collect())
+}
+
+fn parse_cell(cell: &str) -> Result<Option<Number>, String> {
+    let cell = cell.trim().trim_matches('"').trim();
+    if cell.is_empty() {
+        return Ok(None);
+    }
+    match cell.parse::<Number>() {
+        Ok(0) => Ok(None),
+        Ok(number) => Ok(Some(number)),
+        Err(_) => Err(format!("'{cell}' is not a number")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cards() {
+        let csv = "# Series 1, card 1\n\
+                   1,,23,,45,,67,,89\n\
+                   ;12;;34;;56;;78;90\n\
+                   5,17,29,0,41,,,73,\n\
+                   \n\
+                   2,,24,,46,,68,,88\n\
+                   ,13,,35,,57,,79,87\n\
+                   6,18,\"28\",,42,,,74,\n";
+        let cards = parse_cards(csv, ImportFormat::Csv, 3).unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0][1], vec![None, Some(12), None, Some(34), None, Some(56), None, Some(78), Some(90)]);
+        assert_eq!(cards[0][2][3], None);
+        assert_eq!(cards[1][2][2], Some(28));
+
+        let json = serde_json::to_string(&ImportCardsRequest { cards: cards.clone() }).unwrap();
+        assert_eq!(parse_cards(&json, ImportFormat::Json, 3).unwrap(), cards);
+
+        assert_eq!(parse_cards("1,x,3", ImportFormat::Csv, 1), Err("Line 1: 'x' is not a number".to_string()));
+        assert!(parse_cards("1,,3\n4,,6", ImportFormat::Csv, 3).is_err());
+        assert!(parse_cards("# nothing", ImportFormat::Csv, 3).is_err());
+        assert!(parse_cards("[[1]]", ImportFormat::Json, 3).is_err());
+
+        assert_eq!(ImportFormat::from_content_type(Some("text/csv; charset=utf-8")), ImportFormat::Csv);
+        assert_eq!(ImportFormat::from_content_type(None), ImportFormat::Json);
+        assert_eq!(ImportFormat::from_path(Path::new("cards.CSV")), ImportFormat::Csv);
+    }
+}
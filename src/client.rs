@@ -1,9 +1,10 @@
 use std::collections::HashMap;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::Hasher;
 use std::sync::{Arc, Mutex};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::auth::{generate_api_key, hash_api_key};
+
 // Client registration structures
 #[derive(Debug, Deserialize, Clone)]
 pub struct RegisterRequest {
@@ -11,11 +12,16 @@ pub struct RegisterRequest {
     pub client_type: String,
     pub nocard: Option<u32>,  // Number of cards to generate during registration
     pub email: Option<String>,  // Optional email for registration
+    #[serde(default)]
+    pub allow_duplicate_name: bool,  // Register a new client even if another one has the same name
 }
 
 #[derive(Debug, Serialize)]
 pub struct RegisterResponse {
     pub client_id: String,
+    pub token: String,  // Bearer token authenticating the requests of the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,  // Secret API key, only returned when it is issued
     pub message: String,
 }
 
@@ -35,24 +41,14 @@ pub struct ClientInfo {
     pub client_type: String,
     pub registered_at: std::time::SystemTime,
     pub email: String,  // Internal field, not exposed through APIs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_hash: Option<String>,  // SHA-256 of the API key, the key itself is never stored
 }
 
 impl ClientInfo {
     #[must_use] pub fn new(name: &str, client_type: &str, email: &str) -> Self {
-        // Generate a client ID based on name, type, and current time
-        let mut hasher = DefaultHasher::new();
-
-        // Include name, type, and current time for uniqueness
-        hasher.write(name.as_bytes());
-        hasher.write(client_type.as_bytes());
-
-        // Use high-resolution timestamp for better uniqueness
-        if let Ok(duration) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
-            hasher.write(&duration.as_nanos().to_le_bytes());
-        }
-
-        let hash = hasher.finish();
-        let client_id = format!("{hash:016X}");
+        // The client ID is public, it is random so that it tells nothing about the client
+        let client_id = format!("{:016X}", rand::rng().random::<u64>());
 
         ClientInfo {
             id: client_id,
@@ -60,12 +56,25 @@ impl ClientInfo {
             client_type: client_type.to_string(),
             registered_at: std::time::SystemTime::now(),
             email: email.to_string(),
+            api_key_hash: None,
         }
     }
 
     #[must_use] pub fn client_id(&self) -> &str {
         &self.id
     }
+
+    /// Generate a new API key for the client, replacing the previous one, and return it
+    pub fn new_api_key(&mut self) -> String {
+        let api_key = generate_api_key();
+        self.api_key_hash = Some(hash_api_key(&api_key));
+        api_key
+    }
+
+    /// Identifier of the current API key, carried by the tokens issued with it
+    #[must_use] pub fn key_id(&self) -> Option<&str> {
+        self.api_key_hash.as_deref().map(|hash| &hash[..16])
+    }
 }
 
 // Unified client registry with internal thread safety (following GameRegistry model)
@@ -144,6 +153,33 @@ impl ClientRegistry {
         Ok(clients_lock.len())
     }
 
+    /// Get the client holding an API key
+    pub fn get_by_api_key(&self, api_key: &str) -> Result<Option<ClientInfo>, String> {
+        let clients_lock = self.clients.lock()
+            .map_err(|_| "Failed to lock client registry")?;
+
+        let hash = hash_api_key(api_key);
+        Ok(clients_lock.values().find(|client| client.api_key_hash.as_ref() == Some(&hash)).cloned())
+    }
+
+    /// Issue a new API key to a client, invalidating the previous one and the tokens issued with it
+    /// Returns the key with the updated client, or None if the client is not registered
+    pub fn issue_api_key(&self, client_id: &str) -> Result<Option<(String, ClientInfo)>, String> {
+        let mut clients_lock = self.clients.lock()
+            .map_err(|_| "Failed to lock client registry")?;
+
+        Ok(clients_lock.get_mut(client_id).map(|client| (client.new_api_key(), client.clone())))
+    }
+
+    /// Revoke the API key of a client and the tokens issued with it
+    /// Returns false if the client is not registered
+    pub fn revoke_api_key(&self, client_id: &str) -> Result<bool, String> {
+        let mut clients_lock = self.clients.lock()
+            .map_err(|_| "Failed to lock client registry")?;
+
+        Ok(clients_lock.get_mut(client_id).map(|client| client.api_key_hash = None).is_some())
+    }
+
     /// Check if a client exists by client ID
     pub fn contains_client(&self, client_id: &str) -> Result<bool, String> {
         let clients_lock = self.clients.lock()
@@ -237,6 +273,7 @@ mod tests {
             client_type: new_client.client_type,
             registered_at: new_client.registered_at,
             email: String::new(),  // Default empty email for test
+            api_key_hash: None,
         };
         let result = registry.insert(updated_client).unwrap();
         assert!(result.is_some(), "Should return previous client when client ID exists");
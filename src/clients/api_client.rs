@@ -5,8 +5,9 @@ use crate::board::Board;
 use crate::score::ScoreCard;
 use crate::pouch::Pouch;
 use crate::defs::Number;
+use crate::extraction::{ExtractRequest, UndoRequest};
 use std::error::Error;
-use super::common::{get_json, get_json_with_client_id, post_json_with_client_id};
+use super::common::{get_json, get_json_with_client_id, post_json_with_token};
 
 // ============================================================================
 // Game State API Calls
@@ -32,10 +33,11 @@ pub async fn get_scoremap(server_base_url: &str, game_id: &str) -> Result<ScoreC
     get_json(&url).await
 }
 
-/// Extract a number (requires board client ID)
-pub async fn extract_number(server_base_url: &str, game_id: &str, client_id: &str) -> Result<u8, Box<dyn Error>> {
+/// Extract the next number, or submit the number drawn from a physical basket in manual draw mode
+pub async fn extract_number(server_base_url: &str, game_id: &str, token: &str, number: Option<Number>) -> Result<u8, Box<dyn Error>> {
     let url = format!("{server_base_url}/{game_id}/extract");
-    let response = post_json_with_client_id::<(), serde_json::Value>(&url, &(), client_id).await?;
+    let request = ExtractRequest { number };
+    let response = post_json_with_token::<ExtractRequest, serde_json::Value>(&url, &request, token).await?;
 
     if let Some(extracted_number) = response["extracted_number"].as_u64() {
         Ok(extracted_number as u8)
@@ -44,6 +46,18 @@ pub async fn extract_number(server_base_url: &str, game_id: &str, client_id: &st
     }
 }
 
+/// Undo the last extraction, returning the undone number
+pub async fn undo_extraction(server_base_url: &str, game_id: &str, token: &str) -> Result<u8, Box<dyn Error>> {
+    let url = format!("{server_base_url}/{game_id}/undo");
+    let response = post_json_with_token::<UndoRequest, serde_json::Value>(&url, &UndoRequest::default(), token).await?;
+
+    if let Some(undone_number) = response["undone_numbers"][0].as_u64() {
+        Ok(undone_number as u8)
+    } else {
+        Err("Invalid response format from undo endpoint".into())
+    }
+}
+
 /// Get client name by ID
 pub async fn get_client_name_by_id(server_base_url: &str, client_id: &str) -> Result<String, Box<dyn Error>> {
     // Handle special board client ID
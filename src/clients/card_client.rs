@@ -13,7 +13,7 @@
 // Features:
 // - Client registration with server authentication
 // - Card generation and assignment management
-// - Real-time game monitoring with automatic updates
+// - Real-time game monitoring with live updates over WebSocket, polling when the stream is unavailable
 // - Achievement tracking and BINGO detection
 // - Interactive card display with number highlighting
 // - Game discovery and selection capabilities
@@ -25,16 +25,17 @@
 // - --gameid: Specify game ID to connect to
 // - --listgames: List active games and exit
 
-use tombola::defs::{NUMBERSPERCARD};
 use tombola::score::ScoreCard;
+use tombola::defs::BINGO75_LETTERS;
+use tombola::ruleset::CardLayout;
 use tombola::config::ClientConfig;
 
 use std::time::Duration;
-use tokio::time::sleep;
 use clap::Parser;
 
 // Include shared modules
 use tombola::clients::{common, game_utils, api_client, card_management};
+use tombola::clients::live_updates::LiveUpdates;
 mod registration;
 
 use common::{GenerateCardsResponse, ListAssignedCardsResponse, CardInfo, AssignedCardInfo};
@@ -69,6 +70,8 @@ struct Args {
 #[derive(Debug)]
 pub struct TombolaClient {
     client_id: Option<String>,
+    token: Option<String>,  // Bearer token issued at registration
+    api_key: Option<String>,  // API key of the client name, sent to register again under it
     client_name: String,
     server_url: String,
     http_client: reqwest::Client,
@@ -87,6 +90,8 @@ impl TombolaClient {
 
         Self {
             client_id: None,
+            token: None,
+            api_key: None,
             client_name: name.to_string(),
             server_url: server_url.to_string(),
             http_client,
@@ -109,14 +114,13 @@ impl TombolaClient {
         let register_response = registration::join_client(
             &self.server_url,
             game_id,
-            &self.client_name,
-            "player",  // Updated to use proper client type
-            self.nocard,
-            None,  // No email provided by default
+            &common::RegisterRequest::new(&self.client_name, "player", self.nocard, None),  // No email provided by default
+            self.api_key.as_deref(),
             &self.http_client
         ).await?;
 
         self.client_id = Some(register_response.client_id.clone());
+        self.token = Some(register_response.token.clone());
         self.registered = true;
 
         println!("✅ Registration successful!");
@@ -126,6 +130,11 @@ impl TombolaClient {
         Ok(())
     }
 
+    /// Set the API key sent to register again under the client name
+    pub fn set_api_key(&mut self, api_key: Option<String>) {
+        self.api_key = api_key;
+    }
+
     /// Set the number of cards to generate during registration
     pub fn set_nocard(&mut self, count: u32) {
         self.nocard = Some(count);
@@ -192,27 +201,27 @@ impl TombolaClient {
     pub async fn generate_cards(&self, count: u32) -> Result<GenerateCardsResponse, Box<dyn std::error::Error>> {
         self.ensure_registered()?;
         let game_id = self.ensure_game_id()?;
-        let client_id = self.client_id.as_ref().unwrap();
+        let token = self.token.as_ref().unwrap();
 
-        card_management::generate_cards(&self.server_url, game_id, client_id, count, &self.http_client).await
+        card_management::generate_cards(&self.server_url, game_id, token, count, &self.http_client).await
     }
 
     /// List assigned cards for the client
     pub async fn list_assigned_cards(&self) -> Result<ListAssignedCardsResponse, Box<dyn std::error::Error>> {
         self.ensure_registered()?;
         let game_id = self.ensure_game_id()?;
-        let client_id = self.client_id.as_ref().unwrap();
+        let token = self.token.as_ref().unwrap();
 
-        card_management::list_assigned_cards(&self.server_url, game_id, client_id, &self.http_client).await
+        card_management::list_assigned_cards(&self.server_url, game_id, token, &self.http_client).await
     }
 
     /// Get a specific assigned card by ID
     pub async fn get_assigned_card(&self, card_id: &str) -> Result<CardInfo, Box<dyn std::error::Error>> {
         self.ensure_registered()?;
         let game_id = self.ensure_game_id()?;
-        let client_id = self.client_id.as_ref().unwrap();
+        let token = self.token.as_ref().unwrap();
 
-        card_management::get_assigned_card(&self.server_url, game_id, client_id, card_id, &self.http_client).await
+        card_management::get_assigned_card(&self.server_url, game_id, token, card_id, &self.http_client).await
     }
 
     /// Get server status
@@ -234,13 +243,20 @@ impl TombolaClient {
         game_utils::list_games(&self.server_url).await
     }
 
-    /// Start monitoring the game (polls server for updates)
+    /// Follow the live updates of the game
+    #[must_use] pub fn live_updates(&self) -> LiveUpdates {
+        LiveUpdates::start(&self.server_url, self.game_id.as_deref().unwrap_or_default())
+    }
+
+    /// Start monitoring the game (refreshes on live updates, polls the server while they are unavailable)
     pub async fn start_monitoring(&self, interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
         self.ensure_registered()?;
 
-        println!("🔄 Starting game monitoring (polling every {interval_seconds} seconds)...");
+        println!("🔄 Starting game monitoring (live updates, polling every {interval_seconds} seconds as fallback)...");
         println!("   Client ID: {}", self.client_id.as_ref().unwrap());
 
+        let live = self.live_updates();
+
         loop {
             match self.get_status().await {
                 Ok(status) => {
@@ -261,7 +277,7 @@ impl TombolaClient {
                 }
             }
 
-            sleep(Duration::from_secs(interval_seconds)).await;
+            live.wait(Duration::from_secs(interval_seconds)).await;
         }
     }
 
@@ -290,8 +306,11 @@ async fn main() {
     // Determine client name from args or config
     let client_name = args.name.unwrap_or_else(|| config.client_name.clone());
 
-    // Create client
+    // Create client, with the API key of the configuration when it plays under the configured name
     let mut client = TombolaClient::new(&client_name, &server_url);
+    if client_name == config.client_name {
+        client.set_api_key(config.api_key.clone());
+    }
 
     // Handle list games request
     if args.listgames {
@@ -413,7 +432,7 @@ async fn main() {
                 }
             }
 
-            println!("\n🔥 Starting live monitoring (updating every 2 seconds)...");
+            println!("\n🔥 Starting live monitoring (updating as numbers are extracted)...");
             println!("📇 Your Cards ({} total)", assigned_cards.len());
             println!("💡 Numbers highlighted in \x1b[1;33myellow\x1b[0m have been extracted from the pouch");
             println!("🛑 Press Ctrl+C to stop monitoring");
@@ -436,6 +455,9 @@ async fn main() {
                 }
             }
 
+            // Refresh on the live updates of the game, polling every 2 seconds while they are unavailable
+            let live = client.live_updates();
+
             // Main monitoring loop
             loop {
                 // Clear screen for better readability
@@ -467,7 +489,7 @@ async fn main() {
                 let scorecard = match client.get_scorecard().await {
                     Ok(scorecard) => {
                         if scorecard.published_score > 0 {
-                            println!("📊 Current scorecard: {} (achievements shown only if card ID is published in score map)", scorecard.published_score);
+                            println!("📊 Current scorecard: {} (achievements shown only if card ID is published in score map)", scorecard.prize_name(scorecard.published_score));
                         } else {
                             println!("📊 No scorecard yet");
                         }
@@ -484,21 +506,22 @@ async fn main() {
                 let mut bingo_cards = Vec::new();
 
                 for (index, card_info) in card_details.iter().enumerate() {
-                    let (is_bingo, _achievements) = print_card_as_table_with_highlights(index + 1, &card_info.card_id, &card_info.card_data, &extracted_numbers, &scorecard);
+                    let (is_bingo, achievements) = print_card_as_table_with_highlights(index + 1, &card_info.card_id, &card_info.card_data, &extracted_numbers, &scorecard);
                     if is_bingo {
-                        bingo_cards.push(card_info.card_id.clone());
+                        let prize = achievements.last().cloned().unwrap_or_default();
+                        bingo_cards.push((card_info.card_id.clone(), prize));
                     }
                 }
 
-                // Show BINGO summary if any cards have BINGO
+                // Show BINGO summary if any cards won a full-card prize (TOMBOLA, TOMBOLINO, ...)
                 if !bingo_cards.is_empty() {
-                    println!("\n🏆 \x1b[1;32mCONGRATULATIONS! You have {} BINGO card(s)!\x1b[0m 🏆", bingo_cards.len());
-                    for card_id in bingo_cards {
-                        println!("   🎉 BINGO with Card ID: {card_id}");
+                    println!("\n🏆 \x1b[1;32mCONGRATULATIONS! You have {} full card prize(s)!\x1b[0m 🏆", bingo_cards.len());
+                    for (card_id, prize) in bingo_cards {
+                        println!("   🎉 {prize} with Card ID: {card_id}");
                     }
                 }
 
-                if scorecard.published_score == NUMBERSPERCARD {
+                if scorecard.is_game_over() {
                     dump_client_achievements(&client, &assigned_cards).await;
                     return; // Exit if BINGO achieved
                 }
@@ -510,8 +533,8 @@ async fn main() {
                     break;
                 }
 
-                // Wait for 2 seconds before next update
-                std::thread::sleep(Duration::from_secs(2));
+                // Wait for the next update
+                live.wait(Duration::from_secs(2)).await;
             }
         }
         Err(e) => {
@@ -529,7 +552,7 @@ async fn dump_client_achievements(client: &TombolaClient, assigned_cards: &[Assi
     // Get final scorecard
     match client.get_scorecard().await {
         Ok(scorecard) => {
-            println!("📊 Final Score: {}", scorecard.published_score);
+            println!("📊 Final Score: {}", scorecard.prize_name(scorecard.published_score));
 
             println!("\n📇 Card Achievements:");
             let mut cards_with_achievements = 0;
@@ -544,13 +567,10 @@ async fn dump_client_achievements(client: &TombolaClient, assigned_cards: &[Assi
                         for (score, score_achievements) in scorecard.get_scoremap() {
                             for achievement in score_achievements {
                                 if achievement.card_id == card.card_id {
-                                    let achievement_text = match score {
-                                        2 => "2 in line".to_string(),
-                                        3 => "3 in line".to_string(),
-                                        4 => "4 in line".to_string(),
-                                        5 => "5 in line".to_string(),
-                                        x if *x == NUMBERSPERCARD => "🎉 BINGO 🎉".to_string(),
-                                        _ => format!("{score} in line"),
+                                    let achievement_text = if scorecard.is_full_card_score(*score) {
+                                        format!("🎉 {} 🎉", scorecard.prize_name(*score))
+                                    } else {
+                                        scorecard.prize_name(*score)
                                     };
                                     card_achievements.push((*score, achievement_text));
                                 }
@@ -591,11 +611,14 @@ async fn dump_client_achievements(client: &TombolaClient, assigned_cards: &[Assi
 }
 
 fn print_card_as_table_with_highlights(card_number: usize, card_id: &str, card_data: &[Vec<Option<u8>>], extracted_numbers: &[u8], scorecard: &ScoreCard) -> (bool, Vec<String>) {
-    println!("\n┌────────────────────────────────────────────────────────────────────────────────┐");
+    // The table follows the card geometry: one 8 characters cell per card column
+    let columns = card_data.first().map_or(0, Vec::len).max(1);
+    let cell_line = vec!["────────"; columns];
+    println!("\n┌{}┐", "─".repeat(columns * 9 - 1));
 
     // Calculate proper spacing for the title to align the right border
     let title_text = format!("Card {card_number} - ID: {card_id}");
-    let box_width = 78; // Total width of the box content area (counting the actual characters)
+    let box_width = columns * 9 - 3; // Total width of the box content area (counting the actual characters)
     let padding = if title_text.len() < box_width {
         box_width - title_text.len()
     } else {
@@ -603,7 +626,18 @@ fn print_card_as_table_with_highlights(card_number: usize, card_id: &str, card_d
     };
 
     println!("│ {}{} │", title_text, " ".repeat(padding));
-    println!("├────────┬────────┬────────┬────────┬────────┬────────┬────────┬────────┬────────┤");
+    println!("├{}┤", cell_line.join("┬"));
+
+    // 75-ball cards show the B-I-N-G-O letters above the columns and a free centre square
+    let is_bingo75 = scorecard.ruleset.ruleset().card_layout() == CardLayout::Bingo75;
+    if is_bingo75 {
+        print!("│");
+        for letter in BINGO75_LETTERS {
+            print!("   {letter}    │");
+        }
+        println!();
+        println!("├{}┤", cell_line.join("┼"));
+    }
 
     // Get the numbers that contributed to the highest published score for this card
     let highest_score_numbers = get_highest_score_numbers_for_card(scorecard, card_id);
@@ -630,13 +664,14 @@ fn print_card_as_table_with_highlights(card_number: usize, card_id: &str, card_d
                         print!("   {number:2}   |");
                     }
                 }
+                None if is_bingo75 => print!("  FREE  │"),
                 None => print!("        │"),
             }
         }
         println!();
     }
 
-    println!("└────────┴────────┴────────┴────────┴────────┴────────┴────────┴────────┴────────┘");
+    println!("└{}┘", cell_line.join("┴"));
 
     // Get achievements from server scorecard - only show if relevant to current published score
     let mut achievements_for_this_card = Vec::new();
@@ -651,34 +686,25 @@ fn print_card_as_table_with_highlights(card_number: usize, card_id: &str, card_d
                 highest_score = *score;
                 achievements_for_this_card.clear(); // Clear lower achievements
 
-                match score {
-                    2 => achievements_for_this_card.push("2 in line".to_string()),
-                    3 => achievements_for_this_card.push("3 in line".to_string()),
-                    4 => achievements_for_this_card.push("4 in line".to_string()),
-                    5 => achievements_for_this_card.push("5 in line".to_string()),
-                    x if *x == NUMBERSPERCARD => {
-                        achievements_for_this_card.push("BINGO".to_string());
-                        is_bingo = true;
-                    },
-                    _ => {} // Handle any other scores
-                }
+                achievements_for_this_card.push(scorecard.prize_name(*score));
+                is_bingo = scorecard.is_full_card_score(*score);
             }
         }
     }
 
     // Only show achievements if they are relevant to the current published score
     // Don't show obsolete achievements (e.g., don't show "2 in line" if published score is 4)
-    if highest_score > 0 && published_score > 0 && highest_score < published_score {
+    // Patterns of non-progressive rulesets (75-ball bingo) never become obsolete
+    // Full-card prizes (TOMBOLA, TOMBOLINO, ...) stay relevant as well
+    if scorecard.ruleset.ruleset().is_progressive() && !is_bingo && highest_score > 0 && published_score > 0 && highest_score < published_score {
         achievements_for_this_card.clear(); // Clear obsolete achievements
     }
 
     // Display progress information - BINGO announcement removed (shown in main loop summary)
 
     // Display current achievement (only if relevant and not obsolete)
-    if !achievements_for_this_card.is_empty() {
-        if let Some(highest_achievement) = achievements_for_this_card.last() {
-            println!("🏆 {highest_achievement}");
-        }
+    if let Some(highest_achievement) = achievements_for_this_card.last() {
+        println!("🏆 {highest_achievement}");
     }
 
     (is_bingo, achievements_for_this_card)
@@ -693,6 +719,17 @@ fn get_highest_score_numbers_for_card(scorecard: &ScoreCard, card_id: &str) -> V
         return Vec::new();
     }
 
+    // Patterns of non-progressive rulesets stay relevant: use the best one won by this card
+    if !scorecard.ruleset.ruleset().is_progressive() {
+        return scorecard.get_scoremap().iter()
+            .filter_map(|(score, achievements)| {
+                achievements.iter().find(|achievement| achievement.card_id == card_id).map(|achievement| (*score, achievement))
+            })
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, achievement)| achievement.numbers.clone())
+            .unwrap_or_default();
+    }
+
     // Look for this card in the score map for the published score
     if let Some(achievements) = scorecard.get_scoremap().get(&published_score) {
         for achievement in achievements {
@@ -2,13 +2,15 @@
 // Card generation and management utilities shared between client applications
 
 use std::error::Error;
+use std::path::Path;
 use super::common::{GenerateCardsRequest, GenerateCardsResponse, ListAssignedCardsResponse, CardInfo, ErrorResponse};
+use crate::card_import::ImportFormat;
 
 /// Generate cards for a client
 pub async fn generate_cards(
     server_url: &str,
     game_id: &str,
-    client_id: &str,
+    token: &str,
     count: u32,
     http_client: &reqwest::Client
 ) -> Result<GenerateCardsResponse, Box<dyn Error>> {
@@ -17,7 +19,7 @@ pub async fn generate_cards(
 
     let response = http_client
         .post(&url)
-        .header("X-Client-ID", client_id)
+        .bearer_auth(token)
         .json(&request)
         .send()
         .await?;
@@ -35,13 +37,13 @@ pub async fn generate_cards(
 pub async fn list_assigned_cards(
     server_url: &str,
     game_id: &str,
-    client_id: &str,
+    token: &str,
     http_client: &reqwest::Client
 ) -> Result<ListAssignedCardsResponse, Box<dyn Error>> {
     let url = format!("{server_url}/{game_id}/listassignedcards");
     let response = http_client
         .get(&url)
-        .header("X-Client-ID", client_id)
+        .bearer_auth(token)
         .send()
         .await?;
 
@@ -58,14 +60,14 @@ pub async fn list_assigned_cards(
 pub async fn get_assigned_card(
     server_url: &str,
     game_id: &str,
-    client_id: &str,
+    token: &str,
     card_id: &str,
     http_client: &reqwest::Client
 ) -> Result<CardInfo, Box<dyn Error>> {
     let url = format!("{server_url}/{game_id}/getassignedcard/{card_id}");
     let response = http_client
         .get(&url)
-        .header("X-Client-ID", client_id)
+        .bearer_auth(token)
         .send()
         .await?;
 
@@ -77,3 +79,33 @@ pub async fn get_assigned_card(
         Err(format!("Failed to get assigned card: {}", error_response.error).into())
     }
 }
+
+/// Import the pre-printed cards of a CSV or JSON file into the inventory of a game, returning their serials
+pub async fn import_cards(
+    server_url: &str,
+    game_id: &str,
+    token: &str,
+    path: &Path,
+    http_client: &reqwest::Client
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let format = ImportFormat::from_path(path);
+    let body = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let url = format!("{server_url}/{game_id}/importcards");
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .header(reqwest::header::CONTENT_TYPE, format.content_type())
+        .body(body)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        let import_response: serde_json::Value = response.json().await?;
+        Ok(serde_json::from_value(import_response["serials"].clone())?)
+    } else {
+        let error_response: ErrorResponse = response.json().await?;
+        Err(format!("Failed to import cards: {}", error_response.error).into())
+    }
+}
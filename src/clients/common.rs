@@ -16,6 +16,20 @@ pub struct RegisterRequest {
     pub client_type: String,
     pub nocard: Option<u32>,  // Number of cards to generate during registration
     pub email: Option<String>,  // Optional email for registration
+    pub allow_duplicate_name: bool,  // Register a new client even if another one has the same name
+}
+
+impl RegisterRequest {
+    /// Registration of a client name, refused by the server if another client holds it
+    #[must_use] pub fn new(name: &str, client_type: &str, nocard: Option<u32>, email: Option<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            client_type: client_type.to_string(),
+            nocard,
+            email,
+            allow_duplicate_name: false,
+        }
+    }
 }
 
 /// Client registration response
@@ -23,6 +37,26 @@ pub struct RegisterRequest {
 pub struct RegisterResponse {
     pub client_id: String,
     pub message: String,
+    pub token: String,  // Bearer token authenticating the requests of the client
+    #[serde(default)]
+    pub api_key: Option<String>,  // Secret API key, only returned when it is issued
+}
+
+/// Credentials of a registered client: the public ID and the bearer token of its requests
+#[derive(Debug, Clone)]
+pub struct ClientAuth {
+    pub client_id: String,
+    pub token: String,
+}
+
+impl From<RegisterResponse> for ClientAuth {
+    fn from(response: RegisterResponse) -> Self {
+        Self {
+            client_id: response.client_id,
+            // The API key does not expire, unlike the token
+            token: response.api_key.unwrap_or(response.token),
+        }
+    }
 }
 
 /// Generic API error response structure
@@ -103,8 +137,8 @@ where
     }
 }
 
-/// HTTP POST request with JSON body and client ID header
-pub async fn post_json_with_client_id<T, U>(url: &str, body: &T, client_id: &str) -> Result<U, Box<dyn Error>>
+/// HTTP POST request with JSON body and bearer token
+pub async fn post_json_with_token<T, U>(url: &str, body: &T, token: &str) -> Result<U, Box<dyn Error>>
 where
     T: Serialize,
     U: for<'de> Deserialize<'de>,
@@ -115,7 +149,7 @@ where
     let response = client
         .post(url)
         .json(body)
-        .header("X-Client-ID", client_id)
+        .bearer_auth(token)
         .send()
         .await?;
 
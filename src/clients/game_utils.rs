@@ -20,10 +20,8 @@ pub async fn get_game_id(server_base_url: &str) -> Result<String, Box<dyn Error>
                 game["game_id"].as_str(),
                 game["status"].as_str(),
                 game["start_date"].as_str()
-            ) {
-                if status != "Closed" {
-                    return Ok(format!("{game_id}, started at: {start_date}"));
-                }
+            ) && status != "Closed" {
+                return Ok(format!("{game_id}, started at: {start_date}"));
             }
         }
         Err("No available games found".into())
@@ -13,6 +13,7 @@
 // - card_management: Card-specific operations
 // - registration: Client registration and authentication
 // - terminal: Terminal UI utilities for board display
+// - live_updates: Live game updates over WebSocket with polling fallback
 //
 // This architecture allows each client to use only the modules it needs,
 // creating a flexible and maintainable codebase.
@@ -23,3 +24,4 @@ pub mod game_utils;
 pub mod api_client;
 pub mod card_management;
 pub mod registration;
+pub mod live_updates;
@@ -0,0 +1,108 @@
+// src/clients/live_updates.rs
+// Live game updates pushed by the server on the /{game_id}/ws WebSocket.
+// The connection is kept open in a background task and retried while it is down,
+// so that the clients refresh as soon as something happens and fall back to polling otherwise.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+use tokio_tungstenite::tungstenite::Message;
+
+// Delay before trying again to open the stream
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+// Refresh even without updates while the stream is up, in case the server could not report a change
+const STREAM_REFRESH: Duration = Duration::from_secs(30);
+
+/// Background subscription to the live updates of a game
+pub struct LiveUpdates {
+    connected: Arc<AtomicBool>,
+    pending: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl LiveUpdates {
+    /// Start following a game
+    #[must_use] pub fn start(server_base_url: &str, game_id: &str) -> Self {
+        let connected = Arc::new(AtomicBool::new(false));
+        let pending = Arc::new(AtomicBool::new(false));
+        let notify = Arc::new(Notify::new());
+        let task = tokio::spawn(follow(
+            websocket_url(server_base_url, game_id),
+            connected.clone(),
+            pending.clone(),
+            notify.clone(),
+        ));
+
+        Self { connected, pending, notify, task }
+    }
+
+    /// Whether the stream is currently open
+    #[must_use] pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Whether updates arrived since the last call
+    #[must_use] pub fn take_update(&self) -> bool {
+        self.pending.swap(false, Ordering::SeqCst)
+    }
+
+    /// Wait for the next update, or for the polling interval while the stream is down
+    pub async fn wait(&self, poll_interval: Duration) {
+        if self.is_connected() {
+            let _ = timeout(STREAM_REFRESH, self.notify.notified()).await;
+        } else {
+            sleep(poll_interval).await;
+        }
+        self.pending.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Drop for LiveUpdates {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// WebSocket URL of the live updates of a game on the given HTTP server
+#[must_use] pub fn websocket_url(server_base_url: &str, game_id: &str) -> String {
+    let base = if let Some(rest) = server_base_url.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = server_base_url.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        server_base_url.to_string()
+    };
+    format!("{base}/{game_id}/ws")
+}
+
+// Keep the stream open and flag every update received
+async fn follow(url: String, connected: Arc<AtomicBool>, pending: Arc<AtomicBool>, notify: Arc<Notify>) {
+    let signal = || {
+        pending.store(true, Ordering::SeqCst);
+        notify.notify_one();
+    };
+
+    loop {
+        if let Ok((mut stream, _)) = tokio_tungstenite::connect_async(url.as_str()).await {
+            connected.store(true, Ordering::SeqCst);
+            // Updates may have been missed while the stream was down
+            signal();
+
+            while let Some(message) = stream.next().await {
+                match message {
+                    Ok(Message::Text(_)) => signal(),
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => {}
+                }
+            }
+            connected.store(false, Ordering::SeqCst);
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -4,32 +4,42 @@
 use std::error::Error;
 use super::common::{RegisterRequest, RegisterResponse};
 
+/// Send the registration request, with the API key of the client name when there is one
+async fn send_registration(
+    url: &str,
+    request: &RegisterRequest,
+    api_key: Option<&str>,
+    http_client: &reqwest::Client
+) -> Result<reqwest::Response, Box<dyn Error>> {
+    let mut request_builder = http_client.post(url).json(request);
+    if let Some(api_key) = api_key {
+        request_builder = request_builder.bearer_auth(api_key);
+    }
+    Ok(request_builder.send().await?)
+}
+
+/// Tell the user to keep the API key issued by the registration, it is only returned once
+fn show_issued_api_key(client_name: &str, response: &RegisterResponse) {
+    if let Some(api_key) = &response.api_key {
+        println!("🔑 API key of '{client_name}': {api_key}");
+        println!("   Set it as api_key in conf/client.conf to register again as this client");
+    }
+}
+
 /// Register a client globally with the server
 #[allow(dead_code)]
 pub async fn register_global_client(
     server_url: &str,
-    client_name: &str,
-    client_type: &str,
-    nocard: Option<u32>,
-    email: Option<String>,
+    request: &RegisterRequest,
+    api_key: Option<&str>,
     http_client: &reqwest::Client
 ) -> Result<RegisterResponse, Box<dyn Error>> {
-    let request = RegisterRequest {
-        name: client_name.to_string(),
-        client_type: client_type.to_string(),
-        nocard,
-        email,
-    };
-
     let url = format!("{server_url}/register");
-    let response = http_client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await?;
+    let response = send_registration(&url, request, api_key, http_client).await?;
 
     if response.status().is_success() {
         let register_response: RegisterResponse = response.json().await?;
+        show_issued_api_key(&request.name, &register_response);
         Ok(register_response)
     } else {
         let error_text = response.text().await?;
@@ -41,28 +51,16 @@ pub async fn register_global_client(
 pub async fn join_client(
     server_url: &str,
     game_id: &str,
-    client_name: &str,
-    client_type: &str,
-    nocard: Option<u32>,
-    email: Option<String>,
+    request: &RegisterRequest,
+    api_key: Option<&str>,
     http_client: &reqwest::Client
 ) -> Result<RegisterResponse, Box<dyn Error>> {
-    let request = RegisterRequest {
-        name: client_name.to_string(),
-        client_type: client_type.to_string(),
-        nocard,
-        email,
-    };
-
     let url = format!("{server_url}/{game_id}/join");
-    let response = http_client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await?;
+    let response = send_registration(&url, request, api_key, http_client).await?;
 
     if response.status().is_success() {
         let register_response: RegisterResponse = response.json().await?;
+        show_issued_api_key(&request.name, &register_response);
         Ok(register_response)
     } else {
         let error_text = response.text().await?;
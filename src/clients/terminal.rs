@@ -6,8 +6,9 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode},
 };
 
-use crate::defs::{BOARDCONFIG, Number, Colors};
+use crate::defs::{BoardStruct, Number, Colors, BINGO75_LETTERS};
 use crate::board::Board;
+use std::io::{self, Write};
 
 pub struct DeltaPos {
     pub delta_x: u8,
@@ -15,19 +16,24 @@ pub struct DeltaPos {
 }
 
 // Function to calculate the horizontal and vertical shifts
-#[must_use] pub fn downrightshift(prev_num: Number, curr_num: Number) -> DeltaPos {
-    let prev_num = prev_num as i8;
-    let curr_num = curr_num as i8;
-    let numbers_per_row = (BOARDCONFIG.cols_per_card * BOARDCONFIG.cards_per_row) as i8;
+// A prev_num of 0 means the cursor is still at the top left corner of the board
+#[must_use] pub fn downrightshift(prev_num: Number, curr_num: Number, config: &BoardStruct) -> DeltaPos {
+    // Work on 1-based positions on the board so that any first_number is supported
+    let position = |num: Number| if num == 0 { 0 } else { i32::from(num) - i32::from(config.first_number) + 1 };
+    let prev_num = position(prev_num);
+    let curr_num = position(curr_num);
+    let numbers_per_row = i32::from(config.numbers_per_row());
+    let rows_per_card = i32::from(config.rows_per_card);
+    let cols_per_card = i32::from(config.cols_per_card);
     let xpos = (curr_num - 1) % numbers_per_row + 1;
     let ypos = (curr_num - 1) / numbers_per_row + 1;
     let prev_ypos = (prev_num - 1) / numbers_per_row + 1;
 
     // if prev and curr are in different rows_per_card and different cards add a vertical space between the 2
-    let yshift = ((ypos - 1) / BOARDCONFIG.rows_per_card as i8
-        - (prev_ypos - 1) / BOARDCONFIG.rows_per_card as i8)
-        * BOARDCONFIG.vcards_space as i8;
-    let delta_y = (ypos - prev_ypos) * (1 + BOARDCONFIG.vnumbers_space as i8) + yshift;
+    let yshift = ((ypos - 1) / rows_per_card
+        - (prev_ypos - 1) / rows_per_card)
+        * i32::from(config.vcards_space);
+    let delta_y = (ypos - prev_ypos) * (1 + i32::from(config.vnumbers_space)) + yshift;
 
     // if prev and curr are in different rows, shift down and reset xpos
     let prev_xpos = if delta_y == 0 {
@@ -37,31 +43,32 @@ pub struct DeltaPos {
     };
 
     // if prev and curr are in the same row but different cards add a horizontal space between the 2
-    let xshift = ((xpos - 1) / BOARDCONFIG.cols_per_card as i8
-        - (prev_xpos - 1) / BOARDCONFIG.cols_per_card as i8)
-        * BOARDCONFIG.hcards_space as i8;
+    let xshift = ((xpos - 1) / cols_per_card
+        - (prev_xpos - 1) / cols_per_card)
+        * i32::from(config.hcards_space);
 
-    let delta_x = (xpos - prev_xpos - 1) * (2 + BOARDCONFIG.hnumbers_space as i8)
-        + BOARDCONFIG.hnumbers_space as i8
+    let delta_x = (xpos - prev_xpos - 1) * (2 + i32::from(config.hnumbers_space))
+        + i32::from(config.hnumbers_space)
         + xshift;
 
     DeltaPos {
-        delta_x: delta_x as u8,
-        delta_y: delta_y as u8,
+        delta_x: delta_x.clamp(0, i32::from(u8::MAX)) as u8,
+        delta_y: delta_y.clamp(0, i32::from(u8::MAX)) as u8,
     }
 }
 
-pub fn print_board(board: &Board) {
+pub fn print_board(board: &Board, config: &BoardStruct) {
     let sorted_entries = board.get_sorted_entries();
     let mut prev_num = 0;
     // Get the last extracted number from the board
     let extracted = board.get_numbers().last().copied().unwrap_or(0);
 
     for (curr_num, is_marked) in &sorted_entries {
-        for _ in 0..downrightshift(prev_num, *curr_num).delta_y {
+        let shift = downrightshift(prev_num, *curr_num, config);
+        for _ in 0..shift.delta_y {
             println!();
         }
-        let spaces = " ".repeat(downrightshift(prev_num, *curr_num).delta_x as usize);
+        let spaces = " ".repeat(shift.delta_x as usize);
 
         print!("{spaces}");
         if *curr_num == extracted {
@@ -75,6 +82,35 @@ pub fn print_board(board: &Board) {
     }
 }
 
+// Print the 75-ball board: one row of 15 numbers for each letter of B-I-N-G-O
+pub fn print_bingo75_board(board: &Board, config: &BoardStruct) {
+    let numbers_per_row = usize::from(config.numbers_per_row());
+    let spaces = " ".repeat(usize::from(config.hnumbers_space));
+    // Get the last extracted number from the board
+    let extracted = board.get_numbers().last().copied().unwrap_or(0);
+
+    for (row, letter) in BINGO75_LETTERS.iter().enumerate() {
+        if row > 0 {
+            for _ in 0..config.vnumbers_space {
+                println!();
+            }
+        }
+        print!("{}{letter}{}", Colors::blue(), Colors::reset());
+        for col in 0..numbers_per_row {
+            let number = (usize::from(config.first_number) + row * numbers_per_row + col) as Number;
+            print!("{spaces}");
+            if number == extracted {
+                print!("{}{number:2}{}", Colors::green(), Colors::reset()); // Bold green for the last number
+            } else if board.get_numbers().contains(&number) {
+                print!("{number:2}");
+            } else {
+                print!("  ");
+            }
+        }
+        println!();
+    }
+}
+
 // Function to output the last n previous numbers from the board
 #[must_use] pub fn print_last_numbers(board: &Board, n: usize) -> Vec<Number> {
     board.get_last_numbers(n)
@@ -84,10 +120,17 @@ pub enum KeyAction {
     Extract,  // Enter key pressed
     Exit,     // ESC key pressed
     Refresh,  // F5 key pressed for screen update
+    Undo,     // U key pressed to undo the last extraction
+    Update,   // The game was updated on the server
 }
 
 #[must_use] pub fn wait_for_user_action() -> KeyAction {
-    println!("\nPress ENTER to extract a number, F5 to refresh screen, or ESC to exit");
+    wait_for_user_action_or_update(|| false)
+}
+
+// Wait for a key press, or until `updated` reports a change of the game on the server
+#[must_use] pub fn wait_for_user_action_or_update(updated: impl Fn() -> bool) -> KeyAction {
+    println!("\nPress ENTER to extract a number, U to undo the last extraction, F5 to refresh screen, or ESC to exit");
 
     // Enable raw mode to capture individual key presses
     enable_raw_mode().unwrap();
@@ -99,6 +142,12 @@ pub enum KeyAction {
 
     // Wait for a key press
     let result = loop {
+        if !event::poll(std::time::Duration::from_millis(200)).unwrap_or(false) {
+            if updated() {
+                break KeyAction::Update;
+            }
+            continue;
+        }
         if let Ok(Event::Key(key_event)) = event::read() {
             // Only process key press events, not key release events
             if key_event.kind == event::KeyEventKind::Press {
@@ -112,6 +161,9 @@ pub enum KeyAction {
                     KeyCode::F(5) => {
                         break KeyAction::Refresh; // Refresh the screen
                     }
+                    KeyCode::Char('u' | 'U') => {
+                        break KeyAction::Undo; // Undo the last extraction
+                    }
                     _ => {
                         // For any other key, continue waiting
                         continue;
@@ -126,3 +178,27 @@ pub enum KeyAction {
 
     result
 }
+
+// Prompt for the number drawn from a physical basket (manual draw mode)
+// Returns None when the entry is cancelled with an empty line
+#[must_use] pub fn read_drawn_number(config: &BoardStruct) -> Option<Number> {
+    let first = config.first_number;
+    let last = config.last_number();
+    loop {
+        print!("Enter the drawn number ({first}-{last}), or press ENTER to cancel: ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_err() {
+            return None;
+        }
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        match input.parse::<Number>() {
+            Ok(number) if (first..=last).contains(&number) => return Some(number),
+            _ => println!("Invalid number: {input}"),
+        }
+    }
+}
@@ -10,25 +10,34 @@
 // - Direct calls to centralized modules eliminate code duplication
 //
 // Interactive Controls:
-// - ENTER: Extract a number using the /extract API endpoint
+// - ENTER: Extract a number using the /extract API endpoint (in manual draw mode, enter the number drawn from the basket)
+// - U: Undo the last extraction using the /undo API endpoint
 // - F5: Refresh screen and re-fetch fresh data from server without extracting
+//   (the screen is also refreshed automatically on the live updates pushed by the server)
 // - ESC: Exit the client application
 //
 // CLI Options:
 // - --newgame: Create a new game before starting the client
+// - --ruleset: Prize ruleset of the game created with --newgame
+// - --manual: Create the game with manual draw entry (numbers drawn from a physical basket)
 // - --gameid: Specify the game ID to connect to
+// - --importcards: Import pre-printed cards from a CSV or JSON file into the game before starting
 // - --listgames: List active games and exit
 
 use tombola::clients::terminal;
 
 // Use shared modules from library
-use tombola::clients::{game_utils, api_client, registration};
+use tombola::clients::{game_utils, api_client, card_management, registration};
+use tombola::clients::live_updates::LiveUpdates;
+use tombola::clients::common::{ClientAuth, RegisterRequest};
 
 use std::error::Error;
+use std::path::PathBuf;
 use clap::Parser;
 use tombola::defs::Number;
 use tombola::board::{Board, BOARD_ID};
 use tombola::config::ClientConfig;
+use tombola::ruleset::{CardLayout, RulesetKind};
 
 
 #[derive(Parser)]
@@ -44,6 +53,14 @@ struct Args {
     #[arg(long)]
     newgame: bool,
 
+    /// Prize ruleset of the new game (default: tombola)
+    #[arg(long, requires = "newgame")]
+    ruleset: Option<RulesetKind>,
+
+    /// Enter the numbers drawn from a physical basket instead of drawing them on the server
+    #[arg(long, requires = "newgame")]
+    manual: bool,
+
     /// Exit after displaying the current state (no interactive loop)
     #[arg(long)]
     exit: bool,
@@ -55,12 +72,16 @@ struct Args {
     /// List active games and exit
     #[arg(long)]
     listgames: bool,
+
+    /// Import pre-printed cards from a CSV or JSON file into the inventory of the game
+    #[arg(long, value_name = "FILE")]
+    importcards: Option<PathBuf>,
 }
 
 // Function to extract numbers from the highest achievement for highlighting
 // Only emphasizes board client's achievements, and only if no other client has achieved higher
 fn extract_highest_achievement_numbers(scorecard: &tombola::score::ScoreCard) -> Vec<Number> {
-    if scorecard.published_score < 2 {
+    if scorecard.score_map.is_empty() {
         return Vec::new();
     }
 
@@ -80,7 +101,7 @@ fn extract_highest_achievement_numbers(scorecard: &tombola::score::ScoreCard) ->
     }
 
     // Only show emphasis if the board client has the globally highest score
-    if board_client_highest_score == scorecard.published_score && board_client_highest_score >= 2 {
+    if board_client_highest_score == scorecard.published_score && board_client_highest_score > 0 {
         board_client_numbers
     } else {
         Vec::new() // No emphasis if another client achieved higher
@@ -158,19 +179,19 @@ pub async fn run_client_once_with_game_id(server_base_url: &str, game_id: &str,
     run_client_with_exit_flag_and_game_id(server_base_url, game_id, true, client_name, None).await
 }
 
-pub async fn run_client_with_game_id_and_client_id(server_base_url: &str, game_id: &str, client_name: &str, board_client_id: Option<String>) -> Result<(), Box<dyn Error>> {
-    run_client_with_exit_flag_and_game_id(server_base_url, game_id, false, client_name, board_client_id).await
+pub async fn run_client_with_game_id_and_client_id(server_base_url: &str, game_id: &str, client_name: &str, board_auth: Option<ClientAuth>) -> Result<(), Box<dyn Error>> {
+    run_client_with_exit_flag_and_game_id(server_base_url, game_id, false, client_name, board_auth).await
 }
 
-pub async fn run_client_once_with_game_id_and_client_id(server_base_url: &str, game_id: &str, client_name: &str, board_client_id: Option<String>) -> Result<(), Box<dyn Error>> {
-    run_client_with_exit_flag_and_game_id(server_base_url, game_id, true, client_name, board_client_id).await
+pub async fn run_client_once_with_game_id_and_client_id(server_base_url: &str, game_id: &str, client_name: &str, board_auth: Option<ClientAuth>) -> Result<(), Box<dyn Error>> {
+    run_client_with_exit_flag_and_game_id(server_base_url, game_id, true, client_name, board_auth).await
 }
 
-pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_id: &str, exit_after_display: bool, client_name: &str, existing_board_client_id: Option<String>) -> Result<(), Box<dyn Error>> {
-    // Use existing board client ID if provided, otherwise register a new client
-    let board_client_id = if let Some(client_id) = existing_board_client_id {
-        println!("🔗 Using existing board client ID: {client_id}");
-        client_id
+pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_id: &str, exit_after_display: bool, client_name: &str, existing_board_auth: Option<ClientAuth>) -> Result<(), Box<dyn Error>> {
+    // Use existing board client credentials if provided, otherwise register a new client
+    let board_auth = if let Some(auth) = existing_board_auth {
+        println!("🔗 Using existing board client ID: {}", auth.client_id);
+        auth
     } else {
         // Register the board client first
         let http_client = reqwest::Client::builder()
@@ -179,23 +200,35 @@ pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_i
 
         println!("🔗 Registering board client '{client_name}' with game '{game_id}'...");
 
+        // The API key of the configuration lets the client register again under its name
+        let config = ClientConfig::load_or_default();
         let register_response = registration::join_client(
             server_base_url,
             game_id,
-            client_name,
-            "board",
-            Some(1), // Generate 1 BOARD_ID card
-            Some("board@game.system".to_string()),
+            // Not the creator of the game: the board client joins it as a player with 1 card
+            &RegisterRequest::new(client_name, "board", Some(1), Some("board@game.system".to_string())),
+            config.api_key.as_deref(),
             &http_client
         ).await?;
 
-        let board_client_id = register_response.client_id;
-        println!("✅ Board client registered successfully with ID: {board_client_id}");
-        board_client_id
+        println!("✅ Board client registered successfully with ID: {}", register_response.client_id);
+        ClientAuth::from(register_response)
     };
 
+    // In manual draw mode the board owner types in the numbers drawn from the basket
+    let manual_draw = api_client::get_game_status(server_base_url, game_id, &board_auth.client_id).await
+        .is_ok_and(|status| status["draw_mode"] == "manual");
+
+    // Extractions and prizes from other clients redraw the board as they happen
+    let live = (!exit_after_display).then(|| LiveUpdates::start(server_base_url, game_id));
+
     // Main game loop
     loop {
+        // The state fetched below includes any update received so far
+        if let Some(live) = &live {
+            let _ = live.take_update();
+        }
+
         // Retrieve and display current game state
         let board_numbers = api_client::get_board_data(server_base_url, game_id).await?;
 
@@ -223,8 +256,8 @@ pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_i
         show_on_terminal_with_client_names(&display_board, &pouch_data, &scorecard_data, server_base_url, game_id).await;
 
         // Check if BINGO has been reached - if so, exit immediately
-        if scorecard_data.published_score >= 15 {
-            println!("🎉 GAME OVER: BINGO has been reached! 🎉");
+        if scorecard_data.is_game_over() {
+            println!("🎉 GAME OVER: {} has been reached! 🎉", scorecard_data.prize_name(scorecard_data.final_score()));
             println!("The game has ended. No more numbers can be extracted.");
             break; // Exit the game loop immediately
         }
@@ -237,10 +270,20 @@ pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_i
 
         // Wait for user input and handle actions
         let should_continue = loop {
-            match terminal::wait_for_user_action() {
+            let updated = || live.as_ref().is_some_and(LiveUpdates::take_update);
+            match terminal::wait_for_user_action_or_update(updated) {
                 terminal::KeyAction::Extract => {
-                    // Extract a number using the registered board client ID
-                    match api_client::extract_number(server_base_url, game_id, &board_client_id).await {
+                    let entered_number = if manual_draw {
+                        match terminal::read_drawn_number(&scorecard_data.board_config) {
+                            Some(number) => Some(number),
+                            None => continue, // Entry cancelled, wait for the next action
+                        }
+                    } else {
+                        None
+                    };
+
+                    // Extract a number with the token of the registered board client
+                    match api_client::extract_number(server_base_url, game_id, &board_auth.token, entered_number).await {
                         Ok(_) => {
                             break true; // Continue main loop to refresh display
                         }
@@ -257,12 +300,28 @@ pub async fn run_client_with_exit_flag_and_game_id(server_base_url: &str, game_i
                         }
                     }
                 }
+                terminal::KeyAction::Undo => {
+                    // Return the last extracted number to the pouch
+                    match api_client::undo_extraction(server_base_url, game_id, &board_auth.token).await {
+                        Ok(number) => {
+                            println!("↩️  Extraction of number {number} undone");
+                            break true; // Continue main loop to refresh display
+                        }
+                        Err(e) => {
+                            eprintln!("Error undoing extraction: {e}");
+                            continue;
+                        }
+                    }
+                }
                 terminal::KeyAction::Refresh => {
                     // Refresh: clear screen and re-fetch fresh data
                     print!("\x1Bc"); // Clear the screen
                     println!("🔄 Refreshing game state...");
                     break true; // Continue main loop to fetch fresh data and redisplay
                 }
+                terminal::KeyAction::Update => {
+                    break true; // The game changed on the server: redisplay it
+                }
                 terminal::KeyAction::Exit => {
                     println!("Exiting the client.");
                     break false; // Exit the main loop
@@ -298,25 +357,22 @@ async fn show_on_terminal_with_client_names(
     println!("Last number: {}{extracted}{}", tombola::defs::Colors::green(), tombola::defs::Colors::reset());
     println!("Previous numbers: {:?}", terminal::print_last_numbers(board, 3));
     println!("\nCurrent board:");
-    terminal::print_board(board);
+    if scorecard.ruleset.ruleset().card_layout() == CardLayout::Bingo75 {
+        terminal::print_bingo75_board(board, &scorecard.board_config);
+    } else {
+        terminal::print_board(board, &scorecard.board_config);
+    }
     println!();
 
     // Print scorecard with client names resolved
-    if scorecard.published_score >= 2 {
+    if !scorecard.score_map.is_empty() {
         println!();
         println!("ScoreCard achievements:");
         let mut achievements: Vec<_> = scorecard.score_map.iter().collect();
         achievements.sort_by(|a, b| b.0.cmp(a.0)); // Sort descending by score_idx
         for (score_idx, score_achievements) in achievements {
-            // Mark numbers only if scorecard reaches a NEW goal
-            match score_idx {
-                2 => print!("{}TWO in line{}", tombola::defs::Colors::yellow(), tombola::defs::Colors::reset()),
-                3 => print!("{}THREE in line{}", tombola::defs::Colors::yellow(), tombola::defs::Colors::reset()),
-                4 => print!("{}FOUR in line{}", tombola::defs::Colors::yellow(), tombola::defs::Colors::reset()),
-                5 => print!("{}FIVE in line{}", tombola::defs::Colors::yellow(), tombola::defs::Colors::reset()),
-                x if *x == tombola::defs::NUMBERSPERCARD => print!("{}BINGO!!!{}", tombola::defs::Colors::yellow(), tombola::defs::Colors::reset()),
-                _ => {} // Handle all other cases (do nothing)
-            }
+            // Prize names come from the ruleset of the game
+            print!("{}{}{}", tombola::defs::Colors::yellow(), scorecard.prize_name(*score_idx), tombola::defs::Colors::reset());
 
             // Display card IDs with resolved client names and their contributing numbers
             print!(" -> ");
@@ -351,7 +407,7 @@ async fn show_on_terminal_with_client_names(
 }
 
 // Check if the current client is the board owner of the specified game
-async fn check_if_board_owner(server_base_url: &str, game_id: &str) -> Result<Option<String>, Box<dyn Error>> {
+async fn check_if_board_owner(server_base_url: &str, game_id: &str) -> Result<Option<ClientAuth>, Box<dyn Error>> {
     // Load client configuration to get the correct client name
     let config = ClientConfig::load_or_default();
     let client_name = config.client_name.clone();
@@ -362,14 +418,12 @@ async fn check_if_board_owner(server_base_url: &str, game_id: &str) -> Result<Op
     // Register globally to get our client ID (this will return existing ID if already registered)
     let register_response = registration::register_global_client(
         server_base_url,
-        &client_name, // Use the same client name from config
-        "board",
-        Some(0),
-        None,
+        &RegisterRequest::new(&client_name, "board", Some(0), None), // Use the same client name from config
+        config.api_key.as_deref(),
         &client
     ).await?;
 
-    let our_client_id = register_response.client_id;
+    let our_auth = ClientAuth::from(register_response);
 
     // Get the game status to check who the owner is
     let status_url = format!("{server_base_url}/{game_id}/status");
@@ -379,10 +433,10 @@ async fn check_if_board_owner(server_base_url: &str, game_id: &str) -> Result<Op
         let status: serde_json::Value = response.json().await?;
 
         if let Some(owner_id) = status["owner"].as_str() {
-            if owner_id == our_client_id {
+            if owner_id == our_auth.client_id {
                 println!("🔗 Detected that you are the board owner of game {game_id}");
-                println!("✅ Using existing board client ID: {our_client_id}");
-                return Ok(Some(our_client_id));
+                println!("✅ Using existing board client ID: {}", our_auth.client_id);
+                return Ok(Some(our_auth));
             } else {
                 println!("ℹ️  Game {game_id} has a different board owner");
             }
@@ -393,7 +447,7 @@ async fn check_if_board_owner(server_base_url: &str, game_id: &str) -> Result<Op
     Ok(None)
 }
 
-async fn call_newgame(server_base_url: &str) -> Result<(String, String), Box<dyn Error>> {
+async fn call_newgame(server_base_url: &str, ruleset: Option<RulesetKind>, manual: bool) -> Result<(String, ClientAuth), Box<dyn Error>> {
     let client = reqwest::Client::new();
 
     // Load client configuration to get the correct client name
@@ -404,24 +458,32 @@ async fn call_newgame(server_base_url: &str) -> Result<(String, String), Box<dyn
     println!("🔄 Registering as board client globally...");
     let register_response = registration::register_global_client(
         server_base_url,
-        &client_name, // Use client name from config
-        "board",
-        Some(0), // Board clients don't need cards at global level
-        None,
+        // Use client name from config, board clients don't need cards at global level
+        &RegisterRequest::new(&client_name, "board", Some(0), None),
+        config.api_key.as_deref(),
         &client
     ).await?;
 
-    let board_client_id = register_response.client_id;
-    println!("✅ Registered as board client with ID: {board_client_id}");
+    println!("✅ Registered as board client with ID: {}", register_response.client_id);
+    let board_auth = ClientAuth::from(register_response);
 
     let url = format!("{server_base_url}/newgame");
     println!("🔄 Creating new game...");
 
-    let response = client
+    let mut request = client
         .post(&url)
-        .header("X-Client-ID", &board_client_id) // Use dynamic board client ID
-        .send()
-        .await?;
+        .bearer_auth(&board_auth.token); // Authenticate as the board client
+    if ruleset.is_some() || manual {
+        let mut body = serde_json::json!({});
+        if let Some(ruleset) = ruleset {
+            body["ruleset"] = serde_json::json!(ruleset);
+        }
+        if manual {
+            body["draw_mode"] = serde_json::json!("manual");
+        }
+        request = request.json(&body);
+    }
+    let response = request.send().await?;
 
     if response.status().is_success() {
         let newgame_response: serde_json::Value = response.json().await?;
@@ -445,12 +507,20 @@ async fn call_newgame(server_base_url: &str) -> Result<(String, String), Box<dyn
             println!("  Created: {created_at}");
         }
 
+        if let Some(ruleset) = newgame_response["ruleset"].as_str() {
+            println!("  Ruleset: {ruleset}");
+        }
+
+        if let Some(draw_mode) = newgame_response["draw_mode"].as_str() {
+            println!("  Draw mode: {draw_mode}");
+        }
+
         if let Some(note) = newgame_response["note"].as_str() {
             println!("  Note: {note}");
         }
 
         println!(); // Add blank line for readability
-        Ok((game_id, board_client_id))
+        Ok((game_id, board_auth))
     } else {
         let status = response.status();
         let error_text = response.text().await?;
@@ -500,11 +570,11 @@ async fn run_client_with_args(args: Args) -> Result<(), Box<dyn Error>> {
         return game_utils::list_games(&server_base_url).await;
     }
 
-    // Determine game_id and board_client_id
-    let (game_id, board_client_id) = if args.newgame {
+    // Determine game_id and the board client credentials
+    let (game_id, board_auth) = if args.newgame {
         // Create new game first
-        match call_newgame(&server_base_url).await {
-            Ok((new_game_id, client_id)) => (new_game_id, Some(client_id)),
+        match call_newgame(&server_base_url, args.ruleset, args.manual).await {
+            Ok((new_game_id, auth)) => (new_game_id, Some(auth)),
             Err(e) => {
                 eprintln!("Failed to reset game: {e}");
                 return Err(e);
@@ -512,8 +582,8 @@ async fn run_client_with_args(args: Args) -> Result<(), Box<dyn Error>> {
         }
     } else if let Some(provided_game_id) = args.gameid {
         // Check if we're already the board owner of this game
-        let board_client_id = check_if_board_owner(&server_base_url, &provided_game_id).await?;
-        (provided_game_id, board_client_id)
+        let board_auth = check_if_board_owner(&server_base_url, &provided_game_id).await?;
+        (provided_game_id, board_auth)
     } else {
         // No game_id provided and not creating new game - show games list first
         match game_utils::list_games(&server_base_url).await {
@@ -544,14 +614,25 @@ async fn run_client_with_args(args: Args) -> Result<(), Box<dyn Error>> {
 
     println!("Using game ID: {game_id}");
 
+    if let Some(path) = &args.importcards {
+        let Some(auth) = &board_auth else {
+            return Err(format!("Only the board owner of game {game_id} can import cards").into());
+        };
+        let serials = card_management::import_cards(&server_base_url, &game_id, &auth.token, path, &reqwest::Client::new()).await?;
+        println!("✅ Imported {} cards from {}, to be assigned by serial:", serials.len(), path.display());
+        for (index, serial) in serials.iter().enumerate() {
+            println!("  Card {}: {serial}", index + 1);
+        }
+    }
+
     // Determine client name from args or config
     let client_name = args.name.unwrap_or_else(|| config.client_name.clone());
     println!("Board client name: {client_name}");
 
     // Run the main client functionality with game_id and client_name
     if args.exit {
-        run_client_once_with_game_id_and_client_id(&server_base_url, &game_id, &client_name, board_client_id).await
+        run_client_once_with_game_id_and_client_id(&server_base_url, &game_id, &client_name, board_auth).await
     } else {
-        run_client_with_game_id_and_client_id(&server_base_url, &game_id, &client_name, board_client_id).await
+        run_client_with_game_id_and_client_id(&server_base_url, &game_id, &client_name, board_auth).await
     }
 }
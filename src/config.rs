@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use rand::Rng;
 
 #[derive(Debug, Clone)]
 #[derive(Default)]
@@ -23,12 +24,49 @@ impl From<&str> for LoggingMode {
     }
 }
 
+/// Storage backend of the games and clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default)]
+pub enum StoreKind {
+    Memory,
+    #[default]
+    Json,
+    Sqlite,
+}
+
+impl From<&str> for StoreKind {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "memory" => StoreKind::Memory,
+            "json" => StoreKind::Json,
+            "sqlite" => StoreKind::Sqlite,
+            _ => StoreKind::Json, // Default fallback
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub logging: LoggingMode,
     pub logpath: String,
+    pub store: StoreKind,
+    pub snapshot_path: String,
+    pub snapshot_interval: u64,
+    pub cleanup_interval: u64,
+    pub closed_game_retention: u64,
+    pub new_game_expiry: u64,
+    pub autorun: bool,
+    pub autorun_min_players: usize,
+    pub autorun_min_cards: usize,
+    pub autorun_start_time: Option<String>,
+    pub autorun_draw_interval: u64,
+    pub autorun_repeat: bool,
+    pub jwt_secret: String,
+    pub jwt_lifetime: u64,
+    /// IDs of the clients with the global admin role
+    pub admins: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -38,6 +76,7 @@ pub struct ClientConfig {
     pub timeout: u64,
     pub retry_attempts: u32,
     pub client_name: String,
+    pub api_key: Option<String>,  // API key of the client, needed to register again under its name
 }
 
 impl Default for ServerConfig {
@@ -47,6 +86,21 @@ impl Default for ServerConfig {
             port: 3000,
             logging: LoggingMode::default(),
             logpath: "./logs".to_string(),
+            store: StoreKind::default(),
+            snapshot_path: "./data/snapshot".to_string(),
+            snapshot_interval: 30,
+            cleanup_interval: 60,
+            closed_game_retention: 3600,
+            new_game_expiry: 86400,
+            autorun: false,
+            autorun_min_players: 1,
+            autorun_min_cards: 1,
+            autorun_start_time: None,
+            autorun_draw_interval: 10,
+            autorun_repeat: false,
+            jwt_secret: random_secret(),
+            jwt_lifetime: 86400,
+            admins: Vec::new(),
         }
     }
 }
@@ -59,6 +113,7 @@ impl Default for ClientConfig {
             timeout: 30,
             retry_attempts: 3,
             client_name: "DefaultClient".to_string(),
+            api_key: None,
         }
     }
 }
@@ -88,7 +143,11 @@ impl ClientConfig {
             .cloned()
             .unwrap_or_else(|| "DefaultClient".to_string());
 
-        Ok(ClientConfig { host, port, timeout, retry_attempts, client_name })
+        let api_key = config_map.get("api_key")
+            .filter(|k| !k.is_empty())
+            .cloned();
+
+        Ok(ClientConfig { host, port, timeout, retry_attempts, client_name, api_key })
     }
 
     #[must_use] pub fn load_or_default() -> Self {
@@ -132,7 +191,96 @@ impl ServerConfig {
             .cloned()
             .unwrap_or_else(|| "./logs".to_string());
 
-        Ok(ServerConfig { host, port, logging, logpath })
+        let store = config_map.get("store")
+            .map(|s| StoreKind::from(s.as_str()))
+            .unwrap_or_default();
+
+        let snapshot_path = config_map.get("snapshot_path")
+            .cloned()
+            .unwrap_or_else(|| "./data/snapshot".to_string());
+
+        let snapshot_interval = config_map.get("snapshot_interval")
+            .and_then(|i| i.parse::<u64>().ok())
+            .unwrap_or(30);
+
+        let cleanup_interval = config_map.get("cleanup_interval")
+            .and_then(|i| i.parse::<u64>().ok())
+            .unwrap_or(60);
+
+        let closed_game_retention = config_map.get("closed_game_retention")
+            .and_then(|r| r.parse::<u64>().ok())
+            .unwrap_or(3600);
+
+        let new_game_expiry = config_map.get("new_game_expiry")
+            .and_then(|e| e.parse::<u64>().ok())
+            .unwrap_or(86400);
+
+        let autorun = config_map.get("autorun")
+            .and_then(|a| a.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let autorun_min_players = config_map.get("autorun_min_players")
+            .and_then(|p| p.parse::<usize>().ok())
+            .unwrap_or(1);
+
+        let autorun_min_cards = config_map.get("autorun_min_cards")
+            .and_then(|c| c.parse::<usize>().ok())
+            .unwrap_or(1);
+
+        let autorun_start_time = config_map.get("autorun_start_time")
+            .filter(|t| !t.is_empty())
+            .cloned();
+
+        let autorun_draw_interval = config_map.get("autorun_draw_interval")
+            .and_then(|i| i.parse::<u64>().ok())
+            .unwrap_or(10);
+
+        let autorun_repeat = config_map.get("autorun_repeat")
+            .and_then(|r| r.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let jwt_secret = config_map.get("jwt_secret")
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .unwrap_or_else(|| {
+                println!("⚠️  No jwt_secret configured: tokens will not survive a server restart.");
+                random_secret()
+            });
+
+        let jwt_lifetime = config_map.get("jwt_lifetime")
+            .and_then(|l| l.parse::<u64>().ok())
+            .unwrap_or(86400);
+
+        let admins = config_map.get("admins")
+            .map(|a| parse_admins(a))
+            .unwrap_or_default();
+
+        Ok(ServerConfig {
+            host,
+            port,
+            logging,
+            logpath,
+            store,
+            snapshot_path,
+            snapshot_interval,
+            cleanup_interval,
+            closed_game_retention,
+            new_game_expiry,
+            autorun,
+            autorun_min_players,
+            autorun_min_cards,
+            autorun_start_time,
+            autorun_draw_interval,
+            autorun_repeat,
+            jwt_secret,
+            jwt_lifetime,
+            admins,
+        })
+    }
+
+    /// Whether a client holds the global admin role, which is bound to its ID and never to its name
+    #[must_use] pub fn is_admin(&self, client_id: &str) -> bool {
+        self.admins.iter().any(|admin_id| admin_id.eq_ignore_ascii_case(client_id))
     }
 
     #[must_use] pub fn load_or_default() -> Self {
@@ -151,6 +299,11 @@ impl ServerConfig {
     }
 }
 
+// Secret signing the tokens when none is configured, so that they are only valid until the server stops
+fn random_secret() -> String {
+    rand::rng().random::<[u8; 32]>().iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 fn parse_config(content: &str) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
     let mut config = HashMap::new();
 
@@ -173,6 +326,22 @@ fn parse_config(content: &str) -> Result<HashMap<String, String>, Box<dyn std::e
     Ok(config)
 }
 
+// IDs of the admins of server.conf, the 16 hexadecimal digits of registered clients
+fn parse_admins(admins: &str) -> Vec<String> {
+    admins.split(',')
+        .map(str::trim)
+        .filter(|admin_id| !admin_id.is_empty())
+        .filter(|admin_id| {
+            let valid = admin_id.len() == 16 && admin_id.chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                println!("⚠️  Ignoring admin '{admin_id}': admins are the client IDs of registered clients, not names.");
+            }
+            valid
+        })
+        .map(str::to_ascii_uppercase)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,6 +362,15 @@ mod tests {
         assert_eq!(config.get("max_connections"), Some(&"50".to_string()));
     }
 
+    #[test]
+    fn test_parse_admins() {
+        assert_eq!(parse_admins(" 0123456789abcdef, ,FEDCBA9876543210,game_admin,12345"), vec!["0123456789ABCDEF", "FEDCBA9876543210"]);
+
+        let config = ServerConfig { admins: parse_admins("0123456789abcdef"), ..ServerConfig::default() };
+        assert!(config.is_admin("0123456789ABCDEF"));
+        assert!(!config.is_admin("game_admin"));
+    }
+
     #[test]
     fn test_server_config_default() {
         let config = ServerConfig::default();
@@ -200,6 +378,22 @@ mod tests {
         assert_eq!(config.port, 3000);
         assert!(matches!(config.logging, LoggingMode::Console));
         assert_eq!(config.logpath, "./logs");
+        assert_eq!(config.store, StoreKind::Json);
+        assert_eq!(config.snapshot_path, "./data/snapshot");
+        assert_eq!(config.snapshot_interval, 30);
+        assert_eq!(config.cleanup_interval, 60);
+        assert_eq!(config.closed_game_retention, 3600);
+        assert_eq!(config.new_game_expiry, 86400);
+        assert!(!config.autorun);
+        assert_eq!(config.autorun_min_players, 1);
+        assert_eq!(config.autorun_min_cards, 1);
+        assert_eq!(config.autorun_start_time, None);
+        assert_eq!(config.autorun_draw_interval, 10);
+        assert!(!config.autorun_repeat);
+        assert_eq!(config.jwt_secret.len(), 64);
+        assert_ne!(config.jwt_secret, ServerConfig::default().jwt_secret);
+        assert_eq!(config.jwt_lifetime, 86400);
+        assert!(config.admins.is_empty());
     }
 
     #[test]
@@ -213,6 +407,14 @@ mod tests {
         assert!(matches!(LoggingMode::from("invalid"), LoggingMode::Console)); // Default fallback
     }
 
+    #[test]
+    fn test_store_kind_from_string() {
+        assert_eq!(StoreKind::from("memory"), StoreKind::Memory);
+        assert_eq!(StoreKind::from("json"), StoreKind::Json);
+        assert_eq!(StoreKind::from("SQLite"), StoreKind::Sqlite);
+        assert_eq!(StoreKind::from("invalid"), StoreKind::Json); // Default fallback
+    }
+
     #[test]
     fn test_server_config_with_logging() {
         let content = r"
@@ -247,6 +449,7 @@ mod tests {
             timeout: 30,
             retry_attempts: 3,
             client_name: "TestClient".to_string(),
+            api_key: None,
         };
         assert_eq!(config.server_url(), "http://192.168.1.100:8080");
     }
@@ -1,9 +1,16 @@
 // src/defs.rs
 // This module defines the basic structures and constants used in the Tombola game.
 
+use serde::{Deserialize, Serialize};
+
 // Type alias for numbers used in the Tombola game
 pub type Number = u8;
 
+/// Board and card geometry of a game.
+/// The compile-time `BOARDCONFIG` is the classic 90-number layout and the default for every new game,
+/// but each game carries its own copy so that one server can host differently shaped games.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
 pub struct BoardStruct {
     pub cols_per_card: Number,
     pub rows_per_card: Number,
@@ -13,6 +20,7 @@ pub struct BoardStruct {
     pub vnumbers_space: Number,
     pub hcards_space: Number,
     pub vcards_space: Number,
+    pub first_number: Number,
 }
 
 pub const BOARDCONFIG: BoardStruct = BoardStruct {
@@ -24,12 +32,103 @@ pub const BOARDCONFIG: BoardStruct = BoardStruct {
     vnumbers_space: 1, // space between numbers in the same column
     hcards_space: 2, // space between cards in the same row
     vcards_space: 1, // space between cards in the same column
+    first_number: 1, // first number in the pouch
+};
+
+/// Board of the US 75-ball bingo: one row of 15 numbers for each letter of B-I-N-G-O
+pub const BINGO75_BOARDCONFIG: BoardStruct = BoardStruct {
+    cols_per_card: 5,
+    rows_per_card: 5,
+    cards_per_row: 3,
+    cards_per_col: 1,
+    ..BOARDCONFIG
 };
 
-pub const FIRSTNUMBER: Number = 1;
-pub const LASTNUMBER: Number = BOARDCONFIG.cols_per_card * BOARDCONFIG.rows_per_card * BOARDCONFIG.cards_per_row * BOARDCONFIG.cards_per_col - 1 + FIRSTNUMBER;
-pub const NUMBERSPERCARD: Number = BOARDCONFIG.cols_per_card * BOARDCONFIG.rows_per_card;
-pub const CARDSNUMBER: Number = BOARDCONFIG.cards_per_row * BOARDCONFIG.cards_per_col;
+// Column letters of a 75-ball bingo card (and rows of its board)
+pub const BINGO75_LETTERS: [char; 5] = ['B', 'I', 'N', 'G', 'O'];
+
+pub const FIRSTNUMBER: Number = BOARDCONFIG.first_number;
+pub const LASTNUMBER: Number = BOARDCONFIG.last_number();
+pub const NUMBERSPERCARD: Number = BOARDCONFIG.numbers_per_card();
+pub const CARDSNUMBER: Number = BOARDCONFIG.cards_number();
+
+// Numbers covered by each column of a player card (1-9, 10-19, ... as on the classic cartella)
+pub const NUMBERS_PER_CARD_COLUMN: usize = 10;
+
+impl Default for BoardStruct {
+    fn default() -> Self {
+        BOARDCONFIG
+    }
+}
+
+impl BoardStruct {
+    /// Numbers in a single row of the board
+    #[must_use] pub const fn numbers_per_row(&self) -> Number {
+        self.cols_per_card * self.cards_per_row
+    }
+
+    /// Numbers on a complete card (the BINGO score)
+    #[must_use] pub const fn numbers_per_card(&self) -> Number {
+        self.cols_per_card * self.rows_per_card
+    }
+
+    /// Cards that make up the board (and a full series of player cards)
+    #[must_use] pub const fn cards_number(&self) -> Number {
+        self.cards_per_row * self.cards_per_col
+    }
+
+    /// Total amount of numbers in the pouch
+    #[must_use] pub const fn total_numbers(&self) -> usize {
+        self.cols_per_card as usize * self.rows_per_card as usize * self.cards_per_row as usize * self.cards_per_col as usize
+    }
+
+    #[must_use] pub const fn last_number(&self) -> Number {
+        (self.first_number as usize + self.total_numbers() - 1) as Number
+    }
+
+    /// Number of columns of a player card
+    #[must_use] pub const fn card_columns(&self) -> usize {
+        self.total_numbers() / NUMBERS_PER_CARD_COLUMN
+    }
+
+    /// Column of a player card where the given number belongs.
+    /// The last column also takes the remainder of the range (80-90 on the classic layout).
+    #[must_use] pub fn card_column_of(&self, number: Number) -> usize {
+        let offset = (number as usize + 1).saturating_sub(self.first_number as usize);
+        std::cmp::min(offset / NUMBERS_PER_CARD_COLUMN, self.card_columns().saturating_sub(1))
+    }
+
+    /// Check that cards can actually be generated and scored with this geometry
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cols_per_card == 0 || self.rows_per_card == 0 || self.cards_per_row == 0 || self.cards_per_col == 0 {
+            return Err("Board geometry values must be greater than zero".to_string());
+        }
+        if self.first_number == 0 {
+            return Err("first_number must be at least 1".to_string());
+        }
+        let last = self.first_number as usize + self.total_numbers() - 1;
+        if last > Number::MAX as usize {
+            return Err(format!("Number range {}-{last} exceeds the maximum of {}", self.first_number, Number::MAX));
+        }
+        let columns = self.card_columns();
+        if columns < self.cols_per_card as usize {
+            return Err(format!(
+                "{} numbers give {columns} card columns, fewer than the {} numbers per card row",
+                self.total_numbers(), self.cols_per_card
+            ));
+        }
+        // Every card column must fit in the cards of a series (at most one number per row of each card)
+        let widest_column = self.total_numbers() - NUMBERS_PER_CARD_COLUMN * (columns - 1);
+        let column_capacity = self.cards_number() as usize * self.rows_per_card as usize;
+        if widest_column > column_capacity {
+            return Err(format!(
+                "Card columns of {widest_column} numbers do not fit in {} cards of {} rows",
+                self.cards_number(), self.rows_per_card
+            ));
+        }
+        Ok(())
+    }
+}
 
 // Color definitions for terminal output (ESC sequences)
 pub struct Colors;
@@ -2,68 +2,155 @@
 // Core extraction logic shared between tombola_server and API server
 
 use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
 use crate::defs::Number;
 use crate::pouch::Pouch;
 use crate::board::Board;
-use crate::score::ScoreCard;
+use crate::score::{ScoreAchievement, ScoreCard};
 use crate::card::CardAssignmentManager;
 
+/// How the numbers of a game are drawn
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DrawMode {
+    /// The server draws the numbers from its pouch
+    #[default]
+    Random,
+    /// The board owner enters the numbers drawn from a physical basket
+    Manual,
+}
+
+// Extraction request body, only used with manual draw entry
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExtractRequest {
+    pub number: Option<Number>,
+}
+
+/// Where the number of an extraction comes from
+pub trait ExtractionSource {
+    /// Take the extracted number out of the pouch
+    fn draw(&self, pouch: &mut Pouch) -> Result<Number, String>;
+}
+
+/// Numbers drawn by the server from the pouch, in the order derived from the game seed
+pub struct PouchDraw;
+
+impl ExtractionSource for PouchDraw {
+    fn draw(&self, pouch: &mut Pouch) -> Result<Number, String> {
+        if pouch.is_empty() {
+            return Err("Pouch is empty".to_string());
+        }
+        Ok(pouch.extract())
+    }
+}
+
+/// Number drawn from a physical basket and entered by the board owner
+pub struct ManualDraw(pub Number);
+
+impl ExtractionSource for ManualDraw {
+    fn draw(&self, pouch: &mut Pouch) -> Result<Number, String> {
+        if pouch.is_empty() {
+            return Err("Pouch is empty".to_string());
+        }
+        pouch.take(self.0).ok_or_else(|| format!("Number {} is not in the pouch", self.0))
+    }
+}
+
+// Undo request body
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UndoRequest {
+    /// Number of extractions to undo (defaults to 1)
+    pub count: Option<usize>,
+}
+
+// Core undo function: returns the last extracted numbers to the pouch and rolls back the scorecard
+// Returns the undone numbers, most recent first
+pub fn perform_undo(
+    pouch_ref: &Arc<Mutex<Pouch>>,
+    board_ref: &Arc<Mutex<Board>>,
+    scorecard_ref: &Arc<Mutex<ScoreCard>>,
+    count: usize,
+) -> Result<Vec<Number>, String> {
+    // Same lock order as perform_extraction: scorecard, board, then pouch
+    let mut scorecard = scorecard_ref.lock()
+        .map_err(|_| "Failed to acquire scorecard lock".to_string())?;
+    let mut board = board_ref.lock()
+        .map_err(|_| "Failed to acquire board lock".to_string())?;
+    let mut pouch = pouch_ref.lock()
+        .map_err(|_| "Failed to acquire pouch lock".to_string())?;
+
+    if count == 0 || count > board.len() {
+        return Err(format!("Cannot undo {count} extractions: {} numbers have been extracted", board.len()));
+    }
+
+    // Drop the prizes earned through the undone numbers before recomputing the marked numbers
+    scorecard.rollback_to(&board.get_numbers()[..board.len() - count]);
+    let mut undone = Vec::with_capacity(count);
+    for _ in 0..count {
+        if let Some(number) = board.pop(&scorecard) {
+            pouch.put_back(number);
+            undone.push(number);
+        }
+    }
+
+    Ok(undone)
+}
+
+/// Outcome of an extraction, read under the locks it was performed with
+#[derive(Debug)]
+pub struct Extraction {
+    pub number: Number,
+    pub working_score: Number,
+    /// Number of extractions on the board after this one
+    pub draw_index: usize,
+    /// Prizes awarded by the extraction, in prize order
+    pub awarded: Vec<(Number, ScoreAchievement)>,
+    /// Whether the extraction awarded the prize that ends the game
+    pub closed: bool,
+}
+
 // Core extraction function that handles the game logic
 pub fn perform_extraction(
+    source: &dyn ExtractionSource,
     pouch_ref: &Arc<Mutex<Pouch>>,
     board_ref: &Arc<Mutex<Board>>,
     scorecard_ref: &Arc<Mutex<ScoreCard>>,
     card_manager: &Arc<Mutex<CardAssignmentManager>>,
     current_working_score: Number,
     board_client_id: Option<&str>,
-) -> Result<(Number, Number), String> {
-    // Open pouch mutex once for empty check and extraction
-    let extracted: Number = {
-        let mut pouch = match pouch_ref.lock() {
-            Ok(p) => p,
-            Err(_) => return Err("Failed to acquire pouch lock".to_string()),
-        };
-        if pouch.is_empty() {
-            return Err("Pouch is empty".to_string());
-        }
-        pouch.extract()
-    };
+) -> Result<Extraction, String> {
+    // Draw, mark and score under the card manager, scorecard, board and pouch locks, taken in this order,
+    // so that no reader sees the number on the board before the prizes it awards
+    let card_assignments_manager = card_manager.lock()
+        .map_err(|_| "Failed to acquire card manager lock".to_string())?;
+    let mut scorecard = scorecard_ref.lock()
+        .map_err(|_| "Failed to acquire scorecard lock".to_string())?;
+    let mut board = board_ref.lock()
+        .map_err(|_| "Failed to acquire board lock".to_string())?;
+    let mut pouch = pouch_ref.lock()
+        .map_err(|_| "Failed to acquire pouch lock".to_string())?;
+
+    let extracted = source.draw(&mut pouch)?;
 
     // Check if extraction was successful (pouch not empty)
     if extracted == 0 {
         return Err("No numbers remaining in pouch".to_string());
     }
 
-    // Perform all board operations in a coordinated manner
-    // Keep board and scorecard locks open for the entire operation
-    if let Ok(mut board) = board_ref.lock() {
-        if let Ok(scorecard) = scorecard_ref.lock() {
-            // Add the extracted number to the board (includes scoring and marking)
-            board.push(extracted, &scorecard);
-        } else {
-            return Err("Failed to acquire scorecard lock".to_string());
-        }
-    } else {
-        return Err("Failed to acquire board lock".to_string());
-    } // Both locks are released here
-
-    // Calculate scores for all cards - keep all needed locks open
-    let new_working_score = {
-        // Calculate and update the best score using ScoreCard method with all locks coordinated
-        if let Ok(card_assignments_manager) = card_manager.lock() {
-            if let Ok(mut scorecard) = scorecard_ref.lock() {
-                if let Ok(board) = board_ref.lock() {
-                    scorecard.calculate_and_update_best_score(&board, &card_assignments_manager, current_working_score, board_client_id)
-                } else {
-                    return Err("Failed to acquire board lock for scoring".to_string());
-                }
-            } else {
-                return Err("Failed to acquire scorecard lock for scoring".to_string());
-            }
-        } else {
-            return Err("Failed to acquire card manager lock".to_string());
-        }
-    };
+    let before = scorecard.achievement_counts();
+    let was_over = scorecard.is_game_over();
+
+    // Add the extracted number to the board (includes scoring and marking)
+    board.push(extracted, &scorecard);
+
+    // Calculate and update the best score of all cards
+    let new_working_score = scorecard.calculate_and_update_best_score(&board, &card_assignments_manager, current_working_score, board_client_id);
 
-    Ok((extracted, new_working_score))
+    Ok(Extraction {
+        number: extracted,
+        working_score: new_working_score,
+        draw_index: board.len(),
+        awarded: scorecard.achievements_since(&before),
+        closed: !was_over && scorecard.is_game_over(),
+    })
 }
@@ -7,126 +7,163 @@
 // re-registration for a truly fresh game experience.
 
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, watch};
 use std::collections::HashMap;
 use rand::Rng;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::board::{Board, BOARD_ID};
-use crate::pouch::Pouch;
-use crate::score::ScoreCard;
+use crate::pouch::{verify_draw, DrawSeed, DrawVerification, Pouch};
+use crate::score::{ClaimPenalty, ClaimVerdict, PrizeMode, ScoreAchievement, ScoreCard};
 use crate::logging::{log, LogLevel};
 use std::collections::HashSet;
-use crate::card::CardAssignmentManager;
-use crate::defs::Number;
-use crate::extraction::perform_extraction;
+use crate::card::{Card, CardAssignmentManager, CardDistribution, CardInfo};
+use crate::journal::{GameEvent, GameUpdate, Journal, JournalEntry};
+use crate::ruleset::RulesetKind;
+use crate::role::Role;
+use crate::autorun::{AutoRun, AutoRunRequest};
+use crate::defs::{BoardStruct, Number, BOARDCONFIG};
+use crate::extraction::{perform_extraction, perform_undo, DrawMode, ExtractionSource, ManualDraw, PouchDraw};
 
 const MODULE_NAME: &str = "game";
+// Live updates kept for slow subscribers before they are told to resync
+const UPDATES_CAPACITY: usize = 256;
 
-/// Game-specific client type association
-/// This allows clients to have different types in different games
+/// Directory of the JSON dumps of the ended games
+pub const DUMP_DIR: &str = "data/games";
+
+/// Role of a client in a game
+/// This allows clients to have different roles in different games
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameClientType {
+pub struct GameClientRole {
     pub client_id: String,
-    pub client_type: String, // "board", "player", etc.
+    #[serde(alias = "client_type")]
+    pub role: Role,
 }
 
-/// Game-specific client type registry
-/// Manages client types within a specific game context
+/// Game-specific role registry
+/// Manages the roles of the clients within a specific game context
 #[derive(Debug, Clone)]
-pub struct GameClientTypeRegistry {
-    /// `HashMap` mapping `client_id` -> `client_type` for this specific game
-    client_types: Arc<Mutex<HashMap<String, String>>>,
+pub struct GameRoleRegistry {
+    /// `HashMap` mapping `client_id` -> role for this specific game
+    roles: Arc<Mutex<HashMap<String, Role>>>,
 }
 
-impl Default for GameClientTypeRegistry {
+impl Default for GameRoleRegistry {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl GameClientTypeRegistry {
-    /// Create a new empty client type registry
+impl GameRoleRegistry {
+    /// Create a new empty role registry
     #[must_use] pub fn new() -> Self {
         Self {
-            client_types: Arc::new(Mutex::new(HashMap::new())),
+            roles: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    /// Set the client type for a client in this game
-    pub fn set_client_type(&self, client_id: &str, client_type: &str) -> Result<(), String> {
-        let mut types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Set the role of a client in this game
+    pub fn set_role(&self, client_id: &str, role: Role) -> Result<(), String> {
+        let mut roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        types_lock.insert(client_id.to_string(), client_type.to_string());
+        roles_lock.insert(client_id.to_string(), role);
         Ok(())
     }
 
-    /// Get the client type for a client in this game
-    pub fn get_client_type(&self, client_id: &str) -> Result<Option<String>, String> {
-        let types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Get the role of a client in this game
+    pub fn get_role(&self, client_id: &str) -> Result<Option<Role>, String> {
+        let roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        Ok(types_lock.get(client_id).cloned())
+        Ok(roles_lock.get(client_id).copied())
     }
 
-    /// Remove a client's type association from this game
-    pub fn remove_client_type(&self, client_id: &str) -> Result<Option<String>, String> {
-        let mut types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Remove the role of a client from this game
+    pub fn remove_role(&self, client_id: &str) -> Result<Option<Role>, String> {
+        let mut roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        Ok(types_lock.remove(client_id))
+        Ok(roles_lock.remove(client_id))
     }
 
-    /// Get all clients of a specific type in this game
-    pub fn get_clients_by_type(&self, client_type: &str) -> Result<Vec<String>, String> {
-        let types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Get all clients with a specific role in this game
+    pub fn get_clients_with_role(&self, role: Role) -> Result<Vec<String>, String> {
+        let roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        let clients: Vec<String> = types_lock
+        let clients: Vec<String> = roles_lock
             .iter()
-            .filter(|(_, ctype)| *ctype == client_type)
+            .filter(|(_, client_role)| **client_role == role)
             .map(|(client_id, _)| client_id.clone())
             .collect();
 
         Ok(clients)
     }
 
-    /// Check if a client has a specific type in this game
-    pub fn is_client_type(&self, client_id: &str, client_type: &str) -> Result<bool, String> {
-        let types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Check if a client has a specific role in this game
+    pub fn has_role(&self, client_id: &str, role: Role) -> Result<bool, String> {
+        let roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        Ok(types_lock.get(client_id).is_some_and(|ctype| ctype == client_type))
+        Ok(roles_lock.get(client_id) == Some(&role))
     }
 
-    /// Get all client type associations in this game
-    pub fn get_all_client_types(&self) -> Result<Vec<GameClientType>, String> {
-        let types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+    /// Get the roles of all the clients of this game
+    pub fn get_all_roles(&self) -> Result<Vec<GameClientRole>, String> {
+        let roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        let client_types: Vec<GameClientType> = types_lock
+        let roles: Vec<GameClientRole> = roles_lock
             .iter()
-            .map(|(client_id, client_type)| GameClientType {
+            .map(|(client_id, role)| GameClientRole {
                 client_id: client_id.clone(),
-                client_type: client_type.clone(),
+                role: *role,
             })
             .collect();
 
-        Ok(client_types)
+        Ok(roles)
     }
 
-    /// Clear all client type associations for this game
+    /// Clear all the roles of this game
     pub fn clear(&self) -> Result<usize, String> {
-        let mut types_lock = self.client_types.lock()
-            .map_err(|_| "Failed to lock client types registry")?;
+        let mut roles_lock = self.roles.lock()
+            .map_err(|_| "Failed to lock role registry")?;
 
-        let count = types_lock.len();
-        types_lock.clear();
+        let count = roles_lock.len();
+        roles_lock.clear();
         Ok(count)
     }
 }
 
+/// A player of a game with the number of cards it holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSummary {
+    pub client_id: String,
+    pub role: Role,
+    pub card_count: usize,
+}
+
+/// Consistent view of a game, read at once under its locks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub game_id: String,
+    /// Increased by every change of the game
+    pub version: usize,
+    pub status: String,
+    pub owner: Option<String>,
+    /// Extracted numbers in draw order
+    pub board: Vec<Number>,
+    /// Numbers left in the pouch
+    pub pouch_size: usize,
+    pub scorecard: ScoreCard,
+    pub players: Vec<PlayerSummary>,
+    /// Clients following the game without cards
+    pub spectators: Vec<String>,
+}
+
 /// Represents the current status of a game
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameStatus {
@@ -185,9 +222,13 @@ impl GameEntry {
     }
 
     /// Update the `closed_at` timestamp if the game is closed
-    /// This should be called when checking status to ensure `closed_at` is properly set
+    /// The closure time recorded in the journal of the game is used when available
     pub fn update_closed_at(&mut self) {
-        if self.game.is_bingo_reached() && self.closed_at.is_none() {
+        if !self.game.is_bingo_reached() {
+            self.closed_at = None;
+        } else if let Some(closed_at) = self.game.closed_at() {
+            self.closed_at = Some(closed_at);
+        } else if self.closed_at.is_none() {
             self.closed_at = Some(SystemTime::now());
         }
     }
@@ -195,11 +236,8 @@ impl GameEntry {
     /// Get the status and update `closed_at` if necessary
     /// This is a convenience method that combines status checking with `closed_at` updating
     pub fn status_with_update(&mut self) -> GameStatus {
-        let status = self.status();
-        if status == GameStatus::Closed && self.closed_at.is_none() {
-            self.closed_at = Some(SystemTime::now());
-        }
-        status
+        self.update_closed_at();
+        self.status()
     }
 
     /// Check if the game is closed
@@ -399,6 +437,38 @@ impl GameRegistry {
         Ok((new_count, active_count, closed_count))
     }
 
+    /// Get all game entries, updating their `closed_at` timestamps
+    pub fn entries(&self) -> Result<Vec<GameEntry>, String> {
+        let mut games_lock = self.games.lock()
+            .map_err(|_| "Failed to lock games registry")?;
+
+        Ok(games_lock.values_mut()
+            .map(|entry| {
+                entry.update_closed_at();
+                entry.clone()
+            })
+            .collect())
+    }
+
+    /// Get the closure time of a game as a human-readable string, if it is closed
+    pub fn closed_at_string(&self, game_id: &str) -> Result<Option<String>, String> {
+        let mut games_lock = self.games.lock()
+            .map_err(|_| "Failed to lock games registry")?;
+
+        Ok(games_lock.get_mut(game_id).and_then(|entry| {
+            entry.update_closed_at();
+            entry.closed_at_string()
+        }))
+    }
+
+    /// Get all registered games
+    pub fn all_games(&self) -> Result<Vec<Arc<Game>>, String> {
+        let games_lock = self.games.lock()
+            .map_err(|_| "Failed to lock games registry")?;
+
+        Ok(games_lock.values().map(|entry| entry.game.clone()).collect())
+    }
+
     /// Clear all games from the registry
     pub fn clear(&self) -> Result<usize, String> {
         let mut games_lock = self.games.lock()
@@ -424,6 +494,27 @@ impl Clone for GameRegistry {
     }
 }
 
+/// Optional body of the `/newgame` request
+#[derive(Debug, Default, Deserialize)]
+pub struct NewGameRequest {
+    /// Board geometry for the new game (defaults to the classic 90-number layout)
+    pub board_config: Option<BoardStruct>,
+    /// Prize ruleset for the new game (defaults to the Italian tombola)
+    pub ruleset: Option<RulesetKind>,
+    /// Full-card prizes awarded before the game closes (defaults to 1)
+    pub full_card_prizes: Option<Number>,
+    /// Whether prizes are awarded automatically or claimed by the players (defaults to automatic)
+    pub prize_mode: Option<PrizeMode>,
+    /// Penalty for false claims in claim mode (defaults to none)
+    pub false_claim_penalty: Option<ClaimPenalty>,
+    /// Random draw by the server or manual entry of numbers drawn from a basket (defaults to random)
+    pub draw_mode: Option<DrawMode>,
+    /// Cards drawn for each request or dealt from complete series (defaults to random)
+    pub card_distribution: Option<CardDistribution>,
+    /// Run the game unattended, with the settings not given taken from server.conf
+    pub autorun: Option<AutoRunRequest>,
+}
+
 /// Game struct that holds all shared game state components
 /// This provides a single point of access for all game operations
 /// and ensures proper mutex coordination to prevent deadlocks.
@@ -437,27 +528,345 @@ pub struct Game {
     scorecard: Arc<Mutex<ScoreCard>>,
     registered_clients: Arc<Mutex<HashSet<String>>>,  // Just store client IDs
     card_manager: Arc<Mutex<CardAssignmentManager>>,
-    client_type_registry: GameClientTypeRegistry,  // Game-specific client types
+    role_registry: GameRoleRegistry,  // Roles of the clients in this game
+    board_config: BoardStruct,  // Board and card geometry, fixed for the whole game
+    ruleset: RulesetKind,  // Prize ruleset, fixed for the whole game
+    draw_commitment: String,  // Hash of the secret draw seed, published at creation
+    draw_mode: Arc<Mutex<DrawMode>>,  // Random draw by the server or manual entry of the drawn numbers
+    journal: Arc<Mutex<Journal>>,  // Append-only record of every mutation of the game
+    updates: broadcast::Sender<GameUpdate>,  // Live updates for the clients following the game
+    version: Arc<watch::Sender<usize>>,  // Bumped by every mutation, the length of the journal unless restored from a snapshot
+    autorun: Arc<Mutex<Option<AutoRun>>>,  // Settings of an unattended game, drawn by the server
 }
 
 impl Game {
     /// Create a new Game instance with all components initialized
     #[must_use] pub fn new() -> Self {
+        Self::with_config(BOARDCONFIG)
+    }
+
+    /// Create a new Game instance using the given board geometry
+    #[must_use] pub fn with_config(board_config: BoardStruct) -> Self {
+        Self::with_rules(board_config, RulesetKind::default())
+    }
+
+    /// Create a new Game instance using the given board geometry and prize ruleset
+    #[must_use] pub fn with_rules(board_config: BoardStruct, ruleset: RulesetKind) -> Self {
+        Self::with_seed(board_config, ruleset, DrawSeed::random())
+    }
+
+    /// Create a new Game instance whose draw order is derived from the given seed
+    #[must_use] pub fn with_seed(board_config: BoardStruct, ruleset: RulesetKind, seed: DrawSeed) -> Self {
         // Generate a random game ID
         let mut rng = rand::rng();
         let game_id = format!("game_{:08x}", rng.random::<u32>());
+        let created_at = SystemTime::now();
+
+        let mut journal = Journal::new();
+        let version = journal.record(GameEvent::GameCreated {
+            game_id: game_id.clone(),
+            created_at,
+            board_config,
+            ruleset,
+            draw_seed: seed.to_hex(),
+        }) + 1;
 
         Self {
             id: Arc::new(Mutex::new(game_id)),
-            created_at: Arc::new(Mutex::new(SystemTime::now())),
+            created_at: Arc::new(Mutex::new(created_at)),
             owner: Arc::new(Mutex::new(None)),  // Initially no owner, set when game is created
             board: Arc::new(Mutex::new(Board::new())),
-            pouch: Arc::new(Mutex::new(Pouch::new())),
-            scorecard: Arc::new(Mutex::new(ScoreCard::new())),
+            pouch: Arc::new(Mutex::new(Pouch::with_seed(&board_config, seed))),
+            scorecard: Arc::new(Mutex::new(ScoreCard::with_rules(board_config, ruleset))),
             registered_clients: Arc::new(Mutex::new(HashSet::new())),
-            card_manager: Arc::new(Mutex::new(CardAssignmentManager::new())),
-            client_type_registry: GameClientTypeRegistry::new(),
+            card_manager: Arc::new(Mutex::new(CardAssignmentManager::with_rules(board_config, ruleset))),
+            role_registry: GameRoleRegistry::new(),
+            board_config,
+            ruleset,
+            draw_commitment: seed.commitment(),
+            draw_mode: Arc::new(Mutex::new(DrawMode::default())),
+            journal: Arc::new(Mutex::new(journal)),
+            updates: broadcast::channel(UPDATES_CAPACITY).0,
+            version: Arc::new(watch::channel(version).0),
+            autorun: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Rebuild a game by replaying its journal from the `GameCreated` event
+    pub fn replay(entries: &[JournalEntry]) -> Result<Self, String> {
+        let Some(GameEvent::GameCreated { game_id, created_at, board_config, ruleset, draw_seed }) = entries.first().map(|entry| &entry.event) else {
+            return Err("The journal does not start with the creation of the game".to_string());
+        };
+
+        let game = Self::with_seed(*board_config, *ruleset, DrawSeed::from_hex(draw_seed)?);
+        *game.id.lock().map_err(|_| "Failed to lock game ID")? = game_id.clone();
+        *game.created_at.lock().map_err(|_| "Failed to lock creation time")? = *created_at;
+
+        for entry in &entries[1..] {
+            game.apply(&entry.event)
+                .map_err(|e| format!("Failed to replay event {} of game {game_id}: {e}", entry.seq))?;
+        }
+
+        // The replayed game continues the original journal
+        *game.journal.lock().map_err(|_| "Failed to lock journal")? = Journal::from_entries(entries.to_vec());
+        game.version.send_replace(entries.len());
+        Ok(game)
+    }
+
+    /// Keep a stored journal that could not be replayed, after restoring the game from its snapshot,
+    /// so that the next events follow the stored ones instead of restarting from the first sequence number
+    pub fn continue_journal(&self, entries: Vec<JournalEntry>) -> Result<(), String> {
+        *self.journal.lock().map_err(|_| "Failed to lock journal")? = Journal::from_entries(entries);
+        Ok(())
+    }
+
+    // Apply a journal event through the same methods that recorded it
+    fn apply(&self, event: &GameEvent) -> Result<(), String> {
+        match event {
+            GameEvent::GameCreated { .. } => Err("The game is already created".to_string()),
+            GameEvent::FullCardPrizesSet { full_card_prizes } => self.set_full_card_prizes(*full_card_prizes),
+            GameEvent::PrizeModeSet { prize_mode, claim_penalty } => self.set_prize_mode(*prize_mode, *claim_penalty),
+            GameEvent::DrawModeSet { draw_mode } => self.set_draw_mode(*draw_mode),
+            GameEvent::CardDistributionSet { distribution } => self.set_card_distribution(*distribution),
+            GameEvent::AutoRunSet { autorun } => self.set_autorun(autorun.clone()),
+            GameEvent::OwnerSet { client_id } => self.set_owner(client_id),
+            GameEvent::ClientJoined { client_id } => self.add_client(client_id.clone()).map(|_| ()),
+            GameEvent::SpectatorJoined { client_id } => self.add_spectator(client_id.clone()).map(|_| ()),
+            GameEvent::RoleSet { client_id, role } => self.set_role(client_id, *role),
+            GameEvent::CardsImported { cards } => self.import_cards(cards.clone()).map(|_| ()),
+            GameEvent::SeriesOpened { cards } => {
+                self.card_manager.lock()
+                    .map_err(|_| "Failed to lock card manager")?
+                    .open_series(cards.clone());
+                Ok(())
+            }
+            GameEvent::CardsAssigned { client_id, cards } => {
+                self.card_manager.lock()
+                    .map_err(|_| "Failed to lock card manager")?
+                    .restore_assignments(client_id, cards.clone());
+                Ok(())
+            }
+            GameEvent::NumberExtracted { number, current_working_score, board_client_id, .. } => self
+                .extract_number_from(&ManualDraw(*number), *current_working_score, board_client_id.as_deref())
+                .map(|_| ()),
+            GameEvent::PrizeClaimed { client_id, card_id, prize, .. } => self.claim_prize(client_id, card_id, *prize).map(|_| ()),
+            GameEvent::ExtractionsUndone { count, .. } => self.undo_extractions(*count).map(|_| ()),
+            GameEvent::PrizeAwarded { .. } | GameEvent::GameClosed { .. } => Ok(()),
+        }
+    }
+
+    /// Append an event to the journal of the game and push its public update to the subscribers
+    fn record(&self, event: GameEvent) {
+        match self.journal.lock() {
+            Ok(mut journal) => self.record_in(&mut journal, vec![event]),
+            Err(_) => log(LogLevel::Error, MODULE_NAME, "Failed to lock journal, event not recorded"),
+        }
+    }
+
+    // Append events to the journal, locked by the caller, and push their public updates in the same order
+    fn record_in(&self, journal: &mut Journal, events: Vec<GameEvent>) {
+        for event in events {
+            let update = GameUpdate::from_event(&event, |score| {
+                self.scorecard.lock().map(|scorecard| scorecard.prize_name(score)).unwrap_or_default()
+            });
+            journal.record(event);
+            self.version.send_modify(|version| *version += 1);
+            if let Some(update) = update {
+                // Nobody may be following the game
+                let _ = self.updates.send(update);
+            }
+        }
+    }
+
+    /// Follow the live updates of the game
+    #[must_use] pub fn subscribe(&self) -> broadcast::Receiver<GameUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// When the last prize closed the game, as recorded in its journal
+    #[must_use] pub fn closed_at(&self) -> Option<SystemTime> {
+        if !self.is_bingo_reached() {
+            return None;
+        }
+        let journal = self.journal.lock().ok()?;
+        journal.entries().iter().rev()
+            .find(|entry| matches!(entry.event, GameEvent::GameClosed { .. }))
+            .map(|entry| entry.recorded_at)
+    }
+
+    /// When the last change of the game was recorded in its journal
+    #[must_use] pub fn last_change_at(&self) -> Option<SystemTime> {
+        let journal = self.journal.lock().ok()?;
+        journal.entries().last().map(|entry| entry.recorded_at)
+    }
+
+    /// Version of the game, increased by every change
+    #[must_use] pub fn version(&self) -> usize {
+        *self.version.borrow()
+    }
+
+    /// Wait until the version of the game differs from the given one, at most for `timeout`
+    /// Returns the current version
+    pub async fn changed_since(&self, version: usize, timeout: Duration) -> usize {
+        let mut receiver = self.version.subscribe();
+        // A version behind the given one (a game restored from a snapshot) is a change too
+        let _ = tokio::time::timeout(timeout, receiver.wait_for(|current| *current != version)).await;
+        self.version()
+    }
+
+    /// Board, pouch size, scorecard, players and status of the game, read together under its locks
+    pub fn snapshot(&self) -> Result<GameSnapshot, String> {
+        // Same lock order as the extraction: journal, card manager, scorecard, board, then pouch.
+        // Extractions hold the journal until they have bumped the version, so it always matches the board read here
+        let journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        let card_manager = self.card_manager.lock()
+            .map_err(|_| "Failed to lock card manager")?;
+        let scorecard = self.scorecard.lock()
+            .map_err(|_| "Failed to lock scorecard")?;
+        let board = self.board.lock()
+            .map_err(|_| "Failed to lock board")?;
+        let pouch = self.pouch.lock()
+            .map_err(|_| "Failed to lock pouch")?;
+
+        let status = if scorecard.is_game_over() {
+            GameStatus::Closed
+        } else if board.is_empty() {
+            GameStatus::New
+        } else {
+            GameStatus::Active
+        };
+
+        let (spectators, players): (Vec<GameClientRole>, Vec<GameClientRole>) = self.get_all_roles()?
+            .into_iter()
+            .partition(|client| client.role == Role::Spectator);
+        let mut spectators: Vec<String> = spectators.into_iter().map(|client| client.client_id).collect();
+        spectators.sort();
+        let mut players: Vec<PlayerSummary> = players
+            .into_iter()
+            .map(|client| PlayerSummary {
+                card_count: card_manager.get_client_cards(&client.client_id)
+                    .map_or(0, |cards| cards.iter().filter(|card_id| *card_id != BOARD_ID).count()),
+                client_id: client.client_id,
+                role: client.role,
+            })
+            .collect();
+        // Most privileged roles first, then by client ID
+        players.sort_by(|a, b| (a.role, &a.client_id).cmp(&(b.role, &b.client_id)));
+
+        let version = self.version();
+        drop(journal);
+
+        Ok(GameSnapshot {
+            game_id: self.id(),
+            version,
+            status: status.as_str().to_lowercase(),
+            owner: self.owner(),
+            board: board.get_numbers().clone(),
+            pouch_size: pouch.len(),
+            scorecard: scorecard.clone(),
+            players,
+            spectators,
+        })
+    }
+
+    /// Updates of the extractions, prizes and closure after the given draw index, to resume following the game
+    /// The prizes of that draw index are sent again, since they share its event ID and may have been missed
+    /// Returns them with the draw index they reach; a draw index beyond the board (after an undo) starts with a resync
+    pub fn updates_since(&self, draw_index: usize) -> Result<(Vec<GameUpdate>, usize), String> {
+        let scorecard = self.scorecard.lock()
+            .map_err(|_| "Failed to lock scorecard")?;
+        let board = self.board.lock()
+            .map_err(|_| "Failed to lock board")?;
+        let numbers = board.get_numbers();
+
+        let prize_update = |score: Number, achievement: &ScoreAchievement| {
+            let event = GameEvent::PrizeAwarded { score, achievement: achievement.clone() };
+            GameUpdate::from_event(&event, |score| scorecard.prize_name(score))
+        };
+        let mut prizes: Vec<(Number, &ScoreAchievement)> = scorecard.score_map.iter()
+            .flat_map(|(score, achievements)| achievements.iter().map(move |achievement| (*score, achievement)))
+            .filter(|(_, achievement)| achievement.draw_index >= draw_index)
+            .collect();
+        prizes.sort_by_key(|(score, achievement)| (achievement.draw_index, *score));
+        let mut prizes = prizes.into_iter().peekable();
+
+        let mut updates = Vec::new();
+        if draw_index > numbers.len() {
+            updates.push(GameUpdate::Resync);
+        }
+        while let Some((score, achievement)) = prizes.next_if(|(_, achievement)| achievement.draw_index <= draw_index) {
+            updates.extend(prize_update(score, achievement));
+        }
+        for (index, &number) in numbers.iter().enumerate().skip(draw_index) {
+            updates.push(GameUpdate::Extraction { number, draw_index: index + 1 });
+            while let Some((score, achievement)) = prizes.next_if(|(_, achievement)| achievement.draw_index <= index + 1) {
+                updates.extend(prize_update(score, achievement));
+            }
+        }
+        // Prizes claimed after the last extraction
+        for (score, achievement) in prizes {
+            updates.extend(prize_update(score, achievement));
+        }
+        if scorecard.is_game_over() {
+            updates.push(GameUpdate::Closed { draw_index: numbers.len() });
+        }
+
+        Ok((updates, numbers.len()))
+    }
+
+    /// Get the journal entries recorded from the given sequence number on
+    pub fn journal_since(&self, seq: usize) -> Result<Vec<JournalEntry>, String> {
+        let journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        Ok(journal.since(seq).to_vec())
+    }
+
+    /// Get the number of entries in the journal of the game
+    #[must_use] pub fn journal_len(&self) -> usize {
+        self.journal.lock().map_or(0, |journal| journal.len())
+    }
+
+    // Number of achievements recorded for each prize, to find the ones awarded by an operation
+    /// Get the board geometry of this game
+    #[must_use] pub fn board_config(&self) -> &BoardStruct {
+        &self.board_config
+    }
+
+    /// Get the prize ruleset of this game
+    #[must_use] pub fn ruleset(&self) -> RulesetKind {
+        self.ruleset
+    }
+
+    /// Get the SHA-256 hash of the draw seed published at creation
+    #[must_use] pub fn draw_commitment(&self) -> &str {
+        &self.draw_commitment
+    }
+
+    /// Get the draw seed in hexadecimal, only once the game has ended
+    #[must_use] pub fn revealed_seed(&self) -> Option<String> {
+        if !self.is_game_ended() {
+            return None;
         }
+        self.pouch.lock().ok()?.seed().map(DrawSeed::to_hex)
+    }
+
+    /// Replay the draw from the revealed seed and check it against the commitment and the board
+    pub fn verify_draw(&self) -> Result<(String, DrawVerification), String> {
+        if self.draw_mode() == DrawMode::Manual {
+            return Err("The numbers of this game are drawn manually and cannot be replayed".to_string());
+        }
+        if !self.is_game_ended() {
+            return Err("The draw seed is revealed when the game closes".to_string());
+        }
+        let seed = {
+            let pouch = self.pouch.lock()
+                .map_err(|e| format!("Failed to acquire pouch lock: {e}"))?;
+            *pouch.seed().ok_or("The draw seed of this game is not available")?
+        };
+        let board = self.board.lock()
+            .map_err(|e| format!("Failed to acquire board lock: {e}"))?;
+        Ok((seed.to_hex(), verify_draw(&seed, &self.draw_commitment, &self.board_config, board.get_numbers())))
     }
 
     /// Get the game ID
@@ -488,12 +897,105 @@ impl Game {
         self.owner.lock().unwrap().clone()
     }
 
+    /// Set how many full-card prizes (TOMBOLA, TOMBOLINO, ...) are awarded before the game closes
+    pub fn set_full_card_prizes(&self, full_card_prizes: Number) -> Result<(), String> {
+        let mut scorecard = self.scorecard.lock()
+            .map_err(|e| format!("Failed to acquire scorecard lock: {e}"))?;
+        let first_full_card = self.ruleset.ruleset().final_prize(&self.board_config);
+        if full_card_prizes == 0 || first_full_card.checked_add(full_card_prizes - 1).is_none() {
+            return Err(format!("full_card_prizes must be between 1 and {}", Number::MAX - first_full_card + 1));
+        }
+        scorecard.set_full_card_prizes(full_card_prizes);
+        drop(scorecard);
+        log(LogLevel::Info, MODULE_NAME, &format!("Set full-card prizes to: {full_card_prizes}"));
+        self.record(GameEvent::FullCardPrizesSet { full_card_prizes });
+        Ok(())
+    }
+
+    /// Get how many full-card prizes are awarded before the game closes
+    #[must_use] pub fn full_card_prizes(&self) -> Number {
+        if let Ok(scorecard) = self.scorecard.lock() {
+            scorecard.full_card_prizes()
+        } else {
+            1
+        }
+    }
+
+    /// Set how prizes are awarded and the penalty for false claims
+    pub fn set_prize_mode(&self, prize_mode: PrizeMode, claim_penalty: ClaimPenalty) -> Result<(), String> {
+        let mut scorecard = self.scorecard.lock()
+            .map_err(|e| format!("Failed to acquire scorecard lock: {e}"))?;
+        scorecard.set_prize_mode(prize_mode, claim_penalty);
+        drop(scorecard);
+        log(LogLevel::Info, MODULE_NAME, &format!("Set prize mode to: {prize_mode:?} (false claim penalty: {claim_penalty:?})"));
+        self.record(GameEvent::PrizeModeSet { prize_mode, claim_penalty });
+        Ok(())
+    }
+
+    /// Get how prizes are awarded
+    #[must_use] pub fn prize_mode(&self) -> PrizeMode {
+        if let Ok(scorecard) = self.scorecard.lock() {
+            scorecard.prize_mode
+        } else {
+            PrizeMode::default()
+        }
+    }
+
+    /// Get the penalty for false claims
+    #[must_use] pub fn claim_penalty(&self) -> ClaimPenalty {
+        if let Ok(scorecard) = self.scorecard.lock() {
+            scorecard.claim_penalty
+        } else {
+            ClaimPenalty::default()
+        }
+    }
+
+    /// Verify a prize claim made by a client with one of its cards at the current draw
+    pub fn claim_prize(&self, client_id: &str, card_id: &str, prize: Number) -> Result<ClaimVerdict, String> {
+        // The journal stays locked from the verdict to its events, so that the claim is journaled at the draw it was judged
+        let mut journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        let (verdict, awarded, closed, draw_index) = {
+            // Same lock order as perform_extraction: card manager, scorecard, board
+            let card_manager = self.card_manager.lock()
+                .map_err(|e| format!("Failed to acquire card manager lock: {e}"))?;
+            let card = card_manager.get_card_assignment(card_id)
+                .filter(|assignment| assignment.client_id == client_id)
+                .ok_or_else(|| format!("Card {card_id} not assigned to client {client_id}"))?;
+            let mut scorecard = self.scorecard.lock()
+                .map_err(|e| format!("Failed to acquire scorecard lock: {e}"))?;
+            let board = self.board.lock()
+                .map_err(|e| format!("Failed to acquire board lock: {e}"))?;
+
+            let before = scorecard.achievement_counts();
+            let was_over = scorecard.is_game_over();
+            let verdict = scorecard.claim_prize(&board, card, prize)?;
+            log(LogLevel::Info, MODULE_NAME, &format!("Claim of prize {prize} by card {card_id} at draw {}: {verdict:?}", board.len()));
+            (verdict, scorecard.achievements_since(&before), scorecard.is_game_over() && !was_over, board.len())
+        };
+
+        let mut events = vec![GameEvent::PrizeClaimed {
+            client_id: client_id.to_string(),
+            card_id: card_id.to_string(),
+            prize,
+            verdict,
+        }];
+        events.extend(awarded.into_iter().map(|(score, achievement)| GameEvent::PrizeAwarded { score, achievement }));
+        if closed {
+            events.push(GameEvent::GameClosed { draw_index });
+        }
+        self.record_in(&mut journal, events);
+        Ok(verdict)
+    }
+
     /// Set the game owner (ClientID of the creator)
     pub fn set_owner(&self, client_id: &str) -> Result<(), String> {
         match self.owner.lock() {
             Ok(mut owner) => {
                 *owner = Some(client_id.to_string());
+                drop(owner);
                 log(LogLevel::Info, MODULE_NAME, &format!("Set game owner to: {client_id}"));
+                self.record(GameEvent::OwnerSet { client_id: client_id.to_string() });
                 Ok(())
             }
             Err(e) => {
@@ -586,16 +1088,37 @@ impl Game {
 
     /// Add a client to this game (only if no numbers have been extracted)
     pub fn add_client(&self, client_id: String) -> Result<bool, String> {
+        // Locked like the extraction, so that no extraction is journaled between the check and the join
+        let mut journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
         let numbers_extracted = self.has_game_started();
         if numbers_extracted {
             return Err("Cannot register new clients after numbers have been extracted".to_string());
         }
 
-        if let Ok(mut clients) = self.registered_clients.lock() {
-            Ok(clients.insert(client_id))
+        let added = if let Ok(mut clients) = self.registered_clients.lock() {
+            clients.insert(client_id.clone())
         } else {
-            Err("Failed to lock registered clients".to_string())
+            return Err("Failed to lock registered clients".to_string());
+        };
+        if added {
+            self.record_in(&mut journal, vec![GameEvent::ClientJoined { client_id }]);
+        }
+        Ok(added)
+    }
+
+    /// Add a spectator to this game, at any time
+    /// Spectators are clients of the game without cards, they are not counted as players
+    pub fn add_spectator(&self, client_id: String) -> Result<bool, String> {
+        let added = if let Ok(mut clients) = self.registered_clients.lock() {
+            clients.insert(client_id.clone())
+        } else {
+            return Err("Failed to lock registered clients".to_string());
+        };
+        if added {
+            self.record(GameEvent::SpectatorJoined { client_id });
         }
+        Ok(added)
     }
 
     /// Check if a client is registered to this game
@@ -607,36 +1130,46 @@ impl Game {
         }
     }
 
-    // Game-specific client type management methods
+    // Game-specific role management methods
+
+    /// Set the role of a client in this specific game
+    pub fn set_role(&self, client_id: &str, role: Role) -> Result<(), String> {
+        self.role_registry.set_role(client_id, role)?;
+        self.record(GameEvent::RoleSet {
+            client_id: client_id.to_string(),
+            role,
+        });
+        Ok(())
+    }
 
-    /// Set the client type for a client in this specific game
-    pub fn set_client_type(&self, client_id: &str, client_type: &str) -> Result<(), String> {
-        self.client_type_registry.set_client_type(client_id, client_type)
+    /// Get the role of a client in this specific game
+    pub fn get_role(&self, client_id: &str) -> Result<Option<Role>, String> {
+        self.role_registry.get_role(client_id)
     }
 
-    /// Get the client type for a client in this specific game
-    pub fn get_client_type(&self, client_id: &str) -> Result<Option<String>, String> {
-        self.client_type_registry.get_client_type(client_id)
+    /// Check if a client has a specific role in this game
+    pub fn has_role(&self, client_id: &str, role: Role) -> Result<bool, String> {
+        self.role_registry.has_role(client_id, role)
     }
 
-    /// Check if a client has a specific type in this game
-    pub fn is_client_type(&self, client_id: &str, client_type: &str) -> Result<bool, String> {
-        self.client_type_registry.is_client_type(client_id, client_type)
+    /// Get all clients with a specific role in this game
+    pub fn get_clients_with_role(&self, role: Role) -> Result<Vec<String>, String> {
+        self.role_registry.get_clients_with_role(role)
     }
 
-    /// Get all clients of a specific type in this game
-    pub fn get_clients_by_type(&self, client_type: &str) -> Result<Vec<String>, String> {
-        self.client_type_registry.get_clients_by_type(client_type)
+    /// Get the roles of all the clients of this game
+    pub fn get_all_roles(&self) -> Result<Vec<GameClientRole>, String> {
+        self.role_registry.get_all_roles()
     }
 
-    /// Get all client type associations in this game
-    pub fn get_all_client_types(&self) -> Result<Vec<GameClientType>, String> {
-        self.client_type_registry.get_all_client_types()
+    /// Remove the role of a client from this game
+    pub fn remove_role(&self, client_id: &str) -> Result<Option<Role>, String> {
+        self.role_registry.remove_role(client_id)
     }
 
-    /// Remove a client's type association from this game
-    pub fn remove_client_type(&self, client_id: &str) -> Result<Option<String>, String> {
-        self.client_type_registry.remove_client_type(client_id)
+    /// Whether a client holds the board card of this game
+    #[must_use] pub fn has_board_owner(&self) -> bool {
+        self.card_manager.lock().is_ok_and(|manager| manager.get_card_assignment(BOARD_ID).is_some())
     }
 
     /// Get a reference to the card manager Arc<Mutex<CardAssignmentManager>>
@@ -644,17 +1177,205 @@ impl Game {
         &self.card_manager
     }
 
+    /// Generate and assign cards to a client, replacing the cards it had
+    /// The owner gets the board card only while the game has no board owner, otherwise it plays as a player
+    pub fn assign_cards(&self, client_id: &str, count: u32, role: Option<Role>) -> Result<(Vec<CardInfo>, Vec<String>), String> {
+        if self.has_role(client_id, Role::Spectator)? {
+            return Err("Spectators cannot hold cards".to_string());
+        }
+        // The journal is held so that a series is journaled before any card dealt from it
+        let mut journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        let (card_infos, card_ids, series, cards) = {
+            let mut manager = self.card_manager.lock()
+                .map_err(|_| "Failed to acquire card manager lock")?;
+            let client_type = if role != Some(Role::Owner) {
+                None
+            } else if manager.get_card_assignment(BOARD_ID).is_some() {
+                log(LogLevel::Info, MODULE_NAME, &format!("[Client: {client_id}] Board owner already exists, treating board client as regular player"));
+                None
+            } else {
+                Some("board")
+            };
+            let (card_infos, card_ids) = manager.assign_cards_with_type(client_id, count, client_type)?;
+            let cards = card_ids.iter()
+                .filter_map(|card_id| manager.get_card_assignment(card_id).cloned())
+                .collect();
+            (card_infos, card_ids, manager.take_opened_series(), cards)
+        };
+
+        let mut events = Vec::new();
+        if !series.is_empty() {
+            events.push(GameEvent::SeriesOpened { cards: series });
+        }
+        events.push(GameEvent::CardsAssigned { client_id: client_id.to_string(), cards });
+        self.record_in(&mut journal, events);
+        Ok((card_infos, card_ids))
+    }
+
+    /// Add pre-printed cards to the inventory of the game before the first extraction, returning their serials
+    pub fn import_cards(&self, cards: Vec<Card>) -> Result<Vec<String>, String> {
+        if self.has_game_started() {
+            return Err("Cards cannot be imported after the first extraction".to_string());
+        }
+        let serials = self.card_manager.lock()
+            .map_err(|_| "Failed to acquire card manager lock")?
+            .import_cards(cards.clone())?;
+        log(LogLevel::Info, MODULE_NAME, &format!("Imported {} cards into the inventory", serials.len()));
+        self.record(GameEvent::CardsImported { cards });
+        Ok(serials)
+    }
+
+    /// Assign cards of the inventory to a client by serial before the first extraction, returning the new cards
+    pub fn assign_inventory_cards(&self, client_id: &str, serials: &[String]) -> Result<Vec<CardInfo>, String> {
+        if self.has_game_started() {
+            return Err("Cards cannot be assigned after the first extraction".to_string());
+        }
+        if self.has_role(client_id, Role::Spectator)? {
+            return Err("Spectators cannot hold cards".to_string());
+        }
+        let cards = self.card_manager.lock()
+            .map_err(|_| "Failed to acquire card manager lock")?
+            .assign_inventory_cards(client_id, serials)?;
+        let card_infos = cards.iter()
+            .filter(|assignment| serials.contains(&assignment.card_id))
+            .map(|assignment| CardInfo { card_id: assignment.card_id.clone(), card_data: assignment.card_data.clone() })
+            .collect();
+
+        // The event holds every card of the client, as its replay replaces them
+        self.record(GameEvent::CardsAssigned { client_id: client_id.to_string(), cards });
+        Ok(card_infos)
+    }
+
+    /// Set how the numbers of the game are drawn, before the first extraction
+    pub fn set_draw_mode(&self, draw_mode: DrawMode) -> Result<(), String> {
+        if self.has_game_started() {
+            return Err("The draw mode cannot be changed after the first extraction".to_string());
+        }
+        let mut current = self.draw_mode.lock()
+            .map_err(|e| format!("Failed to acquire draw mode lock: {e}"))?;
+        *current = draw_mode;
+        drop(current);
+        log(LogLevel::Info, MODULE_NAME, &format!("Set draw mode to: {draw_mode:?}"));
+        self.record(GameEvent::DrawModeSet { draw_mode });
+        Ok(())
+    }
+
+    /// Get how the numbers of the game are drawn
+    #[must_use] pub fn draw_mode(&self) -> DrawMode {
+        if let Ok(draw_mode) = self.draw_mode.lock() {
+            *draw_mode
+        } else {
+            DrawMode::default()
+        }
+    }
+
+    /// Set how the cards of the game are dealt, before the first extraction
+    pub fn set_card_distribution(&self, distribution: CardDistribution) -> Result<(), String> {
+        if self.has_game_started() {
+            return Err("The card distribution cannot be changed after the first extraction".to_string());
+        }
+        self.card_manager.lock()
+            .map_err(|_| "Failed to acquire card manager lock")?
+            .set_distribution(distribution)?;
+        log(LogLevel::Info, MODULE_NAME, &format!("Set card distribution to: {distribution:?}"));
+        self.record(GameEvent::CardDistributionSet { distribution });
+        Ok(())
+    }
+
+    /// Get how the cards of the game are dealt
+    #[must_use] pub fn card_distribution(&self) -> CardDistribution {
+        self.card_manager.lock().map(|manager| manager.distribution()).unwrap_or_default()
+    }
+
+    /// Run the game unattended: the server draws the numbers once the start conditions are met
+    pub fn set_autorun(&self, autorun: AutoRun) -> Result<(), String> {
+        if self.draw_mode() == DrawMode::Manual {
+            return Err("An unattended game cannot use the manual draw mode".to_string());
+        }
+        let mut current = self.autorun.lock()
+            .map_err(|e| format!("Failed to acquire autorun lock: {e}"))?;
+        *current = Some(autorun.clone());
+        drop(current);
+        log(LogLevel::Info, MODULE_NAME, &format!("Set unattended game settings to: {autorun:?}"));
+        self.record(GameEvent::AutoRunSet { autorun });
+        Ok(())
+    }
+
+    /// Get the settings of an unattended game
+    #[must_use] pub fn autorun(&self) -> Option<AutoRun> {
+        self.autorun.lock().ok().and_then(|autorun| autorun.clone())
+    }
+
+    /// Create a new game with the same settings, board owner included, to follow this one
+    pub fn follow_up(&self) -> Result<Self, String> {
+        let game = Self::with_rules(self.board_config, self.ruleset);
+        game.set_full_card_prizes(self.full_card_prizes())?;
+        game.set_prize_mode(self.prize_mode(), self.claim_penalty())?;
+        game.set_draw_mode(self.draw_mode())?;
+        game.set_card_distribution(self.card_distribution())?;
+        if let Some(autorun) = self.autorun() {
+            game.set_autorun(autorun)?;
+        }
+        if let Some(owner) = self.owner() {
+            game.set_owner(&owner)?;
+            game.add_client(owner.clone())?;
+            game.set_role(&owner, Role::Owner)?;
+            game.assign_cards(&owner, 1, Some(Role::Owner))?;
+        }
+        Ok(game)
+    }
+
     /// Perform a number extraction using the coordinated extraction logic
     /// This encapsulates the complex mutex coordination required for extraction
     pub fn extract_number(&self, current_working_score: Number, board_client_id: Option<&str>) -> Result<(Number, Number), String> {
-        perform_extraction(
+        self.extract_number_from(&PouchDraw, current_working_score, board_client_id)
+    }
+
+    /// Perform a number extraction taking the number from the given source
+    pub fn extract_number_from(&self, source: &dyn ExtractionSource, current_working_score: Number, board_client_id: Option<&str>) -> Result<(Number, Number), String> {
+        // The journal stays locked from the draw to its events, so that concurrent extractions are journaled in their order
+        let mut journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        let extraction = perform_extraction(
+            source,
             &self.pouch,
             &self.board,
             &self.scorecard,
             &self.card_manager,
             current_working_score,
             board_client_id,
-        )
+        )?;
+
+        let mut events = vec![GameEvent::NumberExtracted {
+            number: extraction.number,
+            draw_index: extraction.draw_index,
+            current_working_score,
+            board_client_id: board_client_id.map(str::to_string),
+        }];
+        events.extend(extraction.awarded.into_iter().map(|(score, achievement)| GameEvent::PrizeAwarded { score, achievement }));
+        if extraction.closed {
+            events.push(GameEvent::GameClosed { draw_index: extraction.draw_index });
+        }
+        self.record_in(&mut journal, events);
+        Ok((extraction.number, extraction.working_score))
+    }
+
+    /// Undo the last extractions, returning the numbers to the pouch and rolling back the prizes they earned
+    /// Returns the undone numbers, most recent first
+    pub fn undo_extractions(&self, count: usize) -> Result<Vec<Number>, String> {
+        // Locked like the extraction, so that the board length read below is the one left by this undo
+        let mut journal = self.journal.lock()
+            .map_err(|_| "Failed to lock journal")?;
+        // Once the game has ended the seed is revealed, so the extractions are final
+        if self.is_game_ended() {
+            return Err("Cannot undo extractions: the game is over and its seed is revealed".to_string());
+        }
+        let undone = perform_undo(&self.pouch, &self.board, &self.scorecard, count)?;
+        log(LogLevel::Warning, MODULE_NAME, &format!("Undid extraction of {undone:?} in game {}", self.id()));
+        let draw_index = self.board_length();
+        self.record_in(&mut journal, vec![GameEvent::ExtractionsUndone { count, numbers: undone.clone(), draw_index }]);
+        Ok(undone)
     }
 
     /// Check if the game has started (any numbers extracted)
@@ -687,7 +1408,11 @@ impl Game {
 
     /// Check if BINGO has been reached (game over condition)
     #[must_use] pub fn is_bingo_reached(&self) -> bool {
-        self.published_score() >= 15
+        if let Ok(scorecard) = self.scorecard.lock() {
+            scorecard.is_game_over()
+        } else {
+            false
+        }
     }
 
     /// Get the number of remaining numbers in the pouch
@@ -709,10 +1434,30 @@ impl Game {
         self.is_bingo_reached() || self.is_pouch_empty()
     }
 
-    /// Get the number of registered players
+    /// Get the number of registered players (excludes the spectators)
     #[must_use] pub fn player_count(&self) -> usize {
+        let spectators = self.spectator_count();
         if let Ok(clients) = self.registered_clients.lock() {
-            clients.len()
+            clients.len().saturating_sub(spectators)
+        } else {
+            0
+        }
+    }
+
+    /// Get the number of spectators following this game
+    #[must_use] pub fn spectator_count(&self) -> usize {
+        self.get_clients_with_role(Role::Spectator).map_or(0, |spectators| spectators.len())
+    }
+
+    /// Get the number of clients holding cards in this game (excludes the board)
+    #[must_use] pub fn players_with_cards(&self) -> usize {
+        if let Ok(manager) = self.card_manager.lock() {
+            manager.get_all_assignments()
+                .iter()
+                .filter(|(card_id, _)| **card_id != BOARD_ID)
+                .map(|(_, assignment)| assignment.client_id.as_str())
+                .collect::<HashSet<_>>()
+                .len()
         } else {
             0
         }
@@ -771,8 +1516,12 @@ impl Game {
     /// Dump the complete game state to a JSON file in data/games directory
     /// This function is called when the game ends (BINGO reached)
     pub fn dump_to_json(&self) -> Result<String, String> {
+        self.dump_to_dir(std::path::Path::new(DUMP_DIR))
+    }
+
+    /// Dump the complete game state to a JSON file in the given directory
+    pub fn dump_to_dir(&self, dir: &std::path::Path) -> Result<String, String> {
         use std::fs;
-        use std::path::Path;
 
         // Create the serializable game state
         let game_state = match self.create_serializable_state() {
@@ -782,7 +1531,7 @@ impl Game {
 
         // Create the filename with game ID
         let filename = format!("{}.json", self.id(),);
-        let filepath = Path::new("data/games").join(&filename);
+        let filepath = dir.join(&filename);
 
         // Ensure the directory exists
         if let Some(parent) = filepath.parent() {
@@ -814,7 +1563,7 @@ impl Game {
     }
 
     /// Create a serializable version of the game state
-    fn create_serializable_state(&self) -> Result<SerializableGameState, String> {
+    pub fn create_serializable_state(&self) -> Result<SerializableGameState, String> {
         let board = {
             let guard = self.board.lock()
                 .map_err(|_| "Failed to lock board")?;
@@ -849,14 +1598,56 @@ impl Game {
             id: self.id(),
             created_at: self.created_at(),
             owner: self.owner(),  // Include the game owner
+            board_config: self.board_config,
+            ruleset: self.ruleset,
+            draw_commitment: self.draw_commitment.clone(),
+            draw_seed: pouch.seed().map(DrawSeed::to_hex),
+            draw_put_back: pouch.put_back_order().to_vec(),
+            draw_mode: self.draw_mode(),
+            autorun: self.autorun(),
             board,
             pouch,
             scorecard,
             registered_clients,
             card_manager,
+            role_registry: self.role_registry.get_all_roles()?,
+            version: self.version(),
             game_ended_at: SystemTime::now(),
         })
     }
+
+    /// Rebuild a game from its serialized state, resuming the draw where it stopped
+    pub fn from_serializable_state(state: SerializableGameState) -> Result<Self, String> {
+        let seed = state.draw_seed.as_deref().map(DrawSeed::from_hex).transpose()?;
+        let pouch = Pouch::restore(&state.board_config, seed, state.pouch.numbers, state.draw_put_back);
+
+        let role_registry = GameRoleRegistry::new();
+        for client_role in &state.role_registry {
+            role_registry.set_role(&client_role.client_id, client_role.role)?;
+        }
+
+        Ok(Self {
+            id: Arc::new(Mutex::new(state.id)),
+            created_at: Arc::new(Mutex::new(state.created_at)),
+            owner: Arc::new(Mutex::new(state.owner)),
+            board: Arc::new(Mutex::new(state.board)),
+            pouch: Arc::new(Mutex::new(pouch)),
+            scorecard: Arc::new(Mutex::new(state.scorecard)),
+            registered_clients: Arc::new(Mutex::new(state.registered_clients)),
+            card_manager: Arc::new(Mutex::new(state.card_manager)),
+            role_registry,
+            board_config: state.board_config,
+            ruleset: state.ruleset,
+            draw_commitment: state.draw_commitment,
+            draw_mode: Arc::new(Mutex::new(state.draw_mode)),
+            // Games restored from a snapshot have no journal to continue
+            journal: Arc::new(Mutex::new(Journal::new())),
+            updates: broadcast::channel(UPDATES_CAPACITY).0,
+            // Versions keep increasing across the restore, so that clients waiting on an older one see the change
+            version: Arc::new(watch::channel(state.version).0),
+            autorun: Arc::new(Mutex::new(state.autorun)),
+        })
+    }
 }
 
 /// Serializable version of the Game struct for JSON dumping
@@ -865,14 +1656,46 @@ pub struct SerializableGameState {
     pub id: String,
     pub created_at: SystemTime,
     pub owner: Option<String>,  // ClientID of the game creator
+    #[serde(default)]
+    pub board_config: BoardStruct,
+    #[serde(default)]
+    pub ruleset: RulesetKind,
+    #[serde(default)]
+    pub draw_commitment: String,
+    #[serde(default)]
+    pub draw_seed: Option<String>,
+    /// Numbers put back in the pouch by undos, drawn again before the rest of the seeded order, next number last
+    #[serde(default)]
+    pub draw_put_back: Vec<Number>,
+    #[serde(default)]
+    pub draw_mode: DrawMode,
+    #[serde(default)]
+    pub autorun: Option<AutoRun>,
     pub board: Board,
     pub pouch: Pouch,
     pub scorecard: ScoreCard,
     pub registered_clients: HashSet<String>,
     pub card_manager: CardAssignmentManager,
+    #[serde(default, alias = "client_type_registry")]
+    pub role_registry: Vec<GameClientRole>,
+    #[serde(default)]
+    pub version: usize,
     pub game_ended_at: SystemTime,
 }
 
+impl SerializableGameState {
+    /// Status of the serialized game, as `Game::status` reports it
+    #[must_use] pub fn status(&self) -> GameStatus {
+        if self.scorecard.is_game_over() {
+            GameStatus::Closed
+        } else if !self.board.is_empty() {
+            GameStatus::Active
+        } else {
+            GameStatus::New
+        }
+    }
+}
+
 impl Default for Game {
     fn default() -> Self {
         Self::new()
@@ -882,6 +1705,7 @@ impl Default for Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extraction::ManualDraw;
 
     #[test]
     fn test_game_creation() {
@@ -912,6 +1736,625 @@ mod tests {
         assert!(time_string.len() > 10); // Should be a reasonable length
     }
 
+    #[test]
+    fn test_game_with_custom_board_config() {
+        let board_config = BoardStruct {
+            cols_per_card: 4,
+            ..BOARDCONFIG
+        };
+        let game = Game::with_config(board_config);
+
+        assert_eq!(game.board_config(), &board_config);
+        assert_eq!(game.pouch_length(), 72);
+        assert_eq!(game.pouch().lock().unwrap().numbers.last(), Some(&72));
+        assert_eq!(game.scorecard().lock().unwrap().final_score(), 12);
+
+        // Completing the first board card (1-4, 9-12 and 17-20) closes the game
+        for number in [1, 2, 3, 4, 9, 10, 11, 12, 17, 18, 19, 20] {
+            let mut board = game.board().lock().unwrap();
+            let mut scorecard = game.scorecard().lock().unwrap();
+            let manager = game.card_manager().lock().unwrap();
+            board.push(number, &scorecard);
+            let working_score = scorecard.published_score;
+            scorecard.calculate_and_update_best_score(&board, &manager, working_score, Some(BOARD_ID));
+        }
+        assert_eq!(game.published_score(), 12);
+        assert!(game.is_bingo_reached());
+    }
+
+    #[test]
+    fn test_game_ruleset_awards_player_prizes() {
+        let game = Game::new();
+        assert_eq!(game.ruleset(), RulesetKind::Tombola);
+
+        let card_id = {
+            let mut manager = game.card_manager().lock().unwrap();
+            let (_, card_ids) = manager.assign_cards("player", 1).unwrap();
+            card_ids[0].clone()
+        };
+        let first_row: Vec<Number> = {
+            let manager = game.card_manager().lock().unwrap();
+            manager.get_card_assignment(&card_id).unwrap().card_data[0].iter().flatten().copied().collect()
+        };
+
+        // Three numbers on the same row win both AMBO and TERNO at once
+        {
+            let mut board = game.board().lock().unwrap();
+            let mut scorecard = game.scorecard().lock().unwrap();
+            let manager = game.card_manager().lock().unwrap();
+            for &number in &first_row[..3] {
+                board.push(number, &scorecard);
+            }
+            scorecard.calculate_and_update_best_score(&board, &manager, 0, None);
+        }
+
+        let scorecard = game.scorecard().lock().unwrap();
+        assert_eq!(scorecard.published_score, 3);
+        assert_eq!(scorecard.score_map[&2][0].card_id, card_id);
+        assert_eq!(scorecard.score_map[&2][0].numbers, first_row[..2].to_vec());
+        assert_eq!(scorecard.score_map[&3][0].numbers, first_row[..3].to_vec());
+        assert_eq!(scorecard.prize_name(2), "AMBO");
+        assert_eq!(scorecard.prize_name(3), "TERNO");
+        assert!(!scorecard.is_game_over());
+    }
+
+    #[test]
+    fn test_game_with_second_full_card_prize() {
+        let game = Game::new();
+        game.set_full_card_prizes(2).unwrap();
+        assert!(game.set_full_card_prizes(0).is_err());
+        assert_eq!(game.full_card_prizes(), 2);
+
+        let cards: Vec<(String, Vec<Number>)> = {
+            let mut manager = game.card_manager().lock().unwrap();
+            let (first, _) = manager.assign_cards("player1", 1).unwrap();
+            let (second, _) = manager.assign_cards("player2", 1).unwrap();
+            [first, second].into_iter()
+                .map(|infos| (infos[0].card_id.clone(), infos[0].card_data.iter().flatten().flatten().copied().collect()))
+                .collect()
+        };
+
+        let extract_all = |numbers: &[Number]| {
+            let mut board = game.board().lock().unwrap();
+            let mut scorecard = game.scorecard().lock().unwrap();
+            let manager = game.card_manager().lock().unwrap();
+            for &number in numbers {
+                if !board.get_numbers().contains(&number) {
+                    board.push(number, &scorecard);
+                }
+            }
+            scorecard.calculate_and_update_best_score(&board, &manager, 0, None);
+        };
+
+        // The first full card wins TOMBOLA but the game goes on
+        extract_all(&cards[0].1);
+        assert_eq!(game.published_score(), 15);
+        assert!(!game.is_bingo_reached());
+
+        // The next full card wins TOMBOLINO and closes the game
+        extract_all(&cards[1].1);
+        assert_eq!(game.published_score(), 16);
+        assert!(game.is_bingo_reached());
+
+        let scorecard = game.scorecard().lock().unwrap();
+        assert_eq!(scorecard.score_map[&15].len(), 1);
+        assert_eq!(scorecard.score_map[&15][0].card_id, cards[0].0);
+        assert_eq!(scorecard.score_map[&16].len(), 1);
+        assert_eq!(scorecard.score_map[&16][0].card_id, cards[1].0);
+        assert_eq!(scorecard.prize_name(16), "TOMBOLINO");
+        assert!(scorecard.is_full_card_score(16));
+    }
+
+    #[test]
+    fn test_game_claim_mode() {
+        let game = Game::new();
+        assert_eq!(game.prize_mode(), PrizeMode::Automatic);
+        game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::Suspend(1)).unwrap();
+
+        let (card_id, numbers) = {
+            let mut manager = game.card_manager().lock().unwrap();
+            let (infos, _) = manager.assign_cards("player", 1).unwrap();
+            (infos[0].card_id.clone(), infos[0].card_data.clone())
+        };
+        let first_row: Vec<Number> = numbers[0].iter().flatten().copied().collect();
+        let mut missing = (1..=90).filter(|number| !numbers.iter().flatten().flatten().any(|n| n == number));
+        let push = |number: Number| {
+            let mut board = game.board().lock().unwrap();
+            let mut scorecard = game.scorecard().lock().unwrap();
+            let manager = game.card_manager().lock().unwrap();
+            board.push(number, &scorecard);
+            scorecard.calculate_and_update_best_score(&board, &manager, 0, None);
+        };
+
+        // No prize is awarded automatically in claim mode
+        push(first_row[0]);
+        push(first_row[1]);
+        assert!(game.scorecard().lock().unwrap().score_map.is_empty());
+
+        // A false claim suspends the card for one extraction
+        assert_eq!(game.claim_prize("player", &card_id, 3), Ok(ClaimVerdict::False));
+        assert!(game.claim_prize("player", &card_id, 2).is_err());
+        push(missing.next().unwrap());
+        assert!(game.claim_prize("player", &card_id, 2).is_err());
+
+        // AMBO was reached two extractions ago, the claim is late
+        push(missing.next().unwrap());
+        assert_eq!(game.claim_prize("player", &card_id, 2), Ok(ClaimVerdict::Late));
+
+        // TERNO claimed at the extraction that completed it
+        push(first_row[2]);
+        assert_eq!(game.claim_prize("player", &card_id, 3), Ok(ClaimVerdict::Accepted(3)));
+        assert_eq!(game.claim_prize("player", &card_id, 3), Ok(ClaimVerdict::Unavailable));
+        assert!(game.claim_prize("someone_else", &card_id, 4).is_err());
+
+        let scorecard = game.scorecard().lock().unwrap();
+        assert_eq!(scorecard.published_score, 3);
+        assert_eq!(scorecard.score_map[&3][0].card_id, card_id);
+        assert_eq!(scorecard.score_map[&3][0].draw_index, 5);
+        assert_eq!(scorecard.score_map[&3][0].numbers, first_row[..3].to_vec());
+    }
+
+    #[test]
+    fn test_seeded_games_are_reproducible() {
+        let seed = DrawSeed::from_bytes([1; 32]);
+        let first = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, seed);
+        let second = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, seed);
+        assert_eq!(first.draw_commitment(), seed.commitment());
+
+        for _ in 0..10 {
+            assert_eq!(first.extract_number(0, None).unwrap().0, second.extract_number(0, None).unwrap().0);
+        }
+        assert!(first.revealed_seed().is_none());
+        assert!(first.verify_draw().is_err());
+
+        while !first.is_game_ended() {
+            first.extract_number(0, None).unwrap();
+        }
+        assert_eq!(first.revealed_seed(), Some(seed.to_hex()));
+        let (revealed, verification) = first.verify_draw().unwrap();
+        assert_eq!(revealed, seed.to_hex());
+        assert!(verification.verified);
+        assert_eq!(verification.replayed.len(), first.board_length());
+    }
+
+    #[test]
+    fn test_game_undo_extractions() {
+        let game = Game::new();
+        let numbers = {
+            let mut manager = game.card_manager().lock().unwrap();
+            let (infos, _) = manager.assign_cards("player", 1).unwrap();
+            infos[0].card_data.clone()
+        };
+        let first_row: Vec<Number> = numbers[0].iter().flatten().copied().collect();
+        let missing = (1..=90).find(|number| !numbers.iter().flatten().flatten().any(|n| n == number)).unwrap();
+
+        game.extract_number_from(&ManualDraw(first_row[0]), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(first_row[1]), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(missing), 0, None).unwrap();
+        assert_eq!(game.published_score(), 2);
+
+        // Undoing the extraction after AMBO keeps the prize
+        assert_eq!(game.undo_extractions(1), Ok(vec![missing]));
+        assert_eq!(game.published_score(), 2);
+
+        // Undoing the extraction that completed AMBO drops it
+        assert_eq!(game.undo_extractions(1), Ok(vec![first_row[1]]));
+        assert_eq!(game.published_score(), 0);
+        assert!(game.scorecard().lock().unwrap().score_map.is_empty());
+        assert_eq!(game.board().lock().unwrap().get_numbers(), &vec![first_row[0]]);
+        assert_eq!(game.pouch_length(), 89);
+        assert!(game.pouch().lock().unwrap().numbers.contains(&missing));
+
+        assert!(game.undo_extractions(2).is_err());
+        assert_eq!(game.undo_extractions(1), Ok(vec![first_row[0]]));
+        assert!(!game.has_game_started());
+
+        // Once the game is over the seed is revealed and the extractions are final
+        for number in numbers.iter().flatten().flatten() {
+            game.extract_number_from(&ManualDraw(*number), 0, None).unwrap();
+        }
+        assert!(game.is_game_ended());
+        assert!(game.undo_extractions(1).is_err());
+        assert_eq!(game.board_length(), 15);
+        assert!(game.is_bingo_reached());
+    }
+
+    #[test]
+    fn test_game_restore_draws_undone_numbers_first() {
+        let seed = DrawSeed::from_bytes([8; 32]);
+        let sequence = seed.draw_sequence(&BOARDCONFIG);
+        let game = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, seed);
+        game.extract_number_from(&ManualDraw(sequence[50]), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(sequence[30]), 0, None).unwrap();
+        game.undo_extractions(2).unwrap();
+
+        // The put-back order survives a snapshot, so the draw continues as it would have
+        let json = serde_json::to_string(&game.create_serializable_state().unwrap()).unwrap();
+        let restored = Game::from_serializable_state(serde_json::from_str(&json).unwrap()).unwrap();
+        for _ in 0..5 {
+            assert_eq!(restored.extract_number(0, None).unwrap().0, game.extract_number(0, None).unwrap().0);
+        }
+        assert_eq!(restored.board().lock().unwrap().get_numbers()[..3], [sequence[50], sequence[30], sequence[0]]);
+    }
+
+    #[test]
+    fn test_game_undo_rolls_back_board_score_and_penalties() {
+        // Without a board owner the board advances the score without recording an achievement
+        let game = Game::new();
+        for number in [1, 2, 50] {
+            game.extract_number_from(&ManualDraw(number), 0, None).unwrap();
+        }
+        assert_eq!(game.published_score(), 2);
+        game.undo_extractions(1).unwrap();
+        assert_eq!(game.published_score(), 2);
+        game.undo_extractions(1).unwrap();
+        assert_eq!(game.published_score(), 0);
+
+        // An undone false claim lifts its penalty
+        let game = Game::new();
+        game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::Suspend(0)).unwrap();
+        let (card_id, numbers) = {
+            let mut manager = game.card_manager().lock().unwrap();
+            let (infos, _) = manager.assign_cards("player", 1).unwrap();
+            (infos[0].card_id.clone(), infos[0].card_data.clone())
+        };
+        let mut missing = (1..=90).filter(|number| !numbers.iter().flatten().flatten().any(|n| n == number));
+        game.extract_number_from(&ManualDraw(missing.next().unwrap()), 0, None).unwrap();
+        assert_eq!(game.claim_prize("player", &card_id, 2), Ok(ClaimVerdict::False));
+        game.extract_number_from(&ManualDraw(missing.next().unwrap()), 0, None).unwrap();
+        assert_eq!(game.claim_prize("player", &card_id, 2), Ok(ClaimVerdict::False));
+        assert_eq!(game.scorecard().lock().unwrap().suspended_cards[&card_id], 3);
+
+        // The penalty of the earlier claim is back, then none is left
+        game.undo_extractions(1).unwrap();
+        assert_eq!(game.scorecard().lock().unwrap().suspended_cards[&card_id], 2);
+        game.undo_extractions(1).unwrap();
+        assert!(game.scorecard().lock().unwrap().suspended_cards.is_empty());
+        game.extract_number_from(&ManualDraw(missing.next().unwrap()), 0, None).unwrap();
+        assert_eq!(game.claim_prize("player", &card_id, 2), Ok(ClaimVerdict::False));
+    }
+
+    #[test]
+    fn test_game_replay_from_journal() {
+        let game = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, DrawSeed::from_bytes([3; 32]));
+        game.set_owner("owner").unwrap();
+        game.add_client("owner".to_string()).unwrap();
+        game.set_role("owner", Role::Owner).unwrap();
+        game.assign_cards("owner", 1, Some(Role::Owner)).unwrap();
+        game.add_client("player".to_string()).unwrap();
+        game.set_role("player", Role::Player).unwrap();
+        game.assign_cards("player", 6, Some(Role::Player)).unwrap();
+        game.set_draw_mode(DrawMode::Random).unwrap();
+        game.extract_number(0, Some("owner")).unwrap();
+        game.undo_extractions(1).unwrap();
+        while !game.is_bingo_reached() {
+            game.extract_number(0, Some("owner")).unwrap();
+        }
+
+        let journal = game.journal_since(0).unwrap();
+        assert!(matches!(journal[0].event, GameEvent::GameCreated { .. }));
+        assert!(journal.iter().any(|entry| matches!(entry.event, GameEvent::PrizeAwarded { .. })));
+        assert!(matches!(journal.last().unwrap().event, GameEvent::GameClosed { .. }));
+
+        let replayed = Game::replay(&journal).unwrap();
+        assert_eq!(replayed.id(), game.id());
+        assert_eq!(replayed.created_at(), game.created_at());
+        assert_eq!(replayed.owner(), game.owner());
+        assert_eq!(replayed.draw_commitment(), game.draw_commitment());
+        assert_eq!(replayed.board().lock().unwrap().get_numbers(), game.board().lock().unwrap().get_numbers());
+        assert_eq!(replayed.pouch().lock().unwrap().numbers, game.pouch().lock().unwrap().numbers);
+        assert_eq!(replayed.scorecard().lock().unwrap().score_map, game.scorecard().lock().unwrap().score_map);
+        assert_eq!(replayed.published_score(), game.published_score());
+        assert_eq!(*replayed.registered_clients().lock().unwrap(), *game.registered_clients().lock().unwrap());
+        assert_eq!(replayed.get_role("owner").unwrap(), Some(Role::Owner));
+        let replayed_cards = replayed.card_manager().lock().unwrap().get_client_cards("player").cloned();
+        assert_eq!(replayed_cards, game.card_manager().lock().unwrap().get_client_cards("player").cloned());
+        assert_eq!(replayed.journal_len(), journal.len());
+        assert_eq!(replayed.revealed_seed(), game.revealed_seed());
+
+        assert!(Game::replay(&journal[1..]).is_err());
+    }
+
+    #[test]
+    fn test_game_replay_spectators() {
+        let game = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, DrawSeed::from_bytes([4; 32]));
+        game.set_owner("owner").unwrap();
+        game.add_client("owner".to_string()).unwrap();
+        game.set_role("owner", Role::Owner).unwrap();
+        game.assign_cards("owner", 1, Some(Role::Owner)).unwrap();
+        game.add_client("player".to_string()).unwrap();
+        game.set_role("player", Role::Player).unwrap();
+        game.assign_cards("player", 2, Some(Role::Player)).unwrap();
+        game.extract_number(0, Some("owner")).unwrap();
+
+        // Spectators join after the first extraction, without being counted as players
+        assert!(game.add_client("late".to_string()).is_err());
+        assert!(game.add_spectator("spectator".to_string()).unwrap());
+        game.set_role("spectator", Role::Spectator).unwrap();
+        assert_eq!((game.player_count(), game.spectator_count()), (2, 1));
+        assert_eq!(game.snapshot().unwrap().spectators, vec!["spectator".to_string()]);
+        assert!(game.assign_cards("spectator", 1, None).is_err());
+
+        let journal = game.journal_since(0).unwrap();
+        assert!(matches!(journal.last().unwrap().event, GameEvent::RoleSet { role: Role::Spectator, .. }));
+
+        let replayed = Game::replay(&journal).unwrap();
+        assert_eq!((replayed.player_count(), replayed.spectator_count()), (2, 1));
+        assert_eq!(replayed.snapshot().unwrap().spectators, vec!["spectator".to_string()]);
+        assert_eq!(replayed.get_role("spectator").unwrap(), Some(Role::Spectator));
+        assert!(replayed.card_manager().lock().unwrap().get_client_cards("spectator").is_none());
+    }
+
+    #[test]
+    fn test_game_replay_inventory() {
+        let game = Game::with_seed(BOARDCONFIG, RulesetKind::Tombola, DrawSeed::from_bytes([5; 32]));
+        game.set_owner("owner").unwrap();
+        game.add_client("owner".to_string()).unwrap();
+        game.set_role("owner", Role::Owner).unwrap();
+        game.assign_cards("owner", 1, Some(Role::Owner)).unwrap();
+
+        // Pre-printed cards wait in the inventory until the owner assigns them
+        let printed = crate::card::CardManagement::new().generate_card_group().unwrap();
+        let serials = game.import_cards(printed[..3].to_vec()).unwrap();
+        game.add_client("paper".to_string()).unwrap();
+        game.set_role("paper", Role::Player).unwrap();
+        assert_eq!(game.assign_inventory_cards("paper", &serials[..2]).unwrap().len(), 2);
+
+        // The inventory is closed by the first extraction
+        game.extract_number(0, Some("owner")).unwrap();
+        assert!(game.import_cards(printed[3..].to_vec()).is_err());
+        assert!(game.assign_inventory_cards("paper", &serials[2..]).is_err());
+
+        let replayed = Game::replay(&game.journal_since(0).unwrap()).unwrap();
+        let card_manager = replayed.card_manager().lock().unwrap();
+        assert_eq!(card_manager.get_client_cards("paper"), Some(&serials[..2].to_vec()));
+        assert_eq!(card_manager.inventory_card(&serials[2]), Some(&printed[2]));
+        for (serial, card) in serials.iter().zip(&printed).take(2) {
+            assert_eq!(card_manager.get_card_assignment(serial).map(|assignment| &assignment.card_data), Some(card));
+        }
+    }
+
+    #[test]
+    fn test_game_replay_series_pool() {
+        let game = Game::with_rules(BOARDCONFIG, RulesetKind::Tombola);
+        game.set_card_distribution(CardDistribution::Series).unwrap();
+        game.assign_cards("alice", 4, None).unwrap();
+        game.assign_cards("bob", 4, None).unwrap();
+        let journal = game.journal_since(0).unwrap();
+        assert_eq!(journal.iter().filter(|entry| matches!(entry.event, GameEvent::SeriesOpened { .. })).count(), 2);
+        assert_eq!(game.card_manager().lock().unwrap().series_pool().len(), 4);
+
+        // The undealt cards of the series survive the replay, and are dealt next as they would have been
+        let replayed = Game::replay(&journal).unwrap();
+        let series_pool = game.card_manager().lock().unwrap().series_pool().to_vec();
+        assert_eq!(replayed.card_manager().lock().unwrap().series_pool(), series_pool.as_slice());
+        let (dealt, _) = replayed.assign_cards("carol", 2, None).unwrap();
+        assert_eq!(dealt.into_iter().map(|card| card.card_data).collect::<Vec<Card>>(), series_pool[..2].to_vec());
+    }
+
+    #[test]
+    fn test_game_autorun_follow_up() {
+        let autorun = AutoRun { min_players: 2, min_cards: 3, start_time: None, draw_interval: 5, repeat: true };
+        let game = Game::with_rules(BOARDCONFIG, RulesetKind::Tombola);
+        game.set_full_card_prizes(2).unwrap();
+        game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::Disqualify).unwrap();
+        game.set_autorun(autorun.clone()).unwrap();
+        game.set_card_distribution(CardDistribution::Series).unwrap();
+        game.set_owner("owner").unwrap();
+        game.add_client("owner".to_string()).unwrap();
+        game.set_role("owner", Role::Owner).unwrap();
+        game.assign_cards("owner", 1, Some(Role::Owner)).unwrap();
+        game.assign_cards("player1", 2, None).unwrap();
+        game.assign_cards("player2", 1, None).unwrap();
+        assert_eq!(game.players_with_cards(), 2);
+        assert_eq!(game.card_count(), 3);
+
+        let replayed = Game::replay(&game.journal_since(0).unwrap()).unwrap();
+        assert_eq!(replayed.autorun(), Some(autorun.clone()));
+        assert_eq!(replayed.card_distribution(), CardDistribution::Series);
+        let restored = Game::from_serializable_state(game.create_serializable_state().unwrap()).unwrap();
+        assert_eq!(restored.autorun(), Some(autorun.clone()));
+
+        // The follow-up game keeps the settings and the board owner, but not the players
+        let next = game.follow_up().unwrap();
+        assert_ne!(next.id(), game.id());
+        assert_eq!(next.autorun(), Some(autorun));
+        assert_eq!(next.full_card_prizes(), 2);
+        assert_eq!(next.prize_mode(), PrizeMode::Claim);
+        assert_eq!(next.claim_penalty(), ClaimPenalty::Disqualify);
+        assert_eq!(next.card_distribution(), CardDistribution::Series);
+        assert_eq!(next.owner(), Some("owner".to_string()));
+        assert_eq!(next.get_role("owner").unwrap(), Some(Role::Owner));
+        assert_eq!(next.card_manager().lock().unwrap().get_card_assignment(BOARD_ID).map(|card| card.client_id.clone()), Some("owner".to_string()));
+        assert_eq!(next.players_with_cards(), 0);
+
+        // The server cannot draw from a physical basket
+        let manual = Game::new();
+        manual.set_draw_mode(DrawMode::Manual).unwrap();
+        assert!(manual.set_autorun(AutoRun { min_players: 0, min_cards: 0, start_time: None, draw_interval: 1, repeat: false }).is_err());
+        assert_eq!(manual.autorun(), None);
+    }
+
+    #[test]
+    fn test_game_replay_claims() {
+        let game = Game::new();
+        game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::Disqualify).unwrap();
+        let card = game.assign_cards("player", 1, None).unwrap().0.remove(0);
+        let first_row: Vec<Number> = card.card_data[0].iter().flatten().copied().collect();
+        game.extract_number_from(&ManualDraw(first_row[0]), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(first_row[1]), 0, None).unwrap();
+        assert_eq!(game.claim_prize("player", &card.card_id, 2), Ok(ClaimVerdict::Accepted(2)));
+        assert_eq!(game.claim_prize("player", &card.card_id, 3), Ok(ClaimVerdict::False));
+
+        let replayed = Game::replay(&game.journal_since(0).unwrap()).unwrap();
+        assert_eq!(replayed.scorecard().lock().unwrap().score_map, game.scorecard().lock().unwrap().score_map);
+        // The disqualification of the false claim is replayed too
+        assert!(replayed.claim_prize("player", &card.card_id, 2).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_claim_and_extraction_replay() {
+        let verdicts = |game: &Game| -> Vec<ClaimVerdict> {
+            game.journal_since(0).unwrap().into_iter()
+                .filter_map(|entry| match entry.event {
+                    GameEvent::PrizeClaimed { verdict, .. } => Some(verdict),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        for _ in 0..20 {
+            let game = Game::new();
+            game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::Disqualify).unwrap();
+            let card = game.assign_cards("player", 1, None).unwrap().0.remove(0);
+            let first_row: Vec<Number> = card.card_data[0].iter().flatten().copied().collect();
+            game.extract_number_from(&ManualDraw(first_row[0]), 0, None).unwrap();
+            game.extract_number_from(&ManualDraw(first_row[1]), 0, None).unwrap();
+
+            // The claim is judged either before or after the extraction, and journaled in that order
+            let claim = {
+                let game = game.clone();
+                let card_id = card.card_id.clone();
+                std::thread::spawn(move || game.claim_prize("player", &card_id, 2).unwrap())
+            };
+            let extraction = {
+                let game = game.clone();
+                std::thread::spawn(move || game.extract_number(0, None).unwrap())
+            };
+            let verdict = claim.join().unwrap();
+            extraction.join().unwrap();
+
+            let replayed = Game::replay(&game.journal_since(0).unwrap()).unwrap();
+            assert_eq!(verdicts(&replayed), vec![verdict]);
+            assert_eq!(replayed.scorecard().lock().unwrap().score_map, game.scorecard().lock().unwrap().score_map);
+        }
+    }
+
+    #[test]
+    fn test_concurrent_extractions_are_journaled_in_order() {
+        let game = Game::new();
+        game.assign_cards("player", 6, None).unwrap();
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let game = game.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        game.extract_number(0, None).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let journal = game.journal_since(0).unwrap();
+        let draw_indexes: Vec<usize> = journal.iter()
+            .filter_map(|entry| match entry.event {
+                GameEvent::NumberExtracted { draw_index, .. } => Some(draw_index),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(draw_indexes, (1..=40).collect::<Vec<usize>>());
+
+        // Every prize follows the extraction that awarded it
+        let mut last_draw_index = 0;
+        for entry in &journal {
+            match &entry.event {
+                GameEvent::NumberExtracted { draw_index, .. } => last_draw_index = *draw_index,
+                GameEvent::PrizeAwarded { achievement, .. } => assert_eq!(achievement.draw_index, last_draw_index),
+                _ => {}
+            }
+        }
+
+        let replayed = Game::replay(&journal).unwrap();
+        assert_eq!(replayed.board().lock().unwrap().get_numbers(), game.board().lock().unwrap().get_numbers());
+    }
+
+    #[test]
+    fn test_game_live_updates() {
+        let game = Game::new();
+        let card = game.assign_cards("player", 1, None).unwrap().0.remove(0);
+        let first_row: Vec<Number> = card.card_data[0].iter().flatten().copied().collect();
+        let mut updates = game.subscribe();
+
+        game.extract_number_from(&ManualDraw(first_row[0]), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(first_row[1]), 0, None).unwrap();
+        game.undo_extractions(1).unwrap();
+        game.add_spectator("spectator".to_string()).unwrap();
+
+        assert_eq!(updates.try_recv().unwrap(), GameUpdate::Extraction { number: first_row[0], draw_index: 1 });
+        assert_eq!(updates.try_recv().unwrap(), GameUpdate::Extraction { number: first_row[1], draw_index: 2 });
+        match updates.try_recv().unwrap() {
+            GameUpdate::Prize { score, client_id, card_id, .. } => {
+                assert_eq!((score, client_id, card_id), (2, "player".to_string(), card.card_id.clone()));
+            }
+            update => panic!("Expected a prize, got {update:?}"),
+        }
+        assert_eq!(updates.try_recv().unwrap(), GameUpdate::Undo { numbers: vec![first_row[1]], draw_index: 1 });
+        assert_eq!(updates.try_recv().unwrap(), GameUpdate::Join { client_id: "spectator".to_string() });
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_game_updates_since() {
+        let game = Game::new();
+        let card = game.assign_cards("player", 1, None).unwrap().0.remove(0);
+        let first_row: Vec<Number> = card.card_data[0].iter().flatten().copied().collect();
+        for &number in &first_row[..3] {
+            game.extract_number_from(&ManualDraw(number), 0, None).unwrap();
+        }
+
+        let (updates, reached) = game.updates_since(1).unwrap();
+        assert_eq!(reached, 3);
+        let kinds: Vec<(&str, Option<usize>)> = updates.iter().map(|update| (update.kind(), update.draw_index())).collect();
+        assert_eq!(kinds, vec![
+            ("extraction", Some(2)),
+            ("prize", Some(2)),
+            ("extraction", Some(3)),
+            ("prize", Some(3)),
+        ]);
+
+        // The prizes of the last draw received are sent again, they share the ID of its extraction
+        let (updates, _) = game.updates_since(3).unwrap();
+        let kinds: Vec<(&str, Option<usize>)> = updates.iter().map(|update| (update.kind(), update.draw_index())).collect();
+        assert_eq!(kinds, vec![("prize", Some(3))]);
+
+        // A client ahead of the board (after an undo) must reload the game
+        game.undo_extractions(2).unwrap();
+        assert_eq!(game.updates_since(3).unwrap().0, vec![GameUpdate::Resync]);
+    }
+
+    #[tokio::test]
+    async fn test_game_snapshot_version() {
+        let game = Game::new();
+        game.add_client("player".to_string()).unwrap();
+        game.set_role("player", Role::Player).unwrap();
+        game.assign_cards("player", 3, None).unwrap();
+
+        let before = game.snapshot().unwrap();
+        assert_eq!(before.version, game.journal_len());
+        assert_eq!(before.status, "new");
+        assert_eq!(before.pouch_size, 90);
+        assert_eq!(before.players, vec![PlayerSummary { client_id: "player".to_string(), role: Role::Player, card_count: 3 }]);
+
+        let number = game.extract_number(0, None).unwrap().0;
+        let after = game.snapshot().unwrap();
+        assert!(after.version > before.version);
+        assert_eq!(after.status, "active");
+        assert_eq!((after.board, after.pouch_size), (vec![number], 89));
+
+        // Waiting on an old version returns at once, on the current one until the timeout
+        assert_eq!(game.changed_since(before.version, Duration::from_secs(5)).await, after.version);
+        assert_eq!(game.changed_since(after.version, Duration::from_millis(10)).await, after.version);
+
+        // A game restored from its snapshot continues the version instead of starting over
+        let restored = Game::from_serializable_state(game.create_serializable_state().unwrap()).unwrap();
+        assert_eq!(restored.version(), after.version);
+        restored.extract_number(0, None).unwrap();
+        assert!(restored.version() > after.version);
+    }
+
     #[test]
     fn test_game_state_queries() {
         let game = Game::new();
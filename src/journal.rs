@@ -0,0 +1,258 @@
+// src/journal.rs
+// Append-only journal of the mutations of a game.
+// Every entry is a typed event; replaying them in order with `Game::replay` rebuilds the game.
+// The public part of the events is pushed live to the clients as `GameUpdate`.
+
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::autorun::AutoRun;
+use crate::card::{Card, CardAssignment, CardDistribution};
+use crate::defs::{BoardStruct, Number};
+use crate::extraction::DrawMode;
+use crate::role::Role;
+use crate::ruleset::RulesetKind;
+use crate::score::{ClaimPenalty, ClaimVerdict, PrizeMode, ScoreAchievement};
+
+/// A mutation of a game
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// First event of every journal, with what is needed to recreate the game and its draw
+    GameCreated {
+        game_id: String,
+        created_at: SystemTime,
+        board_config: BoardStruct,
+        ruleset: RulesetKind,
+        /// Secret draw seed in hexadecimal, kept on the server like the game snapshots
+        draw_seed: String,
+    },
+    FullCardPrizesSet {
+        full_card_prizes: Number,
+    },
+    PrizeModeSet {
+        prize_mode: PrizeMode,
+        claim_penalty: ClaimPenalty,
+    },
+    DrawModeSet {
+        draw_mode: DrawMode,
+    },
+    CardDistributionSet {
+        distribution: CardDistribution,
+    },
+    AutoRunSet {
+        autorun: AutoRun,
+    },
+    OwnerSet {
+        client_id: String,
+    },
+    ClientJoined {
+        client_id: String,
+    },
+    /// Spectators join at any time, even after the first extraction
+    SpectatorJoined {
+        client_id: String,
+    },
+    /// Recorded as `client_type_set` with the client type before roles were typed
+    #[serde(alias = "client_type_set")]
+    RoleSet {
+        client_id: String,
+        #[serde(alias = "client_type")]
+        role: Role,
+    },
+    /// Cards added to the inventory of the game, not assigned yet
+    CardsImported {
+        cards: Vec<Card>,
+    },
+    /// Series opened to deal cards, recorded before the assignment dealing from it
+    SeriesOpened {
+        cards: Vec<Card>,
+    },
+    CardsAssigned {
+        client_id: String,
+        cards: Vec<CardAssignment>,
+    },
+    NumberExtracted {
+        number: Number,
+        /// Number of extractions on the board after this one
+        draw_index: usize,
+        current_working_score: Number,
+        board_client_id: Option<String>,
+    },
+    PrizeClaimed {
+        client_id: String,
+        card_id: String,
+        prize: Number,
+        verdict: ClaimVerdict,
+    },
+    ExtractionsUndone {
+        count: usize,
+        numbers: Vec<Number>,
+        /// Number of extractions left on the board
+        #[serde(default)]
+        draw_index: usize,
+    },
+    /// Recorded for the audit trail; replay awards the prize again through the extraction or the claim
+    PrizeAwarded {
+        score: Number,
+        achievement: ScoreAchievement,
+    },
+    /// Recorded for the audit trail when the last prize is awarded
+    GameClosed {
+        draw_index: usize,
+    },
+}
+
+impl GameEvent {
+    /// Whether the event only records the outcome of the events before it
+    #[must_use] pub fn is_outcome(&self) -> bool {
+        matches!(self, GameEvent::PrizeAwarded { .. } | GameEvent::GameClosed { .. })
+    }
+}
+
+/// An event with its position in the journal and the time it was recorded
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JournalEntry {
+    pub seq: usize,
+    pub recorded_at: SystemTime,
+    pub event: GameEvent,
+}
+
+/// In-memory journal of a game
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild a journal from stored entries
+    #[must_use] pub fn from_entries(entries: Vec<JournalEntry>) -> Self {
+        Self { entries }
+    }
+
+    /// Append an event, returning its sequence number
+    pub fn record(&mut self, event: GameEvent) -> usize {
+        let seq = self.entries.len();
+        self.entries.push(JournalEntry {
+            seq,
+            recorded_at: SystemTime::now(),
+            event,
+        });
+        seq
+    }
+
+    #[must_use] pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Entries recorded from the given sequence number on
+    #[must_use] pub fn since(&self, seq: usize) -> &[JournalEntry] {
+        &self.entries[seq.min(self.entries.len())..]
+    }
+
+    #[must_use] pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Live update pushed to the clients following a game, the public view of a `GameEvent`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameUpdate {
+    Extraction {
+        number: Number,
+        draw_index: usize,
+    },
+    Prize {
+        score: Number,
+        prize: String,
+        client_id: String,
+        card_id: String,
+        numbers: Vec<Number>,
+        draw_index: usize,
+    },
+    Claim {
+        client_id: String,
+        card_id: String,
+        prize: Number,
+        verdict: ClaimVerdict,
+    },
+    Join {
+        client_id: String,
+    },
+    Undo {
+        numbers: Vec<Number>,
+        draw_index: usize,
+    },
+    Closed {
+        draw_index: usize,
+    },
+    /// Updates were missed, the client must reload the game state
+    Resync,
+}
+
+impl GameUpdate {
+    /// Public update for an event, if the clients are interested in it; the draw seed and the cards are never published
+    #[must_use] pub fn from_event(event: &GameEvent, prize_name: impl Fn(Number) -> String) -> Option<Self> {
+        match event {
+            GameEvent::NumberExtracted { number, draw_index, .. } => Some(GameUpdate::Extraction {
+                number: *number,
+                draw_index: *draw_index,
+            }),
+            GameEvent::PrizeAwarded { score, achievement } => Some(GameUpdate::Prize {
+                score: *score,
+                prize: prize_name(*score),
+                client_id: achievement.client_id.clone(),
+                card_id: achievement.card_id.clone(),
+                numbers: achievement.numbers.clone(),
+                draw_index: achievement.draw_index,
+            }),
+            GameEvent::PrizeClaimed { client_id, card_id, prize, verdict } => Some(GameUpdate::Claim {
+                client_id: client_id.clone(),
+                card_id: card_id.clone(),
+                prize: *prize,
+                verdict: *verdict,
+            }),
+            GameEvent::ClientJoined { client_id } | GameEvent::SpectatorJoined { client_id } => Some(GameUpdate::Join { client_id: client_id.clone() }),
+            GameEvent::ExtractionsUndone { numbers, draw_index, .. } => Some(GameUpdate::Undo {
+                numbers: numbers.clone(),
+                draw_index: *draw_index,
+            }),
+            GameEvent::GameClosed { draw_index } => Some(GameUpdate::Closed { draw_index: *draw_index }),
+            _ => None,
+        }
+    }
+
+    /// Name of the update, as the `type` of its JSON form
+    #[must_use] pub fn kind(&self) -> &'static str {
+        match self {
+            GameUpdate::Extraction { .. } => "extraction",
+            GameUpdate::Prize { .. } => "prize",
+            GameUpdate::Claim { .. } => "claim",
+            GameUpdate::Join { .. } => "join",
+            GameUpdate::Undo { .. } => "undo",
+            GameUpdate::Closed { .. } => "closed",
+            GameUpdate::Resync => "resync",
+        }
+    }
+
+    /// Number of extractions on the board after the update, for the updates that change it or happen at a given draw
+    #[must_use] pub fn draw_index(&self) -> Option<usize> {
+        match self {
+            GameUpdate::Extraction { draw_index, .. }
+            | GameUpdate::Prize { draw_index, .. }
+            | GameUpdate::Undo { draw_index, .. }
+            | GameUpdate::Closed { draw_index } => Some(*draw_index),
+            GameUpdate::Claim { .. } | GameUpdate::Join { .. } | GameUpdate::Resync => None,
+        }
+    }
+}
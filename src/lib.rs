@@ -6,12 +6,22 @@ pub mod pouch;
 pub mod board;
 pub mod server;
 pub mod card;
+pub mod serial;
+pub mod card_import;
 pub mod client;
 pub mod score;
+pub mod ruleset;
 pub mod extraction;
 pub mod config;
 pub mod logging;
 pub mod game;
+pub mod journal;
+pub mod persistence;
+pub mod scheduler;
+pub mod autorun;
+pub mod auth;
+pub mod role;
+pub mod store;
 pub mod api_handlers;
 
 // Client library modules
@@ -22,4 +32,5 @@ pub mod clients {
     pub mod card_management;
     pub mod registration;
     pub mod terminal;
+    pub mod live_updates;
 }
@@ -0,0 +1,293 @@
+// src/persistence.rs
+// Snapshots of all games and clients, saved on every change and periodically,
+// and restored when the server starts.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::client::ClientRegistry;
+use crate::game::{Game, GameRegistry, SerializableGameState};
+use crate::logging::{log, LogLevel};
+use crate::server::AppState;
+use crate::store::GameStore;
+
+const MODULE_NAME: &str = "persistence";
+
+/// Snapshots of the server state and the game journals in a `GameStore`
+pub struct Persistence {
+    store: Box<dyn GameStore>,
+    changed: Notify,
+    // Number of journal entries already in the store for each game
+    journaled: Mutex<HashMap<String, usize>>,
+}
+
+impl Persistence {
+    #[must_use] pub fn new(store: Box<dyn GameStore>) -> Self {
+        Self {
+            store,
+            changed: Notify::new(),
+            journaled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[must_use] pub fn store(&self) -> &dyn GameStore {
+        self.store.as_ref()
+    }
+
+    /// Request a snapshot after a change of the games or clients
+    pub fn notify_change(&self) {
+        self.changed.notify_one();
+    }
+
+    /// Wait until a change is notified
+    pub async fn changed(&self) {
+        self.changed.notified().await;
+    }
+
+    /// Save all games, their new journal entries and the clients, removing from the store the games no longer registered
+    /// Returns the number of games saved
+    pub fn save_snapshot(&self, game_registry: &GameRegistry, client_registry: &ClientRegistry) -> Result<usize, String> {
+        let games = game_registry.all_games()?;
+        let mut saved_ids = Vec::with_capacity(games.len());
+        for game in &games {
+            let state = game.create_serializable_state()?;
+            self.store.save_game(&state)?;
+            self.append_journal(game)?;
+            saved_ids.push(state.id);
+        }
+
+        // Games removed from the registry are not restored
+        for game_id in self.store.game_ids()? {
+            if !saved_ids.contains(&game_id) {
+                self.store.remove_game(&game_id)?;
+            }
+        }
+
+        self.store.save_clients(&client_registry.get_all_clients()?)?;
+
+        Ok(games.len())
+    }
+
+    /// Restore the games and clients of the last snapshot into the registries
+    /// Returns the number of games and clients restored
+    pub fn load_snapshot(&self, game_registry: &GameRegistry, client_registry: &ClientRegistry) -> Result<(usize, usize), String> {
+        let clients = self.store.load_clients()?;
+        let client_count = clients.len();
+        for client in clients {
+            client_registry.insert(client)?;
+        }
+
+        let mut game_count = 0;
+        for state in self.store.load_games()? {
+            // A damaged game snapshot must not prevent the other games from resuming
+            let restored = state
+                .and_then(|state| self.restore_game(state))
+                .and_then(|game| game_registry.add_game(Arc::new(game)));
+            match restored {
+                Ok(game_id) => {
+                    log(LogLevel::Info, MODULE_NAME, &format!("Restored game {game_id}"));
+                    game_count += 1;
+                }
+                Err(e) => {
+                    log(LogLevel::Error, MODULE_NAME, &format!("Failed to restore game from {}: {e}", self.store.description()));
+                }
+            }
+        }
+
+        Ok((game_count, client_count))
+    }
+
+    // Append the journal entries of a game not yet in the store
+    fn append_journal(&self, game: &Game) -> Result<(), String> {
+        let game_id = game.id();
+        let mut journaled = self.journaled.lock()
+            .map_err(|_| "Failed to lock journal positions")?;
+        let stored = journaled.get(&game_id).copied().unwrap_or(0);
+        let entries = game.journal_since(stored)?;
+        if !entries.is_empty() {
+            self.store.append_journal(&game_id, &entries)?;
+            journaled.insert(game_id, stored + entries.len());
+        }
+        Ok(())
+    }
+
+    // Rebuild a game by replaying its journal, or from its snapshot when it has no usable journal
+    fn restore_game(&self, state: SerializableGameState) -> Result<Game, String> {
+        let journal = self.store.load_journal(&state.id)?;
+        if !journal.is_empty() {
+            match Game::replay(&journal) {
+                Ok(game) => {
+                    self.journaled.lock()
+                        .map_err(|_| "Failed to lock journal positions")?
+                        .insert(state.id, journal.len());
+                    return Ok(game);
+                }
+                Err(e) => {
+                    log(LogLevel::Warning, MODULE_NAME, &format!("Failed to replay the journal of game {}, restoring its snapshot: {e}", state.id));
+                }
+            }
+        }
+
+        // The stored journal stays as the audit trail of the game, its next events are appended after it
+        let game_id = state.id.clone();
+        let game = Game::from_serializable_state(state)?;
+        let stored = journal.len();
+        game.continue_journal(journal)?;
+        self.journaled.lock()
+            .map_err(|_| "Failed to lock journal positions")?
+            .insert(game_id, stored);
+        Ok(game)
+    }
+}
+
+/// Save a snapshot on every notified change and every `interval_secs` seconds (0 disables periodic snapshots)
+pub async fn run_snapshots(app_state: Arc<AppState>, interval_secs: u64) {
+    let mut interval = (interval_secs > 0).then(|| {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval
+    });
+
+    loop {
+        match interval.as_mut() {
+            Some(interval) => {
+                tokio::select! {
+                    () = app_state.persistence.changed() => {}
+                    _ = interval.tick() => {}
+                }
+            }
+            None => app_state.persistence.changed().await,
+        }
+
+        let persistence = &app_state.persistence;
+        if let Err(e) = persistence.save_snapshot(&app_state.game_registry, &app_state.global_client_registry) {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to save snapshot to {}: {e}", persistence.store().description()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use crate::client::ClientInfo;
+    use crate::extraction::{DrawMode, ManualDraw};
+    use crate::journal::{GameEvent, JournalEntry};
+    use crate::store::{JsonDirStore, MemoryStore, SqliteStore};
+    use crate::role::Role;
+
+    fn temp_snapshot_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("tombola_snapshot_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let path = temp_snapshot_path("roundtrip");
+        let persistence = Persistence::new(Box::new(JsonDirStore::new(&path)));
+
+        let game_registry = GameRegistry::new();
+        let client_registry = ClientRegistry::new();
+        let client = ClientInfo::new("player1", "player", "player1@example.com");
+        client_registry.insert(client.clone()).unwrap();
+
+        let game = Game::new();
+        game.set_owner(&client.id).unwrap();
+        game.add_client(client.id.clone()).unwrap();
+        game.set_role(&client.id, Role::Player).unwrap();
+        game.set_draw_mode(DrawMode::Manual).unwrap();
+        let card_ids = game.assign_cards(&client.id, 2, None).unwrap().1;
+        game.extract_number_from(&ManualDraw(42), 0, None).unwrap();
+        game_registry.add_game(Arc::new(game.clone())).unwrap();
+
+        assert_eq!(persistence.save_snapshot(&game_registry, &client_registry), Ok(1));
+
+        let restored_games = GameRegistry::new();
+        let restored_clients = ClientRegistry::new();
+        assert_eq!(persistence.load_snapshot(&restored_games, &restored_clients), Ok((1, 1)));
+
+        let restored = restored_games.get_game(&game.id()).unwrap().unwrap();
+        assert_eq!(restored.owner(), Some(client.id.clone()));
+        assert_eq!(restored.draw_mode(), DrawMode::Manual);
+        assert_eq!(restored.draw_commitment(), game.draw_commitment());
+        assert_eq!(restored.board().lock().unwrap().get_numbers(), &vec![42]);
+        assert_eq!(restored.pouch_length(), 89);
+        assert!(restored.contains_client(&client.id));
+        assert_eq!(restored.get_role(&client.id).unwrap(), Some(Role::Player));
+        assert_eq!(restored.card_manager().lock().unwrap().get_client_cards(&client.id), Some(&card_ids));
+        assert_eq!(restored_clients.get(&client.id).unwrap().unwrap().name, "player1");
+        // The game is rebuilt from its journal, which it keeps extending
+        assert_eq!(restored.journal_len(), game.journal_len());
+        assert_eq!(persistence.store().load_journal(&game.id()).unwrap().len(), game.journal_len());
+
+        // The restored game resumes where it stopped
+        restored.extract_number_from(&ManualDraw(7), 0, None).unwrap();
+        assert!(restored.extract_number_from(&ManualDraw(42), 0, None).is_err());
+
+        // Games removed from the registry disappear from the snapshot
+        game_registry.remove_game(&game.id()).unwrap();
+        assert_eq!(persistence.save_snapshot(&game_registry, &client_registry), Ok(0));
+        assert_eq!(persistence.load_snapshot(&GameRegistry::new(), &ClientRegistry::new()), Ok((0, 1)));
+
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_snapshot_restore_continues_stored_journal() {
+        let persistence = Persistence::new(Box::new(MemoryStore::new()));
+
+        let game_registry = GameRegistry::new();
+        let game = Game::new();
+        game.set_draw_mode(DrawMode::Manual).unwrap();
+        game.extract_number_from(&ManualDraw(42), 0, None).unwrap();
+        game_registry.add_game(Arc::new(game.clone())).unwrap();
+        persistence.save_snapshot(&game_registry, &ClientRegistry::new()).unwrap();
+
+        // An entry that cannot be replayed makes the restore fall back to the snapshot
+        let stored = game.journal_len();
+        persistence.store().append_journal(&game.id(), &[JournalEntry {
+            seq: stored,
+            recorded_at: std::time::SystemTime::now(),
+            event: GameEvent::NumberExtracted { number: 42, draw_index: 2, current_working_score: 0, board_client_id: None },
+        }]).unwrap();
+
+        let restored_games = GameRegistry::new();
+        assert_eq!(persistence.load_snapshot(&restored_games, &ClientRegistry::new()), Ok((1, 0)));
+        let restored = restored_games.get_game(&game.id()).unwrap().unwrap();
+        assert_eq!(restored.board().lock().unwrap().get_numbers(), &vec![42]);
+        assert_eq!(restored.journal_len(), stored + 1);
+
+        // The next events are appended after the stored ones, none of them is lost or renumbered
+        restored.extract_number_from(&ManualDraw(7), 0, None).unwrap();
+        persistence.save_snapshot(&restored_games, &ClientRegistry::new()).unwrap();
+        let journal = persistence.store().load_journal(&game.id()).unwrap();
+        assert_eq!(journal.len(), restored.journal_len());
+        assert!(journal.iter().enumerate().all(|(index, entry)| entry.seq == index));
+        assert!(matches!(journal[stored + 1].event, GameEvent::NumberExtracted { number: 7, .. }));
+    }
+
+    #[test]
+    fn test_seeded_draw_resumes_after_restore() {
+        let persistence = Persistence::new(Box::new(SqliteStore::open_in_memory().unwrap()));
+
+        let game_registry = GameRegistry::new();
+        let game = Game::new();
+        for _ in 0..5 {
+            game.extract_number(0, None).unwrap();
+        }
+        game_registry.add_game(Arc::new(game.clone())).unwrap();
+        persistence.save_snapshot(&game_registry, &ClientRegistry::new()).unwrap();
+
+        let restored_games = GameRegistry::new();
+        persistence.load_snapshot(&restored_games, &ClientRegistry::new()).unwrap();
+        let restored = restored_games.get_game(&game.id()).unwrap().unwrap();
+        for _ in 0..10 {
+            assert_eq!(restored.extract_number(0, None).unwrap().0, game.extract_number(0, None).unwrap().0);
+        }
+    }
+}
@@ -1,10 +1,100 @@
-use crate::defs::{Number, FIRSTNUMBER, LASTNUMBER};
+use crate::defs::{BoardStruct, Number, BOARDCONFIG};
 use serde::{Deserialize, Serialize};
-use rand::{rng, Rng};
+use rand::{rng, Rng, RngCore, SeedableRng};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+/// Secret seed of a game draw.
+/// The draw order is a shuffle of the number range with ChaCha20 seeded by these bytes,
+/// and the SHA-256 hash of the bytes is published as commitment before the first extraction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawSeed([u8; 32]);
+
+impl DrawSeed {
+    /// Generate a new random seed
+    #[must_use] pub fn random() -> Self {
+        let mut bytes = [0u8; 32];
+        rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    #[must_use] pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a seed from its 64-digit hexadecimal form
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err("Draw seed must be 64 hexadecimal digits".to_string());
+        }
+        let mut bytes = [0u8; 32];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16)
+                .map_err(|_| "Draw seed must be 64 hexadecimal digits".to_string())?;
+        }
+        Ok(Self(bytes))
+    }
+
+    #[must_use] pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// SHA-256 hash of the seed bytes, in hexadecimal
+    #[must_use] pub fn commitment(&self) -> String {
+        to_hex(&Sha256::digest(self.0))
+    }
+
+    /// Complete draw sequence of the number range of the given board geometry
+    #[must_use] pub fn draw_sequence(&self, board_config: &BoardStruct) -> Vec<Number> {
+        let mut sequence: Vec<Number> = (board_config.first_number..=board_config.last_number()).collect();
+        sequence.shuffle(&mut ChaCha20Rng::from_seed(self.0));
+        sequence
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Result of replaying a draw from its revealed seed
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DrawVerification {
+    /// The seed hashes to the commitment published at creation
+    pub commitment_valid: bool,
+    /// Draw sequence replayed from the seed, as long as the extracted numbers
+    pub replayed: Vec<Number>,
+    /// The extracted numbers match the replayed sequence
+    pub sequence_valid: bool,
+    pub verified: bool,
+}
+
+/// Check a revealed seed against the published commitment and the numbers extracted in the game
+#[must_use] pub fn verify_draw(seed: &DrawSeed, commitment: &str, board_config: &BoardStruct, extracted: &[Number]) -> DrawVerification {
+    let commitment_valid = seed.commitment().eq_ignore_ascii_case(commitment);
+    let mut replayed = seed.draw_sequence(board_config);
+    replayed.truncate(extracted.len());
+    let sequence_valid = replayed == extracted;
+    DrawVerification {
+        commitment_valid,
+        replayed,
+        sequence_valid,
+        verified: commitment_valid && sequence_valid,
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Pouch {
     pub numbers: Vec<Number>,
+    // The seed and the draw order stay on the server until the game closes
+    #[serde(skip)]
+    seed: Option<DrawSeed>,
+    // Remaining numbers in draw order, next number last
+    #[serde(skip)]
+    draw_order: Vec<Number>,
+    // Numbers put back by undos, on top of the draw order in the same order
+    #[serde(skip)]
+    put_back: Vec<Number>,
 }
 
 impl Default for Pouch {
@@ -15,12 +105,61 @@ impl Default for Pouch {
 
 impl Pouch {
     #[must_use] pub fn new() -> Self {
-        let numbers: Vec<Number> = (FIRSTNUMBER..=LASTNUMBER).collect();
+        Self::with_config(&BOARDCONFIG)
+    }
+
+    /// Create a full pouch for the number range of the given board geometry, drawn with a random seed
+    #[must_use] pub fn with_config(board_config: &BoardStruct) -> Self {
+        Self::with_seed(board_config, DrawSeed::random())
+    }
+
+    /// Create a full pouch whose draw order is derived from the given seed
+    #[must_use] pub fn with_seed(board_config: &BoardStruct, seed: DrawSeed) -> Self {
+        let numbers: Vec<Number> = (board_config.first_number..=board_config.last_number()).collect();
+        let mut draw_order = seed.draw_sequence(board_config);
+        draw_order.reverse();
         Pouch {
             numbers,
+            seed: Some(seed),
+            draw_order,
+            put_back: Vec::new(),
         }
     }
 
+    /// Rebuild a pouch holding the given remaining numbers, keeping the draw order of the seed
+    /// with the numbers put back by undos drawn first, as `put_back_order` returned them
+    #[must_use] pub fn restore(board_config: &BoardStruct, seed: Option<DrawSeed>, mut numbers: Vec<Number>, mut put_back: Vec<Number>) -> Self {
+        numbers.sort_unstable();
+        if seed.is_none() {
+            put_back.clear();
+        }
+        put_back.retain(|number| numbers.binary_search(number).is_ok());
+        let mut draw_order: Vec<Number> = seed
+            .map(|seed| seed.draw_sequence(board_config))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|number| numbers.binary_search(number).is_ok() && !put_back.contains(number))
+            .collect();
+        draw_order.reverse();
+        draw_order.extend_from_slice(&put_back);
+        Pouch {
+            numbers,
+            seed,
+            draw_order,
+            put_back,
+        }
+    }
+
+    /// Numbers put back by undos and not drawn again yet, next number last
+    #[must_use] pub fn put_back_order(&self) -> &[Number] {
+        &self.put_back
+    }
+
+    /// Seed of the draw, if the pouch was created on this server
+    #[must_use] pub fn seed(&self) -> Option<&DrawSeed> {
+        self.seed.as_ref()
+    }
+
     #[must_use] pub fn len(&self) -> usize {
         self.numbers.len()
     }
@@ -33,12 +172,154 @@ impl Pouch {
         self.numbers.remove(index)
     }
 
+    /// Take a given number out of the pouch, if it has not been extracted yet
+    pub fn take(&mut self, number: Number) -> Option<Number> {
+        let index = self.numbers.iter().position(|&n| n == number)?;
+        self.draw_order.retain(|&n| n != number);
+        self.put_back.retain(|&n| n != number);
+        Some(self.remove(index))
+    }
+
+    /// Return an extracted number to the pouch, as the next number of the draw order
+    pub fn put_back(&mut self, number: Number) {
+        if let Err(index) = self.numbers.binary_search(&number) {
+            self.numbers.insert(index, number);
+            if self.seed.is_some() {
+                self.draw_order.push(number);
+                self.put_back.push(number);
+            }
+        }
+    }
+
     pub fn extract(&mut self) -> Number {
         if self.is_empty() {
             0 // Return 0 if pouch is empty
+        } else if let Some(number) = self.draw_order.pop() {
+            if self.put_back.last() == Some(&number) {
+                self.put_back.pop();
+            }
+            match self.numbers.iter().position(|&n| n == number) {
+                Some(index) => self.remove(index),
+                None => number,
+            }
         } else {
+            // Pouches restored without their seed fall back to a random draw
             let random_index = rng().random_range(0..self.len());
             self.remove(random_index)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_draw_is_reproducible() {
+        let seed = DrawSeed::from_bytes([7; 32]);
+        let mut first = Pouch::with_seed(&BOARDCONFIG, seed);
+        let mut second = Pouch::with_seed(&BOARDCONFIG, seed);
+
+        let drawn: Vec<Number> = (0..90).map(|_| first.extract()).collect();
+        assert_eq!(drawn, (0..90).map(|_| second.extract()).collect::<Vec<_>>());
+        assert_eq!(drawn, seed.draw_sequence(&BOARDCONFIG));
+        assert!(first.is_empty());
+        assert_eq!(first.extract(), 0);
+
+        let mut sorted = drawn.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (1..=90).collect::<Vec<Number>>());
+    }
+
+    #[test]
+    fn test_take_number() {
+        let mut pouch = Pouch::new();
+        assert_eq!(pouch.take(42), Some(42));
+        assert_eq!(pouch.take(42), None);
+        assert_eq!(pouch.take(91), None);
+        assert_eq!(pouch.len(), 89);
+
+        let drawn: Vec<Number> = (0..89).map(|_| pouch.extract()).collect();
+        assert!(!drawn.contains(&42));
+        assert!(pouch.is_empty());
+    }
+
+    #[test]
+    fn test_put_back_number() {
+        let seed = DrawSeed::from_bytes([9; 32]);
+        let mut pouch = Pouch::with_seed(&BOARDCONFIG, seed);
+        let first = pouch.extract();
+        let second = pouch.extract();
+
+        // A returned number is drawn again next, keeping the seeded sequence
+        pouch.put_back(second);
+        assert_eq!(pouch.len(), 89);
+        assert!(pouch.numbers.is_sorted());
+        pouch.put_back(second);
+        assert_eq!(pouch.len(), 89);
+        assert_eq!(pouch.extract(), second);
+        assert_eq!(vec![first, second], seed.draw_sequence(&BOARDCONFIG)[..2]);
+    }
+
+    #[test]
+    fn test_restore_keeps_draw_order() {
+        let seed = DrawSeed::from_bytes([5; 32]);
+        let mut pouch = Pouch::with_seed(&BOARDCONFIG, seed);
+        pouch.extract();
+        pouch.extract();
+
+        let mut restored = Pouch::restore(&BOARDCONFIG, Some(seed), pouch.numbers.clone(), Vec::new());
+        assert_eq!(restored.numbers, pouch.numbers);
+        for _ in 0..88 {
+            assert_eq!(restored.extract(), pouch.extract());
+        }
+    }
+
+    #[test]
+    fn test_restore_keeps_put_back_order() {
+        let seed = DrawSeed::from_bytes([6; 32]);
+        let sequence = seed.draw_sequence(&BOARDCONFIG);
+        let mut pouch = Pouch::with_seed(&BOARDCONFIG, seed);
+
+        // Numbers taken out of the seeded order and put back are drawn first, in the reverse order of the undos
+        for number in [sequence[40], sequence[20], sequence[60]] {
+            pouch.take(number);
+        }
+        pouch.put_back(sequence[60]);
+        pouch.put_back(sequence[20]);
+        assert_eq!(pouch.put_back_order(), [sequence[60], sequence[20]]);
+
+        let mut restored = Pouch::restore(&BOARDCONFIG, Some(seed), pouch.numbers.clone(), pouch.put_back_order().to_vec());
+        assert_eq!(restored.extract(), sequence[20]);
+        assert_eq!(pouch.extract(), sequence[20]);
+        assert_eq!(pouch.put_back_order(), [sequence[60]]);
+        for _ in 0..88 {
+            assert_eq!(restored.extract(), pouch.extract());
+        }
+        assert!(restored.put_back_order().is_empty());
+    }
+
+    #[test]
+    fn test_seed_hex_roundtrip() {
+        let seed = DrawSeed::random();
+        assert_eq!(DrawSeed::from_hex(&seed.to_hex()), Ok(seed));
+        assert_eq!(seed.commitment().len(), 64);
+        assert!(DrawSeed::from_hex("abc").is_err());
+        assert!(DrawSeed::from_hex(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_verify_draw() {
+        let seed = DrawSeed::from_bytes([42; 32]);
+        let mut pouch = Pouch::with_seed(&BOARDCONFIG, seed);
+        let extracted: Vec<Number> = (0..10).map(|_| pouch.extract()).collect();
+
+        let verification = verify_draw(&seed, &seed.commitment(), &BOARDCONFIG, &extracted);
+        assert!(verification.verified);
+        assert_eq!(verification.replayed, extracted);
+
+        let other_seed = DrawSeed::from_bytes([43; 32]);
+        assert!(!verify_draw(&other_seed, &seed.commitment(), &BOARDCONFIG, &extracted).commitment_valid);
+        assert!(!verify_draw(&seed, &seed.commitment(), &BOARDCONFIG, &extracted[1..]).sequence_valid);
+    }
+}
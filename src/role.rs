@@ -0,0 +1,186 @@
+// src/role.rs
+// Roles of the clients and the permissions they grant.
+// Every client of a game holds one role in it, and the clients whose IDs are listed in the `admins` of server.conf are global admins.
+// What each role may do is declared once in `Role::allows`, and enforced for the handlers by the `Authorized` extractor.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Role of a client, ordered from the most to the least privileged
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Global administrator, allowed everything in every game
+    Admin,
+    /// Creator of the game, holding the board card
+    #[serde(alias = "board")]
+    Owner,
+    /// Draws the numbers in place of the owner, granted by the owner
+    Caller,
+    /// Plays with cards
+    Player,
+    /// Follows the game without cards, joining at any time
+    Spectator,
+}
+
+/// Action guarded by a role
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Extract numbers and undo extractions
+    Draw,
+    /// Dump the game and grant roles
+    ManageGame,
+    /// Get cards and claim prizes with them
+    Play,
+    /// Read the cards assigned to the client
+    ReadCards,
+    /// List the clients of the game
+    ViewPlayers,
+}
+
+impl Action {
+    #[must_use] pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Draw => "draw numbers",
+            Action::ManageGame => "manage the game",
+            Action::Play => "play cards",
+            Action::ReadCards => "read cards",
+            Action::ViewPlayers => "view the players",
+        }
+    }
+}
+
+impl Role {
+    /// Whether the role may perform the action
+    #[must_use] pub fn allows(&self, action: Action) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Owner => matches!(action, Action::Draw | Action::ManageGame | Action::ReadCards | Action::ViewPlayers),
+            Role::Caller => matches!(action, Action::Draw | Action::ReadCards | Action::ViewPlayers),
+            Role::Player => matches!(action, Action::Play | Action::ReadCards | Action::ViewPlayers),
+            Role::Spectator => matches!(action, Action::ViewPlayers),
+        }
+    }
+
+    /// Whether a client can take the role by joining a game, the others are granted
+    /// The owner role is only taken by creating the game
+    #[must_use] pub fn can_join_as(&self) -> bool {
+        matches!(self, Role::Player | Role::Spectator)
+    }
+
+    /// Whether the owner of a game can grant the role to its clients
+    #[must_use] pub fn can_be_granted(&self) -> bool {
+        matches!(self, Role::Caller | Role::Player)
+    }
+
+    #[must_use] pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Owner => "owner",
+            Role::Caller => "caller",
+            Role::Player => "player",
+            Role::Spectator => "spectator",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    /// Parse a role or a client type of the registration requests, where "board" is the owner
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "admin" => Ok(Role::Admin),
+            "owner" | "board" => Ok(Role::Owner),
+            "caller" => Ok(Role::Caller),
+            "player" => Ok(Role::Player),
+            "spectator" => Ok(Role::Spectator),
+            _ => Err(format!("Unknown role '{s}'")),
+        }
+    }
+}
+
+/// Request of the owner granting a role to a client of the game
+#[derive(Debug, Deserialize)]
+pub struct SetRoleRequest {
+    pub client_id: String,
+    pub role: Role,
+}
+
+/// Action required by an `Authorized` handler argument
+pub trait Permission {
+    const ACTION: Action;
+}
+
+/// Permission to draw numbers
+pub struct CanDraw;
+
+/// Permission to manage the game
+pub struct CanManage;
+
+/// Permission to play cards
+pub struct CanPlay;
+
+/// Permission to read the cards of the client
+pub struct CanReadCards;
+
+/// Permission to list the clients of the game
+pub struct CanViewPlayers;
+
+impl Permission for CanDraw {
+    const ACTION: Action = Action::Draw;
+}
+
+impl Permission for CanManage {
+    const ACTION: Action = Action::ManageGame;
+}
+
+impl Permission for CanPlay {
+    const ACTION: Action = Action::Play;
+}
+
+impl Permission for CanReadCards {
+    const ACTION: Action = Action::ReadCards;
+}
+
+impl Permission for CanViewPlayers {
+    const ACTION: Action = Action::ViewPlayers;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        let actions = [Action::Draw, Action::ManageGame, Action::Play, Action::ReadCards, Action::ViewPlayers];
+        let allowed = |role: Role| actions.iter().copied().filter(|action| role.allows(*action)).collect::<Vec<_>>();
+
+        assert_eq!(allowed(Role::Admin), actions);
+        assert_eq!(allowed(Role::Owner), [Action::Draw, Action::ManageGame, Action::ReadCards, Action::ViewPlayers]);
+        assert_eq!(allowed(Role::Caller), [Action::Draw, Action::ReadCards, Action::ViewPlayers]);
+        assert_eq!(allowed(Role::Player), [Action::Play, Action::ReadCards, Action::ViewPlayers]);
+        assert_eq!(allowed(Role::Spectator), [Action::ViewPlayers]);
+    }
+
+    #[test]
+    fn test_role_parsing() {
+        assert_eq!("board".parse::<Role>(), Ok(Role::Owner));
+        assert_eq!("Player".parse::<Role>(), Ok(Role::Player));
+        assert!("referee".parse::<Role>().is_err());
+
+        // The client types of older snapshots and journals are read as roles
+        assert_eq!(serde_json::from_str::<Role>("\"board\"").unwrap(), Role::Owner);
+        assert_eq!(serde_json::to_string(&Role::Owner).unwrap(), "\"owner\"");
+
+        assert!(Role::Player.can_join_as() && Role::Spectator.can_join_as());
+        assert!(!Role::Owner.can_join_as() && !Role::Caller.can_join_as() && !Role::Admin.can_join_as());
+    }
+}
@@ -0,0 +1,545 @@
+// src/ruleset.rs
+// This module defines the prize rulesets used by the ScoreCard to evaluate cards.
+//
+// A ruleset lists the prizes of a game, decides which prizes a card has reached for
+// a given set of extracted numbers and tells which prize ends the game.
+// The classic Italian tombola (ambo, terno, quaterna, cinquina, tombola) is the default,
+// the UK 90-ball housie (one line, two lines, full house) plays on the same cards and the
+// US 75-ball bingo awards patterns on 5x5 cards with a free centre square.
+
+use crate::card::Card;
+use crate::defs::{BoardStruct, Number, BINGO75_BOARDCONFIG, BOARDCONFIG};
+use serde::{Deserialize, Serialize};
+
+/// A prize that can be awarded in a game, keyed by its `score_map` index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Prize {
+    pub score: Number,
+    pub name: String,
+}
+
+impl Prize {
+    #[must_use] pub fn new(score: Number, name: &str) -> Self {
+        Self {
+            score,
+            name: name.to_string(),
+        }
+    }
+}
+
+/// Shape of the player cards of a ruleset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardLayout {
+    /// Cards of `rows_per_card` rows with one column for every ten numbers (tombola, housie)
+    Columns,
+    /// 5x5 cards with the B-I-N-G-O column ranges and a free centre square
+    Bingo75,
+}
+
+/// A prize reached by a card: its score and the numbers that made it
+pub type PrizeResult = (Number, Vec<Number>);
+
+/// Rules deciding which prizes exist and when a card wins them
+pub trait PrizeRuleset: Send + Sync {
+    /// Ruleset identifier as used in `/newgame`
+    fn name(&self) -> &'static str;
+
+    /// All the prizes of the game in ascending score order
+    fn prizes(&self, board_config: &BoardStruct) -> Vec<Prize>;
+
+    /// Every prize the card has reached, with the numbers that made it
+    fn evaluate_card(&self, card: &Card, extracted: &[Number], board_config: &BoardStruct) -> Vec<PrizeResult>;
+
+    /// Score of the prize that ends the game
+    fn final_prize(&self, board_config: &BoardStruct) -> Number;
+
+    /// Whether prizes must be won in order: once a prize is awarded, lower prizes can no longer be won
+    fn is_progressive(&self) -> bool {
+        true
+    }
+
+    /// Cards the board owner plays with: the board split in `cards_number()` cards by default
+    fn board_cards(&self, board_config: &BoardStruct) -> Vec<Card> {
+        split_board_in_cards(board_config)
+    }
+
+    /// Name of the full-card prize of the given rank when a game awards more than one (rank 1 is the final prize)
+    fn full_card_prize_name(&self, rank: Number, board_config: &BoardStruct) -> String {
+        let name = self.prizes(board_config).pop().map_or_else(String::new, |prize| prize.name);
+        if rank <= 1 { name } else { format!("{name} #{rank}") }
+    }
+
+    /// Shape of the player cards
+    fn card_layout(&self) -> CardLayout {
+        CardLayout::Columns
+    }
+
+    /// Board geometry used when `/newgame` does not give one
+    fn default_board_config(&self) -> BoardStruct {
+        BOARDCONFIG
+    }
+
+    /// Check that the ruleset can be played on the given board geometry
+    fn validate_board(&self, board_config: &BoardStruct) -> Result<(), String> {
+        board_config.validate()
+    }
+}
+
+/// Ruleset selectable per game
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RulesetKind {
+    /// Italian tombola: ambo, terno, quaterna, cinquina and tombola
+    #[default]
+    Tombola,
+    /// UK 90-ball housie: one line, two lines and full house
+    Housie,
+    /// US 75-ball bingo: line, diagonal, four corners, X and blackout on 5x5 cards
+    Bingo75,
+}
+
+static TOMBOLA_RULESET: TombolaRuleset = TombolaRuleset;
+static HOUSIE_RULESET: HousieRuleset = HousieRuleset;
+static BINGO75_RULESET: Bingo75Ruleset = Bingo75Ruleset;
+
+impl RulesetKind {
+    /// Get the ruleset implementation
+    #[must_use] pub fn ruleset(&self) -> &'static dyn PrizeRuleset {
+        match self {
+            RulesetKind::Tombola => &TOMBOLA_RULESET,
+            RulesetKind::Housie => &HOUSIE_RULESET,
+            RulesetKind::Bingo75 => &BINGO75_RULESET,
+        }
+    }
+
+    #[must_use] pub fn as_str(&self) -> &'static str {
+        self.ruleset().name()
+    }
+}
+
+impl std::str::FromStr for RulesetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tombola" => Ok(RulesetKind::Tombola),
+            "housie" => Ok(RulesetKind::Housie),
+            "bingo75" => Ok(RulesetKind::Bingo75),
+            _ => Err(format!("Unknown ruleset '{s}'")),
+        }
+    }
+}
+
+/// Split the board in the cards it is made of (2×3 cards of 5×3 numbers on the classic layout)
+#[must_use] pub fn split_board_in_cards(board_config: &BoardStruct) -> Vec<Card> {
+    let numbers_per_row = board_config.numbers_per_row() as usize;
+    let rows_per_card = board_config.rows_per_card as usize;
+    let cols_per_card = board_config.cols_per_card as usize;
+    let cards_per_row = board_config.cards_per_row as usize;
+
+    (0..board_config.cards_number() as usize)
+        .map(|card_index| {
+            let card_row = card_index / cards_per_row;
+            let card_col = card_index % cards_per_row;
+            (0..rows_per_card)
+                .map(|row| {
+                    (0..cols_per_card)
+                        .map(|col| {
+                            let number = board_config.first_number as usize
+                                + (card_row * rows_per_card + row) * numbers_per_row
+                                + card_col * cols_per_card + col;
+                            Some(number as Number)
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Extracted numbers of each card row, in row order
+fn extracted_by_row(card: &Card, extracted: &[Number]) -> Vec<Vec<Number>> {
+    card.iter()
+        .map(|row| row.iter().flatten().filter(|number| extracted.contains(number)).copied().collect())
+        .collect()
+}
+
+/// All the numbers of a card if every one of them has been extracted
+fn full_card(card: &Card, extracted: &[Number]) -> Option<Vec<Number>> {
+    let numbers: Vec<Number> = card.iter().flatten().flatten().copied().collect();
+    if !numbers.is_empty() && numbers.iter().all(|number| extracted.contains(number)) {
+        Some(numbers)
+    } else {
+        None
+    }
+}
+
+/// Classic Italian tombola: 2 to 5 numbers on the same row, then the full card
+#[derive(Debug)]
+pub struct TombolaRuleset;
+
+impl TombolaRuleset {
+    fn line_prize_name(numbers: Number) -> String {
+        match numbers {
+            2 => "AMBO".to_string(),
+            3 => "TERNO".to_string(),
+            4 => "QUATERNA".to_string(),
+            5 => "CINQUINA".to_string(),
+            n => format!("{n} IN LINE"),
+        }
+    }
+}
+
+impl PrizeRuleset for TombolaRuleset {
+    fn name(&self) -> &'static str {
+        "tombola"
+    }
+
+    fn prizes(&self, board_config: &BoardStruct) -> Vec<Prize> {
+        let mut prizes: Vec<Prize> = (2..=board_config.cols_per_card)
+            .filter(|&score| score < board_config.numbers_per_card())
+            .map(|score| Prize { score, name: Self::line_prize_name(score) })
+            .collect();
+        prizes.push(Prize::new(self.final_prize(board_config), "TOMBOLA"));
+        prizes
+    }
+
+    fn evaluate_card(&self, card: &Card, extracted: &[Number], board_config: &BoardStruct) -> Vec<PrizeResult> {
+        let mut reached = Vec::new();
+
+        // Line prizes use the row with the most extracted numbers
+        let best_row = extracted_by_row(card, extracted)
+            .into_iter()
+            .max_by_key(Vec::len)
+            .unwrap_or_default();
+        for prize in self.prizes(board_config) {
+            if prize.score != self.final_prize(board_config) && best_row.len() >= prize.score as usize {
+                reached.push((prize.score, best_row[..prize.score as usize].to_vec()));
+            }
+        }
+
+        if let Some(numbers) = full_card(card, extracted) {
+            reached.push((self.final_prize(board_config), numbers));
+        }
+
+        reached
+    }
+
+    fn final_prize(&self, board_config: &BoardStruct) -> Number {
+        board_config.numbers_per_card()
+    }
+
+    fn full_card_prize_name(&self, rank: Number, _board_config: &BoardStruct) -> String {
+        match rank {
+            0 | 1 => "TOMBOLA".to_string(),
+            2 => "TOMBOLINO".to_string(),
+            n => format!("TOMBOLA #{n}"),
+        }
+    }
+}
+
+/// UK 90-ball housie: complete rows win one line and two lines, then the full house
+#[derive(Debug)]
+pub struct HousieRuleset;
+
+impl HousieRuleset {
+    const ONE_LINE: Number = 1;
+    const TWO_LINES: Number = 2;
+    const FULL_HOUSE: Number = 3;
+}
+
+impl PrizeRuleset for HousieRuleset {
+    fn name(&self) -> &'static str {
+        "housie"
+    }
+
+    fn prizes(&self, _board_config: &BoardStruct) -> Vec<Prize> {
+        vec![
+            Prize::new(Self::ONE_LINE, "ONE LINE"),
+            Prize::new(Self::TWO_LINES, "TWO LINES"),
+            Prize::new(Self::FULL_HOUSE, "FULL HOUSE"),
+        ]
+    }
+
+    fn evaluate_card(&self, card: &Card, extracted: &[Number], _board_config: &BoardStruct) -> Vec<PrizeResult> {
+        let mut reached = Vec::new();
+
+        // A line is a card row with all of its numbers extracted
+        let complete_rows: Vec<Vec<Number>> = card.iter()
+            .zip(extracted_by_row(card, extracted))
+            .filter(|(row, extracted_row)| !extracted_row.is_empty() && row.iter().flatten().count() == extracted_row.len())
+            .map(|(_, extracted_row)| extracted_row)
+            .collect();
+
+        if let Some(first_row) = complete_rows.first() {
+            reached.push((Self::ONE_LINE, first_row.clone()));
+        }
+        if complete_rows.len() >= 2 {
+            reached.push((Self::TWO_LINES, complete_rows[..2].concat()));
+        }
+        if let Some(numbers) = full_card(card, extracted) {
+            reached.push((Self::FULL_HOUSE, numbers));
+        }
+
+        reached
+    }
+
+    fn final_prize(&self, _board_config: &BoardStruct) -> Number {
+        Self::FULL_HOUSE
+    }
+}
+
+/// US 75-ball bingo: patterns on 5x5 cards, the free centre square counts as marked
+#[derive(Debug)]
+pub struct Bingo75Ruleset;
+
+impl Bingo75Ruleset {
+    const LINE: Number = 1;
+    const DIAGONAL: Number = 2;
+    const FOUR_CORNERS: Number = 3;
+    const X: Number = 4;
+    const BLACKOUT: Number = 5;
+}
+
+impl PrizeRuleset for Bingo75Ruleset {
+    fn name(&self) -> &'static str {
+        "bingo75"
+    }
+
+    fn prizes(&self, _board_config: &BoardStruct) -> Vec<Prize> {
+        vec![
+            Prize::new(Self::LINE, "LINE"),
+            Prize::new(Self::DIAGONAL, "DIAGONAL"),
+            Prize::new(Self::FOUR_CORNERS, "FOUR CORNERS"),
+            Prize::new(Self::X, "X"),
+            Prize::new(Self::BLACKOUT, "BLACKOUT"),
+        ]
+    }
+
+    fn evaluate_card(&self, card: &Card, extracted: &[Number], _board_config: &BoardStruct) -> Vec<PrizeResult> {
+        let mut reached = Vec::new();
+        let size = card.len();
+        if size == 0 {
+            return reached;
+        }
+
+        // Empty cells are the free square and are always marked
+        let is_marked = |&(row, col): &(usize, usize)| match card[row].get(col) {
+            Some(Some(number)) => extracted.contains(number),
+            _ => true,
+        };
+        let pattern = |cells: &[(usize, usize)]| -> Option<Vec<Number>> {
+            cells.iter().all(is_marked).then(|| {
+                cells.iter().filter_map(|&(row, col)| card[row].get(col).copied().flatten()).collect()
+            })
+        };
+
+        let rows = (0..size).map(|row| (0..size).map(|col| (row, col)).collect::<Vec<_>>());
+        let columns = (0..size).map(|col| (0..size).map(|row| (row, col)).collect::<Vec<_>>());
+        let lines: Vec<Vec<(usize, usize)>> = rows.chain(columns).collect();
+        let diagonal: Vec<(usize, usize)> = (0..size).map(|i| (i, i)).collect();
+        let anti_diagonal: Vec<(usize, usize)> = (0..size).map(|i| (i, size - 1 - i)).collect();
+        let corners = [(0, 0), (0, size - 1), (size - 1, 0), (size - 1, size - 1)];
+
+        if let Some(numbers) = lines.iter().find_map(|line| pattern(line)) {
+            reached.push((Self::LINE, numbers));
+        }
+        let diagonals: Vec<Vec<Number>> = [&diagonal, &anti_diagonal].into_iter().filter_map(|cells| pattern(cells)).collect();
+        if let Some(numbers) = diagonals.first() {
+            reached.push((Self::DIAGONAL, numbers.clone()));
+        }
+        if let Some(numbers) = pattern(&corners) {
+            reached.push((Self::FOUR_CORNERS, numbers));
+        }
+        if diagonals.len() == 2 {
+            let mut numbers = diagonals.concat();
+            numbers.sort_unstable();
+            numbers.dedup();
+            reached.push((Self::X, numbers));
+        }
+        if let Some(numbers) = full_card(card, extracted) {
+            reached.push((Self::BLACKOUT, numbers));
+        }
+
+        reached
+    }
+
+    fn final_prize(&self, _board_config: &BoardStruct) -> Number {
+        Self::BLACKOUT
+    }
+
+    // Patterns are independent: a card can still win a line after somebody made an X
+    fn is_progressive(&self) -> bool {
+        false
+    }
+
+    // The board owner has no card in 75-ball bingo
+    fn board_cards(&self, _board_config: &BoardStruct) -> Vec<Card> {
+        Vec::new()
+    }
+
+    fn card_layout(&self) -> CardLayout {
+        CardLayout::Bingo75
+    }
+
+    fn default_board_config(&self) -> BoardStruct {
+        BINGO75_BOARDCONFIG
+    }
+
+    fn validate_board(&self, board_config: &BoardStruct) -> Result<(), String> {
+        // The board shows one row of 15 numbers for each B-I-N-G-O column
+        if board_config.numbers_per_row() != 15 || board_config.rows_per_card as usize * board_config.cards_per_col as usize != 5 {
+            return Err("75-ball bingo needs a board of 5 rows of 15 numbers".to_string());
+        }
+        if board_config.first_number == 0 || board_config.first_number as usize + 74 > Number::MAX as usize {
+            return Err(format!("first_number must be between 1 and {}", Number::MAX as usize - 74));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defs::BOARDCONFIG;
+
+    fn sample_card() -> Card {
+        vec![
+            vec![Some(1), None, Some(21), None, Some(41), None, Some(61), None, Some(81)],
+            vec![None, Some(12), None, Some(32), None, Some(52), None, Some(72), Some(82)],
+            vec![Some(3), Some(13), Some(23), None, None, Some(53), Some(63), None, None],
+        ]
+    }
+
+    #[test]
+    fn test_tombola_prizes() {
+        let prizes = RulesetKind::Tombola.ruleset().prizes(&BOARDCONFIG);
+        let scores: Vec<Number> = prizes.iter().map(|prize| prize.score).collect();
+        assert_eq!(scores, vec![2, 3, 4, 5, 15]);
+        assert_eq!(prizes[0].name, "AMBO");
+        assert_eq!(prizes[4].name, "TOMBOLA");
+        assert_eq!(RulesetKind::Tombola.ruleset().final_prize(&BOARDCONFIG), 15);
+    }
+
+    #[test]
+    fn test_tombola_evaluate_lines() {
+        let ruleset = RulesetKind::Tombola.ruleset();
+        let card = sample_card();
+
+        assert!(ruleset.evaluate_card(&card, &[1, 12, 53], &BOARDCONFIG).is_empty());
+
+        let reached = ruleset.evaluate_card(&card, &[1, 21, 41, 12], &BOARDCONFIG);
+        assert_eq!(reached, vec![(2, vec![1, 21]), (3, vec![1, 21, 41])]);
+    }
+
+    #[test]
+    fn test_tombola_evaluate_full_card() {
+        let ruleset = RulesetKind::Tombola.ruleset();
+        let card = sample_card();
+        let all_numbers: Vec<Number> = card.iter().flatten().flatten().copied().collect();
+
+        let reached = ruleset.evaluate_card(&card, &all_numbers, &BOARDCONFIG);
+        let scores: Vec<Number> = reached.iter().map(|(score, _)| *score).collect();
+        assert_eq!(scores, vec![2, 3, 4, 5, 15]);
+        assert_eq!(reached.last().unwrap().1.len(), 15);
+    }
+
+    #[test]
+    fn test_housie_evaluate_lines() {
+        let ruleset = RulesetKind::Housie.ruleset();
+        let card = sample_card();
+
+        // Four numbers of the first row are not a line
+        assert!(ruleset.evaluate_card(&card, &[1, 21, 41, 61], &BOARDCONFIG).is_empty());
+
+        let first_row = [1, 21, 41, 61, 81];
+        let reached = ruleset.evaluate_card(&card, &first_row, &BOARDCONFIG);
+        assert_eq!(reached, vec![(1, first_row.to_vec())]);
+
+        let two_rows = [1, 21, 41, 61, 81, 12, 32, 52, 72, 82];
+        let reached = ruleset.evaluate_card(&card, &two_rows, &BOARDCONFIG);
+        assert_eq!(reached, vec![(1, first_row.to_vec()), (2, two_rows.to_vec())]);
+    }
+
+    #[test]
+    fn test_housie_full_house() {
+        let ruleset = RulesetKind::Housie.ruleset();
+        let card = sample_card();
+        let all_numbers: Vec<Number> = card.iter().flatten().flatten().copied().collect();
+
+        let reached = ruleset.evaluate_card(&card, &all_numbers, &BOARDCONFIG);
+        let scores: Vec<Number> = reached.iter().map(|(score, _)| *score).collect();
+        assert_eq!(scores, vec![1, 2, 3]);
+        assert_eq!(ruleset.final_prize(&BOARDCONFIG), 3);
+        let names: Vec<String> = ruleset.prizes(&BOARDCONFIG).into_iter().map(|prize| prize.name).collect();
+        assert_eq!(names, vec!["ONE LINE", "TWO LINES", "FULL HOUSE"]);
+    }
+
+    fn bingo75_card() -> Card {
+        vec![
+            vec![Some(1), Some(16), Some(31), Some(46), Some(61)],
+            vec![Some(2), Some(17), Some(32), Some(47), Some(62)],
+            vec![Some(3), Some(18), None, Some(48), Some(63)],
+            vec![Some(4), Some(19), Some(34), Some(49), Some(64)],
+            vec![Some(5), Some(20), Some(35), Some(50), Some(65)],
+        ]
+    }
+
+    #[test]
+    fn test_bingo75_patterns() {
+        let ruleset = RulesetKind::Bingo75.ruleset();
+        let card = bingo75_card();
+        let config = BINGO75_BOARDCONFIG;
+
+        assert!(ruleset.evaluate_card(&card, &[1, 17, 49, 62], &config).is_empty());
+
+        // The N column and the diagonals are complete with the free centre square
+        let reached = ruleset.evaluate_card(&card, &[31, 32, 34, 35], &config);
+        assert_eq!(reached, vec![(1, vec![31, 32, 34, 35])]);
+        let reached = ruleset.evaluate_card(&card, &[1, 17, 49, 65], &config);
+        assert_eq!(reached, vec![(2, vec![1, 17, 49, 65])]);
+
+        // Both diagonals make the X and the four corners
+        let reached = ruleset.evaluate_card(&card, &[1, 17, 49, 65, 61, 47, 19, 5], &config);
+        let scores: Vec<Number> = reached.iter().map(|(score, _)| *score).collect();
+        assert_eq!(scores, vec![2, 3, 4]);
+        assert_eq!(reached[0].1, vec![1, 17, 49, 65]);
+        assert_eq!(reached[1].1, vec![1, 61, 5, 65]);
+        assert_eq!(reached[2].1, vec![1, 5, 17, 19, 47, 49, 61, 65]);
+
+        let all_numbers: Vec<Number> = card.iter().flatten().flatten().copied().collect();
+        let reached = ruleset.evaluate_card(&card, &all_numbers, &config);
+        assert_eq!(reached.last().unwrap(), &(5, all_numbers.clone()));
+        assert_eq!(all_numbers.len(), 24);
+    }
+
+    #[test]
+    fn test_bingo75_board_validation() {
+        let ruleset = RulesetKind::Bingo75.ruleset();
+        assert!(!ruleset.is_progressive());
+        assert_eq!(ruleset.card_layout(), CardLayout::Bingo75);
+        assert!(ruleset.board_cards(&BINGO75_BOARDCONFIG).is_empty());
+        assert!(ruleset.validate_board(&ruleset.default_board_config()).is_ok());
+        assert!(ruleset.validate_board(&BOARDCONFIG).is_err());
+        assert_eq!(BINGO75_BOARDCONFIG.total_numbers(), 75);
+    }
+
+    #[test]
+    fn test_split_board_in_cards() {
+        let cards = split_board_in_cards(&BOARDCONFIG);
+        assert_eq!(cards.len(), 6);
+        assert_eq!(cards[0][0], vec![Some(1), Some(2), Some(3), Some(4), Some(5)]);
+        assert_eq!(cards[1][0], vec![Some(6), Some(7), Some(8), Some(9), Some(10)]);
+        assert_eq!(cards[5][2], vec![Some(86), Some(87), Some(88), Some(89), Some(90)]);
+    }
+
+    #[test]
+    fn test_ruleset_kind_from_str() {
+        assert_eq!("tombola".parse::<RulesetKind>(), Ok(RulesetKind::Tombola));
+        assert_eq!("TOMBOLA".parse::<RulesetKind>(), Ok(RulesetKind::Tombola));
+        assert_eq!("housie".parse::<RulesetKind>(), Ok(RulesetKind::Housie));
+        assert_eq!("Bingo75".parse::<RulesetKind>(), Ok(RulesetKind::Bingo75));
+        assert!("poker".parse::<RulesetKind>().is_err());
+        assert_eq!(serde_json::to_string(&RulesetKind::Tombola).unwrap(), "\"tombola\"");
+    }
+}
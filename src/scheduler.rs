@@ -0,0 +1,234 @@
+// src/scheduler.rs
+// Background cleanup of the game registry: timestamps the closure of games, archives closed games
+// to data/games and removes them after the retention time, and expires new games abandoned before their first draw.
+// Games that ran out of numbers without their last prize, as claim games can, are archived like closed games.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::config::ServerConfig;
+use crate::game::{Game, GameRegistry, GameStatus, DUMP_DIR};
+use crate::logging::{log, LogLevel};
+use crate::server::AppState;
+
+const MODULE_NAME: &str = "scheduler";
+
+/// How long games are kept in the registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CleanupPolicy {
+    /// Time a closed game is kept after its closure, or a game with an empty pouch after its last change,
+    /// `None` keeps them
+    pub closed_retention: Option<Duration>,
+    /// Time after its creation a game with no extraction is removed, `None` keeps new games
+    pub new_game_expiry: Option<Duration>,
+}
+
+impl CleanupPolicy {
+    /// Policy of the server configuration, where 0 disables a removal
+    #[must_use] pub fn from_config(config: &ServerConfig) -> Self {
+        let duration = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        Self {
+            closed_retention: duration(config.closed_game_retention),
+            new_game_expiry: duration(config.new_game_expiry),
+        }
+    }
+}
+
+/// Games removed by a cleanup run
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CleanupReport {
+    /// Closed games and games with an empty pouch archived and removed
+    pub archived: Vec<String>,
+    /// New games removed as abandoned
+    pub expired: Vec<String>,
+}
+
+impl CleanupReport {
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.archived.is_empty() && self.expired.is_empty()
+    }
+}
+
+/// Remove the games past the retention times of the policy at the given time
+/// Closed games and games with an empty pouch are dumped to JSON in `archive_dir` before their removal and kept if the dump fails
+pub fn cleanup_games(game_registry: &GameRegistry, policy: &CleanupPolicy, archive_dir: &Path, now: SystemTime) -> Result<CleanupReport, String> {
+    let elapsed_since = |time: SystemTime| now.duration_since(time).unwrap_or_default();
+    let mut report = CleanupReport::default();
+
+    for entry in game_registry.entries()? {
+        match entry.status() {
+            GameStatus::Closed => {
+                let (Some(retention), Some(closed_at)) = (policy.closed_retention, entry.closed_at) else {
+                    continue;
+                };
+                if elapsed_since(closed_at) < retention {
+                    continue;
+                }
+                if archive_game(game_registry, &entry.game_id, &entry.game, archive_dir)? {
+                    report.archived.push(entry.game_id);
+                }
+            }
+            GameStatus::New => {
+                let Some(expiry) = policy.new_game_expiry else {
+                    continue;
+                };
+                // The first draw may have happened since the entries were read
+                if elapsed_since(entry.game.created_at()) < expiry || entry.game.has_game_started() {
+                    continue;
+                }
+                if game_registry.remove_game(&entry.game_id)? {
+                    log(LogLevel::Info, MODULE_NAME, &format!("Removed game {} abandoned before its first draw", entry.game_id));
+                    report.expired.push(entry.game_id);
+                }
+            }
+            GameStatus::Active => {
+                // A claim game can run out of numbers with its last prize never claimed
+                let (Some(retention), true) = (policy.closed_retention, entry.game.is_pouch_empty()) else {
+                    continue;
+                };
+                let Some(last_change_at) = entry.game.last_change_at() else {
+                    continue;
+                };
+                if elapsed_since(last_change_at) < retention {
+                    continue;
+                }
+                if archive_game(game_registry, &entry.game_id, &entry.game, archive_dir)? {
+                    report.archived.push(entry.game_id);
+                }
+            }
+        }
+    }
+
+    report.archived.sort();
+    report.expired.sort();
+    Ok(report)
+}
+
+// Dump the game to `archive_dir` and remove it from the registry, keeping it if the dump fails
+fn archive_game(game_registry: &GameRegistry, game_id: &str, game: &Game, archive_dir: &Path) -> Result<bool, String> {
+    match game.dump_to_dir(archive_dir) {
+        Ok(dump_message) => {
+            log(LogLevel::Info, MODULE_NAME, &format!("Archived game {game_id}: {dump_message}"));
+        }
+        Err(e) => {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to archive game {game_id}, keeping it: {e}"));
+            return Ok(false);
+        }
+    }
+    game_registry.remove_game(game_id)
+}
+
+/// Clean up the game registry every `interval_secs` seconds (0 disables the cleanup)
+pub async fn run_cleanup(app_state: Arc<AppState>, interval_secs: u64) {
+    if interval_secs == 0 {
+        log(LogLevel::Info, MODULE_NAME, "Game cleanup disabled");
+        return;
+    }
+
+    let policy = CleanupPolicy::from_config(&app_state.config);
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        match cleanup_games(&app_state.game_registry, &policy, Path::new(DUMP_DIR), SystemTime::now()) {
+            Ok(report) if !report.is_empty() => {
+                log(LogLevel::Info, MODULE_NAME, &format!("Cleanup archived {} ended and removed {} abandoned games", report.archived.len(), report.expired.len()));
+                // The removed games leave the store with the next snapshot
+                app_state.persistence.notify_change();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("Failed to clean up games: {e}"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::ManualDraw;
+    use crate::score::{ClaimPenalty, PrizeMode};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_cleanup_games() {
+        let registry = GameRegistry::new();
+
+        let new_game = Arc::new(Game::new());
+        let active_game = Arc::new(Game::new());
+        active_game.extract_number(0, None).unwrap();
+        let closed_game = Arc::new(Game::new());
+        let card = closed_game.assign_cards("player", 1, None).unwrap().0.remove(0);
+        for number in card.card_data.iter().flatten().flatten() {
+            closed_game.extract_number_from(&ManualDraw(*number), 0, None).unwrap();
+        }
+        assert!(closed_game.is_bingo_reached());
+        for game in [&new_game, &active_game, &closed_game] {
+            registry.add_game(game.clone()).unwrap();
+        }
+
+        let policy = CleanupPolicy { closed_retention: Some(HOUR), new_game_expiry: Some(24 * HOUR) };
+        let now = SystemTime::now();
+        let archive_dir = std::env::temp_dir().join(format!("tombola_cleanup_{}", std::process::id()));
+
+        // Within the retention times nothing is removed, but the closure is timestamped
+        assert!(cleanup_games(&registry, &policy, &archive_dir, now).unwrap().is_empty());
+        let closed_entry = registry.entries().unwrap().into_iter().find(|entry| entry.game_id == closed_game.id()).unwrap();
+        assert_eq!(closed_entry.closed_at, closed_game.closed_at());
+
+        // A disabled policy keeps everything
+        let keep = CleanupPolicy { closed_retention: None, new_game_expiry: None };
+        assert!(cleanup_games(&registry, &keep, &archive_dir, now + 48 * HOUR).unwrap().is_empty());
+
+        let report = cleanup_games(&registry, &policy, &archive_dir, now + 2 * HOUR).unwrap();
+        assert_eq!(report, CleanupReport { archived: vec![closed_game.id()], expired: Vec::new() });
+        assert!(archive_dir.join(format!("{}.json", closed_game.id())).exists());
+        std::fs::remove_dir_all(&archive_dir).unwrap();
+
+        let report = cleanup_games(&registry, &policy, &archive_dir, now + 48 * HOUR).unwrap();
+        assert_eq!(report, CleanupReport { archived: Vec::new(), expired: vec![new_game.id()] });
+
+        // Active games are never removed
+        assert_eq!(registry.all_games().unwrap().len(), 1);
+        assert!(registry.get_game(&active_game.id()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_cleanup_archives_exhausted_claim_games() {
+        let registry = GameRegistry::new();
+        let game = Arc::new(Game::new());
+        game.set_prize_mode(PrizeMode::Claim, ClaimPenalty::None).unwrap();
+        game.assign_cards("player", 1, None).unwrap();
+        while !game.is_pouch_empty() {
+            game.extract_number(0, None).unwrap();
+        }
+        registry.add_game(game.clone()).unwrap();
+        let entry = registry.entries().unwrap().remove(0);
+        assert_eq!(entry.status(), GameStatus::Active);
+
+        let policy = CleanupPolicy { closed_retention: Some(HOUR), new_game_expiry: None };
+        let now = SystemTime::now();
+        let archive_dir = std::env::temp_dir().join(format!("tombola_exhausted_{}", std::process::id()));
+        assert!(cleanup_games(&registry, &policy, &archive_dir, now).unwrap().is_empty());
+
+        let report = cleanup_games(&registry, &policy, &archive_dir, now + 2 * HOUR).unwrap();
+        assert_eq!(report, CleanupReport { archived: vec![game.id()], expired: Vec::new() });
+        assert!(archive_dir.join(format!("{}.json", game.id())).exists());
+        std::fs::remove_dir_all(&archive_dir).unwrap();
+        assert!(registry.get_game(&game.id()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cleanup_policy_from_config() {
+        let mut config = ServerConfig::default();
+        assert_eq!(CleanupPolicy::from_config(&config), CleanupPolicy { closed_retention: Some(HOUR), new_game_expiry: Some(24 * HOUR) });
+
+        config.closed_game_retention = 0;
+        config.new_game_expiry = 0;
+        assert_eq!(CleanupPolicy::from_config(&config), CleanupPolicy { closed_retention: None, new_game_expiry: None });
+    }
+}
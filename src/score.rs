@@ -1,9 +1,10 @@
 // tombola/src/score.rs
 // This module handles the scorecard logic and prize checking for the Tombola game.
 
-use crate::defs::{BOARDCONFIG, NUMBERSPERCARD, Number};
-use crate::board::{Board, board_card_id};
-use crate::card::CardAssignmentManager;
+use crate::defs::{BoardStruct, Number, BOARDCONFIG};
+use crate::board::{Board, BOARD_ID, board_card_id};
+use crate::card::{CardAssignment, CardAssignmentManager};
+use crate::ruleset::{Prize, PrizeResult, PrizeRuleset, RulesetKind};
 use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
@@ -13,6 +14,54 @@ pub struct ScoreAchievement {
     pub client_id: String,
     pub card_id: String,
     pub numbers: Vec<Number>,
+    /// Number of extractions on the board when the prize was awarded
+    #[serde(default)]
+    pub draw_index: usize,
+}
+
+/// How prizes are awarded in a game
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrizeMode {
+    /// The server awards prizes after every extraction
+    #[default]
+    Automatic,
+    /// Players claim prizes via `/{game_id}/claim` and the server verifies them
+    Claim,
+}
+
+/// Penalty applied to a card making a false claim
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimPenalty {
+    /// The claim is only rejected
+    #[default]
+    None,
+    /// The card cannot claim until the given number of further extractions has been made
+    Suspend(usize),
+    /// The card cannot claim any more prizes in this game
+    Disqualify,
+}
+
+// Claim request body
+#[derive(Debug, Deserialize)]
+pub struct ClaimRequest {
+    pub card_id: String,
+    pub prize: Number,
+}
+
+/// Outcome of a verified claim
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimVerdict {
+    /// The claim is valid and the prize with the given score has been recorded
+    Accepted(Number),
+    /// The card reached the prize before the last extraction
+    Late,
+    /// The prize has already been awarded at an earlier extraction
+    Unavailable,
+    /// The card has not reached the prize, the penalty has been applied
+    False,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
@@ -20,14 +69,110 @@ pub struct ScoreCard {
     /// Official recorded achievement level - used for API responses and client display
     pub published_score: Number,
     pub score_map: HashMap<Number, Vec<ScoreAchievement>>, // score_idx -> Vec<ScoreAchievement>
+    /// Board geometry used to locate lines and cards on the board
+    #[serde(default)]
+    pub board_config: BoardStruct,
+    /// Prize rules of this game
+    #[serde(default)]
+    pub ruleset: RulesetKind,
+    /// Full-card prizes awarded before the game closes (TOMBOLA, TOMBOLINO, ...), 0 is read as 1
+    #[serde(default)]
+    pub full_card_prizes: Number,
+    /// Prizes of the game, keyed like `score_map`
+    #[serde(default)]
+    pub prizes: Vec<Prize>,
+    /// Whether prizes are awarded automatically or claimed by the players
+    #[serde(default)]
+    pub prize_mode: PrizeMode,
+    /// Penalty for false claims
+    #[serde(default)]
+    pub claim_penalty: ClaimPenalty,
+    /// Cards penalized for false claims -> draw index from which they can claim again
+    #[serde(default)]
+    pub suspended_cards: HashMap<String, usize>,
+    /// False claims in order, as card ID and draw index of the claim, to lift their penalties on undo
+    #[serde(default)]
+    pub false_claims: Vec<(String, usize)>,
 }
 
 impl ScoreCard {
     #[must_use] pub fn new() -> Self {
+        Self::with_config(BOARDCONFIG)
+    }
+
+    /// Create an empty scorecard for the given board geometry
+    #[must_use] pub fn with_config(board_config: BoardStruct) -> Self {
+        Self::with_rules(board_config, RulesetKind::default())
+    }
+
+    /// Create an empty scorecard for the given board geometry and prize ruleset
+    #[must_use] pub fn with_rules(board_config: BoardStruct, ruleset: RulesetKind) -> Self {
         ScoreCard {
             published_score: 0,
             score_map: HashMap::new(),
+            board_config,
+            ruleset,
+            full_card_prizes: 1,
+            prizes: ruleset.ruleset().prizes(&board_config),
+            prize_mode: PrizeMode::default(),
+            claim_penalty: ClaimPenalty::default(),
+            suspended_cards: HashMap::new(),
+            false_claims: Vec::new(),
+        }
+    }
+
+    /// Set how prizes are awarded and the penalty for false claims
+    pub fn set_prize_mode(&mut self, prize_mode: PrizeMode, claim_penalty: ClaimPenalty) {
+        self.prize_mode = prize_mode;
+        self.claim_penalty = claim_penalty;
+    }
+
+    fn rules(&self) -> &'static dyn PrizeRuleset {
+        self.ruleset.ruleset()
+    }
+
+    /// Set how many full-card prizes are awarded before the game closes
+    pub fn set_full_card_prizes(&mut self, full_card_prizes: Number) {
+        self.full_card_prizes = full_card_prizes.max(1);
+        self.prizes = self.game_prizes();
+    }
+
+    /// Number of full-card prizes awarded before the game closes
+    #[must_use] pub fn full_card_prizes(&self) -> Number {
+        self.full_card_prizes.max(1)
+    }
+
+    /// All the prizes of the game: the ruleset prizes followed by the extra full-card ranks
+    #[must_use] pub fn game_prizes(&self) -> Vec<Prize> {
+        let rules = self.rules();
+        let first_full_card = rules.final_prize(&self.board_config);
+        let mut prizes = rules.prizes(&self.board_config);
+        for rank in 2..=self.full_card_prizes() {
+            prizes.push(Prize::new(first_full_card + rank - 1, &rules.full_card_prize_name(rank, &self.board_config)));
         }
+        prizes
+    }
+
+    /// Check if the score is one of the full-card prizes (the first one or a later rank)
+    #[must_use] pub fn is_full_card_score(&self, score: Number) -> bool {
+        (self.rules().final_prize(&self.board_config)..=self.final_score()).contains(&score)
+    }
+
+    /// Score of the prize that ends the game: the last full-card rank
+    #[must_use] pub fn final_score(&self) -> Number {
+        self.rules().final_prize(&self.board_config) + self.full_card_prizes() - 1
+    }
+
+    /// Check if the prize that ends the game has been awarded
+    #[must_use] pub fn is_game_over(&self) -> bool {
+        self.published_score >= self.final_score()
+    }
+
+    /// Display name of the prize recorded at the given `score_map` index
+    #[must_use] pub fn prize_name(&self, score: Number) -> String {
+        self.game_prizes().into_iter()
+            .find(|prize| prize.score == score)
+            .map_or_else(|| format!("{score} in line"), |prize| prize.name)
     }
 
     #[must_use] pub fn get_scorecard(&self) -> Number {
@@ -38,437 +183,317 @@ impl ScoreCard {
         &self.score_map
     }
 
+    /// Number of achievements recorded for each prize, to find the ones awarded by an operation
+    #[must_use] pub fn achievement_counts(&self) -> HashMap<Number, usize> {
+        self.score_map.iter().map(|(score, achievements)| (*score, achievements.len())).collect()
+    }
+
+    /// Achievements recorded after the given `achievement_counts`, in prize order
+    #[must_use] pub fn achievements_since(&self, counts: &HashMap<Number, usize>) -> Vec<(Number, ScoreAchievement)> {
+        let mut awarded: Vec<(Number, ScoreAchievement)> = self.score_map.iter()
+            .flat_map(|(score, achievements)| achievements.iter()
+                .skip(counts.get(score).copied().unwrap_or(0))
+                .map(|achievement| (*score, achievement.clone())))
+            .collect();
+        awarded.sort_by_key(|(score, _)| *score);
+        awarded
+    }
+
     pub fn update_scorecard(&mut self, score: Number) {
         self.published_score = score;
     }
 
-    #[must_use] pub fn allcards_calculate_score(&self, board_numbers: &[Number], card_assignments: &std::collections::HashMap<String, crate::card::CardAssignment>) -> (Number, Vec<(String, Vec<Number>)>) {
-        let mut card_details = Vec::new();
-        let current_published_score = self.published_score; // Get the current published score value
-        let mut global_score = 0;
-
-        // Helper function to calculate score and contributing numbers for a card
-        let calculate_card_score = |assignment: &crate::card::CardAssignment| -> (Number, Vec<Number>) {
-            // Extract all numbers from the card
-            let mut card_numbers = Vec::new();
-            for row in &assignment.card_data {
-                for &number in row.iter().flatten() {
-                    card_numbers.push(number);
+    // Best prizes reached by the cards the board is made of, first board card wins ties
+    fn board_prizes(&self, board_numbers: &[Number]) -> Vec<PrizeResult> {
+        let rules = self.rules();
+        let mut prizes: Vec<PrizeResult> = Vec::new();
+        for card in rules.board_cards(&self.board_config) {
+            for (score, numbers) in rules.evaluate_card(&card, board_numbers, &self.board_config) {
+                if !prizes.iter().any(|(prize_score, _)| *prize_score == score) {
+                    prizes.push((score, numbers));
                 }
             }
+        }
+        prizes
+    }
 
-            // Calculate how many card numbers have been extracted
-            let mut extracted_card_numbers = Vec::new();
-            for &card_number in &card_numbers {
-                if board_numbers.contains(&card_number) {
-                    extracted_card_numbers.push(card_number);
-                }
-            }
+    /// Highest prize reached on the board and, when it is a new achievement, the numbers to mark
+    #[must_use] pub fn board_calculate_score(&self, board_numbers: &[Number]) -> (Number, Vec<Number>) {
+        let best = self.board_prizes(board_numbers)
+            .into_iter()
+            .max_by_key(|(score, _)| *score);
+
+        match best {
+            Some((score, numbers)) if score > self.published_score => (score, numbers),
+            Some((score, _)) => (score, Vec::new()),
+            None => (0, Vec::new()),
+        }
+    }
 
-            // Calculate the score for this specific card
-            if extracted_card_numbers.len() == card_numbers.len() && !card_numbers.is_empty() {
-                // Full card (BINGO) - all extracted numbers contribute
-                (NUMBERSPERCARD, extracted_card_numbers)
-            } else if !extracted_card_numbers.is_empty() {
-                // Check for line completions within this card
-                let mut max_line_score = 0;
-                let mut best_line_numbers = Vec::new();
-
-                // Check each row in the card (3 rows)
-                for row_index in 0..3 {
-                    let row = &assignment.card_data[row_index];
-                    let mut row_extracted_numbers = Vec::new();
-
-                    for &number in row.iter().flatten() {
-                        if board_numbers.contains(&number) {
-                            row_extracted_numbers.push(number);
-                        }
-                    }
-
-                    // Update max line score if this row has more extracted numbers
-                    if row_extracted_numbers.len() > max_line_score {
-                        max_line_score = row_extracted_numbers.len();
-                        best_line_numbers = row_extracted_numbers;
-                    }
-                }
+    /// Drop the prizes and penalties of the extractions undone, leaving the given board numbers
+    pub fn rollback_to(&mut self, board_numbers: &[Number]) {
+        let draw_index = board_numbers.len();
+        for achievements in self.score_map.values_mut() {
+            achievements.retain(|achievement| achievement.draw_index <= draw_index);
+        }
+        self.score_map.retain(|_, achievements| !achievements.is_empty());
 
-                // Return the highest line score (2, 3, 4, or 5) and the numbers that made it
-                if max_line_score >= 2 {
-                    (max_line_score as Number, best_line_numbers)
-                } else {
-                    (0, Vec::new())
-                }
-            } else {
-                (0, Vec::new())
-            }
+        // The board advances the score in automatic mode even when it has no owner to record it
+        let board_score = match self.prize_mode {
+            PrizeMode::Automatic => self.board_prizes(board_numbers).iter().map(|(score, _)| *score).max().unwrap_or(0),
+            PrizeMode::Claim => 0,
         };
-
-        // Iterate through all card assignments, but exclude the board card
-        for (card_id, assignment) in card_assignments {
-            // Skip the board card - it should only be processed by board_calculate_score
-            if card_id == &board_card_id() {
-                continue;
-            }
-
-            let (card_score, contributing_numbers) = calculate_card_score(assignment);
-
-            // Update the global score to the highest score achieved by any card
-            if card_score > global_score {
-                global_score = card_score;
-            }
-
-            // Only include cards that have achieved a meaningful score (>= 2) and have contributing numbers
-            if card_score >= 2 && !contributing_numbers.is_empty() {
-                card_details.push((card_id.to_string(), contributing_numbers));
+        self.published_score = self.score_map.keys().max().copied().unwrap_or(0).max(board_score);
+
+        // A penalty undone gives the card back the one of its previous false claim, if any
+        let undone: Vec<String> = self.false_claims.iter()
+            .filter(|(_, claimed_at)| *claimed_at > draw_index)
+            .map(|(card_id, _)| card_id.clone())
+            .collect();
+        self.false_claims.retain(|(_, claimed_at)| *claimed_at <= draw_index);
+        for card_id in undone {
+            self.suspended_cards.remove(&card_id);
+            let previous = self.false_claims.iter().rev().find(|(claimed_by, _)| *claimed_by == card_id).map(|(_, claimed_at)| *claimed_at);
+            if let Some(allowed_from) = previous.and_then(|claimed_at| self.penalty_end(claimed_at)) {
+                self.suspended_cards.insert(card_id, allowed_from);
             }
         }
-
-        // Only return results if the global score is greater than the current published score
-        if global_score > current_published_score {
-            // Filter card_details to only include cards that achieved the global score
-            let filtered_card_details: Vec<(String, Vec<Number>)> = card_details.into_iter()
-                .filter(|(card_id, _)| {
-                    // Use the helper function to recalculate the score for this card
-                    if let Some(assignment) = card_assignments.get(card_id) {
-                        let (card_score, _) = calculate_card_score(assignment);
-                        card_score == global_score
-                    } else {
-                        false
-                    }
-                })
-                .collect();
-
-            (global_score, filtered_card_details)
-        } else {
-            (0, Vec::new())
-        }
     }
 
-    #[must_use] pub fn board_calculate_score(&self, board_numbers: &[Number]) -> (Number, Vec<Number>) {
-        // Calculate score based on the last extracted number
-        if let Some(&last_number) = board_numbers.last() {
-            self.board_score_check(board_numbers, last_number)
-        } else {
-            (0, Vec::new())
+    // Draw index from which a card making a false claim at the given draw can claim again
+    fn penalty_end(&self, draw_index: usize) -> Option<usize> {
+        match self.claim_penalty {
+            ClaimPenalty::None => None,
+            ClaimPenalty::Suspend(draws) => Some(draw_index + draws + 1),
+            ClaimPenalty::Disqualify => Some(usize::MAX),
         }
     }
 
-    #[must_use] pub fn board_score_check(&self, board_numbers: &[Number], extracted: Number) -> (Number, Vec<Number>) {
-        // Calculate score based on the last extracted number
-        let numbers_per_row = (BOARDCONFIG.cols_per_card * BOARDCONFIG.cards_per_row) as i8;
-
-        // Store the previous published score value BEFORE any updates
-        let previous_published_score = self.published_score;
-        let mut new_scorecard = 0;
+    /// Numbers of the highest prize won by the board card, as marked on the board
+    #[must_use] pub fn board_marked_numbers(&self) -> Vec<Number> {
+        self.score_map.iter()
+            .filter_map(|(score, achievements)| {
+                achievements.iter()
+                    .find(|achievement| achievement.card_id == BOARD_ID)
+                    .map(|achievement| (*score, achievement.numbers.clone()))
+            })
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, numbers)| numbers)
+            .unwrap_or_default()
+    }
 
-        // Calculate extracted number's position for line checking
-        let extracted_ypos = (extracted as i8 - 1) / numbers_per_row + 1;
-        let extracted_xpos = (extracted as i8 - 1) % numbers_per_row;
-        let extracted_card = extracted_xpos / BOARDCONFIG.cols_per_card as i8;
+    // Calculate and update the best score from both board and card scores
+    pub fn calculate_and_update_best_score(
+        &mut self,
+        board: &Board,
+        card_manager: &CardAssignmentManager,
+        current_working_score: Number,
+        board_client_id: Option<&str>
+    ) -> Number {
+        let rules = self.rules();
+        let board_numbers = board.get_numbers();
+        let working_score = std::cmp::max(current_working_score, self.published_score);
 
-        // Check for complete cards (15 numbers) first
-        let total_cards = (BOARDCONFIG.cards_per_row * BOARDCONFIG.cards_per_col) as i8;
+        // In claim mode prizes are only recorded by verified claims
+        if self.prize_mode == PrizeMode::Claim {
+            return working_score;
+        }
 
-        let mut bingo_found = false;
-        for card_index in 0..total_cards {
-            let card_row = card_index / BOARDCONFIG.cards_per_row as i8;
-            let card_col = card_index % BOARDCONFIG.cards_per_row as i8;
+        // Evaluate every player card (the board card is scored through the board owner below)
+        let mut card_results: Vec<(String, String, Vec<PrizeResult>)> = card_manager.get_all_assignments()
+            .iter()
+            .filter(|(card_id, _)| card_id.as_str() != BOARD_ID)
+            .map(|(card_id, assignment)| (
+                assignment.client_id.clone(),
+                card_id.clone(),
+                rules.evaluate_card(&assignment.card_data, board_numbers, &self.board_config),
+            ))
+            .collect();
+        // Keep achievements in a stable order
+        card_results.sort_by(|a, b| a.1.cmp(&b.1));
+
+        // The board always advances the score, but its achievements are recorded only for the board owner
+        let board_results = self.board_prizes(board_numbers);
+
+        // Award every prize reached for the first time to all the cards reaching it with this extraction
+        let mut best_score = working_score;
+        for prize in rules.prizes(&self.board_config) {
+            if self.score_map.contains_key(&prize.score) {
+                continue; // Achievements are preserved permanently
+            }
+            if rules.is_progressive() && prize.score <= working_score {
+                continue; // A higher prize has already been awarded
+            }
 
-            let mut card_numbers_found = 0;
-            let mut card_numbers: Vec<Number> = Vec::new();
+            let mut achievements: Vec<ScoreAchievement> = card_results.iter()
+                .filter_map(|(client_id, card_id, reached)| {
+                    reached.iter()
+                        .find(|(score, _)| *score == prize.score)
+                        .map(|(_, numbers)| ScoreAchievement {
+                            client_id: client_id.clone(),
+                            card_id: card_id.clone(),
+                            numbers: numbers.clone(),
+                            draw_index: board_numbers.len(),
+                        })
+                })
+                .collect();
 
-            // Check all numbers in this card
-            for row in 0..BOARDCONFIG.rows_per_card as i8 {
-                for col in 0..BOARDCONFIG.cols_per_card as i8 {
-                    let number = 1 + (card_row * BOARDCONFIG.rows_per_card as i8 + row) * numbers_per_row +
-                               card_col * BOARDCONFIG.cols_per_card as i8 + col;
+            let board_reached = board_results.iter().find(|(score, _)| *score == prize.score);
+            if let Some((_, numbers)) = board_reached
+                && let Some(client_id) = board_client_id {
+                achievements.push(ScoreAchievement {
+                    client_id: client_id.to_string(),
+                    card_id: board_card_id(),
+                    numbers: numbers.clone(),
+                    draw_index: board_numbers.len(),
+                });
+            }
 
-                    card_numbers.push(number as Number);
+            if board_reached.is_some() || !achievements.is_empty() {
+                best_score = std::cmp::max(best_score, prize.score);
+            }
+            if !achievements.is_empty() {
+                self.score_map.insert(prize.score, achievements);
+            }
+        }
 
-                    if board_numbers.contains(&(number as Number)) {
-                        card_numbers_found += 1;
-                    }
-                }
+        // Later full-card prizes go, one rank per extraction, to the cards completed after the previous rank
+        let first_full_card = rules.final_prize(&self.board_config);
+        if let Some(client_id) = board_client_id {
+            card_results.push((client_id.to_string(), board_card_id(), board_results));
+        }
+        for score in first_full_card + 1..=self.final_score() {
+            if self.score_map.contains_key(&score) {
+                continue;
+            }
+            if !self.score_map.contains_key(&(score - 1)) {
+                break; // The previous rank has not been awarded yet
             }
 
-            // If this card is complete (15 numbers), set scorecard to 15
-            if card_numbers_found == NUMBERSPERCARD {
-                new_scorecard = NUMBERSPERCARD;
-                bingo_found = true;
-                break;
+            let winners: Vec<&str> = self.score_map.iter()
+                .filter(|(prize_score, _)| self.is_full_card_score(**prize_score))
+                .flat_map(|(_, achievements)| achievements.iter().map(|achievement| achievement.card_id.as_str()))
+                .collect();
+            let achievements: Vec<ScoreAchievement> = card_results.iter()
+                .filter(|(_, card_id, _)| !winners.contains(&card_id.as_str()))
+                .filter_map(|(client_id, card_id, reached)| {
+                    reached.iter()
+                        .find(|(prize_score, _)| *prize_score == first_full_card)
+                        .map(|(_, numbers)| ScoreAchievement {
+                            client_id: client_id.clone(),
+                            card_id: card_id.clone(),
+                            numbers: numbers.clone(),
+                            draw_index: board_numbers.len(),
+                        })
+                })
+                .collect();
+
+            if !achievements.is_empty() {
+                self.score_map.insert(score, achievements);
+                best_score = std::cmp::max(best_score, score);
             }
+            break;
         }
 
-        // Only do line scoring if no BINGO was found
-        if !bingo_found {
-            // Count numbers in the same row and card as the extracted number (for line scoring)
-            let mut same_row_card_count = 0;
+        // Only update the published score when it actually increases
+        if best_score > self.published_score {
+            self.update_scorecard(best_score);
+        }
+        best_score
+    }
 
-            for &num in board_numbers {
-                if num == extracted {
-                    continue; // Skip the extracted number itself for counting
-                }
+    // Draw index at which the card first reached the prize with the given score
+    fn reached_at(&self, card: &CardAssignment, score: Number, board_numbers: &[Number]) -> Option<usize> {
+        let rules = self.rules();
+        (1..=board_numbers.len()).find(|&draw| {
+            rules.evaluate_card(&card.card_data, &board_numbers[..draw], &self.board_config)
+                .iter()
+                .any(|(prize_score, _)| *prize_score == score)
+        })
+    }
 
-                let num_ypos = (num as i8 - 1) / numbers_per_row + 1;
-                let num_xpos = (num as i8 - 1) % numbers_per_row;
-                let num_card = num_xpos / BOARDCONFIG.cols_per_card as i8;
+    /// Verify a claim for the prize with the given score made with the card at the current draw.
+    /// Valid claims are recorded in `score_map`, false claims get the penalty of the game.
+    /// Returns an error when the claim cannot be evaluated at all.
+    pub fn claim_prize(&mut self, board: &Board, card: &CardAssignment, prize: Number) -> Result<ClaimVerdict, String> {
+        if self.prize_mode != PrizeMode::Claim {
+            return Err("Prizes are awarded automatically in this game".to_string());
+        }
+        if card.card_id == BOARD_ID {
+            return Err("The board card cannot claim prizes".to_string());
+        }
+        if self.is_game_over() {
+            return Err("Game over: no more prizes can be claimed".to_string());
+        }
+        if !self.game_prizes().iter().any(|game_prize| game_prize.score == prize) {
+            return Err(format!("Unknown prize {prize}"));
+        }
+        let draw_index = board.len();
+        if let Some(&allowed_from) = self.suspended_cards.get(&card.card_id)
+            && draw_index < allowed_from {
+            return Err(if allowed_from == usize::MAX {
+                format!("Card {} is disqualified from claiming prizes", card.card_id)
+            } else {
+                format!("Card {} cannot claim prizes before draw {allowed_from}", card.card_id)
+            });
+        }
 
-                // Check if same row and same card
-                if num_ypos == extracted_ypos && num_card == extracted_card {
-                    same_row_card_count += 1;
-                }
-            }
+        // Every full-card rank is verified as the first full-card prize
+        let rules = self.rules();
+        let first_full_card = rules.final_prize(&self.board_config);
+        let evaluated = if self.is_full_card_score(prize) { first_full_card } else { prize };
 
-            // Update scorecard for line scoring
-            // Only update if we haven't achieved this goal before
-            let current_line_score = same_row_card_count + 1;
-            if current_line_score > previous_published_score {
-                new_scorecard = current_line_score;
-            } else {
-                new_scorecard = previous_published_score;
+        let Some(reached_at) = self.reached_at(card, evaluated, board.get_numbers()) else {
+            if let Some(allowed_from) = self.penalty_end(draw_index) {
+                self.suspended_cards.insert(card.card_id.clone(), allowed_from);
             }
+            self.false_claims.push((card.card_id.clone(), draw_index));
+            return Ok(ClaimVerdict::False);
+        };
+        if reached_at != draw_index {
+            return Ok(ClaimVerdict::Late);
         }
 
-        // Return numbers to mark based on scorecard achievement
-        // Only return numbers if we just achieved a NEW score (higher than previous)
-        let is_new_achievement = new_scorecard > previous_published_score;
-
-        let numbers_to_mark = if is_new_achievement {
-            match new_scorecard {
-                2..=5 => {
-                    // Return numbers that are part of the current scoring line
-                    let mut numbers_to_mark = Vec::new();
-                    for &num in board_numbers {
-                        let num_ypos = (num as i8 - 1) / numbers_per_row + 1;
-                        let num_xpos = (num as i8 - 1) % numbers_per_row;
-                        let num_card = num_xpos / BOARDCONFIG.cols_per_card as i8;
-
-                        // Only mark if it's in the same row and card as the extracted number
-                        if num_ypos == extracted_ypos && num_card == extracted_card {
-                            numbers_to_mark.push(num);
-                        }
-                    }
-                    numbers_to_mark
-                },
-                x if x == NUMBERSPERCARD => {
-                    // Return all numbers in the completed card
-                    for card_index in 0..total_cards {
-                        let card_row = card_index / BOARDCONFIG.cards_per_row as i8;
-                        let card_col = card_index % BOARDCONFIG.cards_per_row as i8;
-
-                        let mut card_numbers_found = 0;
-                        let mut card_numbers: Vec<Number> = Vec::new();
-
-                        // Collect all numbers in this card (regardless of extraction)
-                        for row in 0..BOARDCONFIG.rows_per_card as i8 {
-                            for col in 0..BOARDCONFIG.cols_per_card as i8 {
-                                let number = 1 + (card_row * BOARDCONFIG.rows_per_card as i8 + row) * numbers_per_row +
-                                           card_col * BOARDCONFIG.cols_per_card as i8 + col;
-
-                                card_numbers.push(number as Number);
-
-                                if board_numbers.contains(&(number as Number)) {
-                                    card_numbers_found += 1;
-                                }
-                            }
-                        }
-
-                        // If this card is complete, return all its extracted numbers
-                        if card_numbers_found == NUMBERSPERCARD {
-                            return (new_scorecard, board_numbers.iter()
-                                .filter(|&num| card_numbers.contains(num))
-                                .copied()
-                                .collect());
-                        }
-                    }
-                    Vec::new()
-                },
-                _ => Vec::new()
+        // Pick the prize to record: a prize awarded at this draw is shared, full-card prizes move to the next rank
+        let awarded_now = |score: Number| {
+            self.score_map.get(&score).is_some_and(|achievements| achievements.iter().all(|achievement| achievement.draw_index == draw_index))
+        };
+        let score = if evaluated == first_full_card {
+            let last_rank = (first_full_card..=self.final_score()).rev().find(|score| self.score_map.contains_key(score));
+            let already_won = self.score_map.iter()
+                .filter(|(score, _)| self.is_full_card_score(**score))
+                .any(|(_, achievements)| achievements.iter().any(|achievement| achievement.card_id == card.card_id));
+            match last_rank {
+                Some(_) if already_won => return Ok(ClaimVerdict::Unavailable),
+                Some(last_rank) if awarded_now(last_rank) => last_rank,
+                Some(last_rank) => last_rank + 1,
+                None => first_full_card,
             }
         } else {
-            Vec::new()
-        };
-
-        (new_scorecard, numbers_to_mark)
-    }
-
-    // Calculate and update the best score from both board and card scores
-    pub fn calculate_and_update_best_score(
-        &mut self,
-        board: &Board,
-        card_manager: &CardAssignmentManager,
-        current_working_score: Number,
-        board_client_id: Option<&str>
-    ) -> Number {
-        // Get board numbers internally
-        let board_numbers = board.get_numbers();
-
-        // Get card assignments directly from the card manager
-        let card_assignments = card_manager.get_all_assignments();
-
-        // Calculate board score internally
-        let (boardscore_value, board_numbers_contributing) = self.board_calculate_score(board_numbers);
-
-        // Calculate scores for all cards
-        let (allcardscore_value, card_details) = self.allcards_calculate_score(board_numbers, card_assignments);
-
-        let bestscore = std::cmp::max(allcardscore_value, boardscore_value);
-
-        // Always maintain score_map by preserving existing achievements and only adding new ones
-        // This ensures achievements are never lost between iterations
-        if bestscore >= 2 {
-            // If this is a new achievement level (higher than what we had before)
-            if bestscore > current_working_score {
-                // Special handling for BINGO (15) - it's a unique achievement, not a line progression
-                if bestscore == NUMBERSPERCARD {
-                    // BINGO is achieved - only store legitimate BINGO achievements
-                    let mut bingo_achievements = Vec::new();
-
-                    // Check for card BINGO achievements
-                    if allcardscore_value == NUMBERSPERCARD {
-                        // Only include cards that actually have all 15 numbers extracted
-                        for (card_id, _) in &card_details {
-                            if let Some(assignment) = card_assignments.get(card_id) {
-                                // Count how many numbers from this card have been extracted
-                                let mut card_numbers = Vec::new();
-                                for row in &assignment.card_data {
-                                    for &number in row.iter().flatten() {
-                                        card_numbers.push(number);
-                                    }
-                                }
-
-                                let extracted_from_card: Vec<Number> = card_numbers.iter()
-                                    .filter(|&&num| board_numbers.contains(&num))
-                                    .copied()
-                                    .collect();
-
-                                // Only include if this card has exactly 15 extracted numbers (BINGO)
-                                if extracted_from_card.len() == NUMBERSPERCARD as usize {
-                                    bingo_achievements.push(ScoreAchievement {
-                                        client_id: card_manager.get_client_id_for_card(card_id),
-                                        card_id: card_id.to_string(),
-                                        numbers: extracted_from_card,
-                                    });
-                                }
-                            }
-                        }
-                    }
-
-                    // Check for board BINGO achievement
-                    if boardscore_value == NUMBERSPERCARD {
-                        if let Some(client_id) = board_client_id {
-                            bingo_achievements.push(ScoreAchievement {
-                                client_id: client_id.to_string(),
-                                card_id: board_card_id(),
-                                numbers: board_numbers_contributing.clone(),
-                            });
-                        }
-                    }
-
-                    // Only store BINGO achievements if we have any legitimate ones
-                    if !bingo_achievements.is_empty() {
-                        self.score_map.insert(NUMBERSPERCARD, bingo_achievements);
-                    }
-
-                    // When BINGO is reached, we don't process regular line achievements
-                    // BINGO is the final achievement
-                } else {
-                    // Regular line achievements (2-5) - store achievements for all levels from 2 to bestscore
-                    // This ensures we don't lose previous achievements when reaching a higher level
-                    // NOTE: Only store achievements for valid line levels (2-5), not intermediate levels
-
-                    for achievement_level in 2..=std::cmp::min(bestscore, 5) {
-                        // Use entry API to avoid double lookup
-                        if let std::collections::hash_map::Entry::Vacant(e) = self.score_map.entry(achievement_level) {
-                            let mut level_achievements = Vec::new();
-
-                            if achievement_level == bestscore {
-                                // For the current best score, use the actual current achievements
-                                if allcardscore_value >= boardscore_value {
-                                    // Add card achievements that achieved this score level
-                                    for (card_id, numbers) in &card_details {
-                                        if numbers.len() as Number == achievement_level {
-                                            level_achievements.push(ScoreAchievement {
-                                                client_id: card_manager.get_client_id_for_card(card_id),
-                                                card_id: card_id.to_string(),
-                                                numbers: numbers.clone(),
-                                            });
-                                        }
-                                    }
-
-                                    // If board score also meets this level, include it
-                                    if boardscore_value == achievement_level {
-                                        if let Some(client_id) = board_client_id {
-                                            level_achievements.push(ScoreAchievement {
-                                                client_id: client_id.to_string(),
-                                                card_id: board_card_id(),
-                                                numbers: board_numbers_contributing.clone(),
-                                            });
-                                        }
-                                    }
-                                } else if boardscore_value == achievement_level {
-                                    // Only board achievement - only create if we have a client ID
-                                    if let Some(client_id) = board_client_id {
-                                        level_achievements.push(ScoreAchievement {
-                                            client_id: client_id.to_string(),
-                                            card_id: board_card_id(),
-                                            numbers: board_numbers_contributing.clone(),
-                                        });
-                                    }
-                                }
-                            } else {
-                                // For lower achievement levels, we need to reconstruct what achievements existed
-                                // This is for cases where we jump from level 2 to level 4, we need to fill in level 3
-
-                                // Check if any card achieved exactly this level
-                                for (card_id, numbers) in &card_details {
-                                    if numbers.len() as Number >= achievement_level {
-                                        // Take only the first 'achievement_level' numbers for this level
-                                        let level_numbers: Vec<Number> = numbers.iter()
-                                            .take(achievement_level as usize)
-                                            .copied()
-                                            .collect();
-                                        level_achievements.push(ScoreAchievement {
-                                            client_id: card_manager.get_client_id_for_card(card_id),
-                                            card_id: card_id.to_string(),
-                                            numbers: level_numbers,
-                                        });
-                                    }
-                                }
-
-                                // Check if board achieved this level
-                                if boardscore_value >= achievement_level {
-                                    if let Some(client_id) = board_client_id {
-                                        // For board, take the first 'achievement_level' numbers from the contributing numbers
-                                        let level_numbers: Vec<Number> = board_numbers_contributing.iter()
-                                            .take(achievement_level as usize)
-                                            .copied()
-                                            .collect();
-                                        level_achievements.push(ScoreAchievement {
-                                            client_id: client_id.to_string(),
-                                            card_id: board_card_id(),
-                                            numbers: level_numbers,
-                                        });
-                                    }
-                                }
-                            }
-
-                            if !level_achievements.is_empty() {
-                                e.insert(level_achievements);
-                            }
-                        }
-                    }
-                }
+            if self.score_map.get(&prize).is_some_and(|achievements| achievements.iter().any(|achievement| achievement.card_id == card.card_id)) {
+                return Ok(ClaimVerdict::Unavailable);
             }
-            // Note: We do NOT touch existing achievements in score_map - they are preserved permanently
+            prize
+        };
+        let higher_awarded = rules.is_progressive() && self.published_score > score && !awarded_now(score);
+        if (self.score_map.contains_key(&score) && !awarded_now(score)) || higher_awarded {
+            return Ok(ClaimVerdict::Unavailable);
         }
 
-        // Only update the published score when it actually increases
-        if bestscore > current_working_score {
-            self.update_scorecard(bestscore);
-            bestscore // Return the new working score
-        } else {
-            current_working_score // Return the unchanged working score
+        let numbers = rules.evaluate_card(&card.card_data, board.get_numbers(), &self.board_config)
+            .into_iter()
+            .find(|(prize_score, _)| *prize_score == evaluated)
+            .map(|(_, numbers)| numbers)
+            .unwrap_or_default();
+        self.score_map.entry(score).or_default().push(ScoreAchievement {
+            client_id: card.client_id.clone(),
+            card_id: card.card_id.clone(),
+            numbers,
+            draw_index,
+        });
+        if score > self.published_score {
+            self.update_scorecard(score);
         }
+        Ok(ClaimVerdict::Accepted(score))
     }
 }
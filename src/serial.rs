@@ -0,0 +1,155 @@
+// src/serial.rs
+// Serial numbers of the cards.
+// A serial is derived from the content of its card with SHA-256, so a card gets the same serial on every server and release,
+// and ends with a check character so that a mistyped serial is detected before it is looked up.
+//
+// Version 1 serials are 14 characters of the Crockford base32 alphabet (digits and letters without I, L, O and U):
+// - the version of the scheme, "1"
+// - 12 characters encoding the card ID, the first 60 bits of the SHA-256 digest of the card
+// - a Luhn mod 32 check character computed over the 13 previous ones, catching any mistyped character
+//   and any swap of two adjacent characters but 0 and Z
+// The digest covers the version, the number of rows and of columns of the card, then its cells row by row,
+// one byte each holding the number or 0 for an empty cell.
+// Serials are read case-insensitively, ignoring hyphens and spaces, with I and L read as 1 and O as 0.
+
+use sha2::{Digest, Sha256};
+
+use crate::card::Card;
+
+/// Version of the serial scheme, first character of the serials
+pub const SERIAL_VERSION: u8 = 1;
+
+/// Length of a serial: version, card ID and check character
+pub const SERIAL_LENGTH: usize = 1 + ID_CHARS + 1;
+
+// Crockford base32 alphabet
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// Characters encoding the 60 bits of a card ID
+const ID_CHARS: usize = 12;
+
+/// ID of a card: the first 60 bits of the SHA-256 digest of its content
+#[must_use] pub fn card_id(card: &Card) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update([SERIAL_VERSION, card.len() as u8, card.first().map_or(0, Vec::len) as u8]);
+    for row in card {
+        hasher.update(row.iter().map(|cell| cell.unwrap_or(0)).collect::<Vec<u8>>());
+    }
+    let digest = hasher.finalize();
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes) >> 4
+}
+
+/// Serial of a card ID
+#[must_use] pub fn serial_of(id: u64) -> String {
+    let mut values = vec![SERIAL_VERSION];
+    values.extend((0..ID_CHARS).rev().map(|index| ((id >> (index * 5)) & 0x1F) as u8));
+    values.push(check_value(&values));
+    values.into_iter().map(|value| ALPHABET[value as usize] as char).collect()
+}
+
+/// Serial of a card
+#[must_use] pub fn card_serial(card: &Card) -> String {
+    serial_of(card_id(card))
+}
+
+/// Check a serial typed or read from a printed card, returning its canonical form
+pub fn parse_serial(serial: &str) -> Result<String, String> {
+    let values = serial.chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| decode_char(c).ok_or_else(|| format!("Invalid character '{c}' in card serial")))
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    if values.len() != SERIAL_LENGTH {
+        return Err(format!("A card serial has {SERIAL_LENGTH} characters, got {}", values.len()));
+    }
+    if values[0] != SERIAL_VERSION {
+        return Err(format!("Unsupported card serial version {}", ALPHABET[values[0] as usize] as char));
+    }
+    let (payload, check) = values.split_at(SERIAL_LENGTH - 1);
+    if check_value(payload) != check[0] {
+        return Err("Wrong check character, the card serial is mistyped".to_string());
+    }
+
+    Ok(values.into_iter().map(|value| ALPHABET[value as usize] as char).collect())
+}
+
+// Value of a Crockford base32 character, with the usual substitutions of similar characters
+fn decode_char(c: char) -> Option<u8> {
+    let c = match c.to_ascii_uppercase() {
+        'I' | 'L' => '1',
+        'O' => '0',
+        c => c,
+    };
+    ALPHABET.iter().position(|symbol| *symbol as char == c).map(|position| position as u8)
+}
+
+// Luhn mod 32 check value of a sequence of base32 values
+fn check_value(values: &[u8]) -> u8 {
+    let n = ALPHABET.len() as u32;
+    let sum: u32 = values.iter().rev().enumerate()
+        .map(|(index, value)| {
+            let addend = u32::from(*value) * if index % 2 == 0 { 2 } else { 1 };
+            addend / n + addend % n
+        })
+        .sum();
+    ((n - sum % n) % n) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_card() -> Card {
+        vec![
+            vec![Some(1), None, Some(23), None, Some(45), None, Some(67), None, Some(89)],
+            vec![None, Some(12), None, Some(34), None, Some(56), None, Some(78), Some(90)],
+            vec![Some(5), Some(17), Some(29), None, Some(41), None, None, Some(73), None],
+        ]
+    }
+
+    #[test]
+    fn test_card_serial_is_stable() {
+        let card = sample_card();
+        let serial = card_serial(&card);
+        assert_eq!(serial.len(), SERIAL_LENGTH);
+        assert!(serial.starts_with('1'));
+        // The scheme is part of the API: a change of this value needs a new serial version
+        assert_eq!(serial, "12HGVZ7Q8JESCZ");
+        assert_eq!(parse_serial(&serial), Ok(serial.clone()));
+
+        let mut other = card.clone();
+        other[0].swap(0, 1);
+        assert_ne!(card_serial(&other), serial);
+    }
+
+    #[test]
+    fn test_parse_serial() {
+        let serial = card_serial(&sample_card());
+
+        // Case, hyphens, spaces and look-alike characters are tolerated
+        let typed = format!("{}-{} {}", &serial[..5], &serial[5..10], &serial[10..]).to_lowercase();
+        assert_eq!(parse_serial(&typed), Ok(serial.clone()));
+        assert_eq!(parse_serial(&serial.replace('1', "l")), Ok(serial.clone()));
+
+        // A mistyped character or a swap of adjacent characters is detected
+        for index in 1..SERIAL_LENGTH {
+            let mut chars: Vec<char> = serial.chars().collect();
+            chars[index] = if chars[index] == 'A' { 'B' } else { 'A' };
+            assert!(parse_serial(&chars.iter().collect::<String>()).is_err());
+
+            let mut chars: Vec<char> = serial.chars().collect();
+            if chars[index - 1] != chars[index] {
+                chars.swap(index - 1, index);
+                assert!(parse_serial(&chars.iter().collect::<String>()).is_err());
+            }
+        }
+
+        assert!(parse_serial(&serial[1..]).is_err());
+        assert!(parse_serial(&format!("2{}", &serial[1..])).is_err());
+        assert!(parse_serial("0000000000000000").is_err());
+        assert!(parse_serial(&serial.replace(&serial[2..3], "U")).is_err());
+    }
+}
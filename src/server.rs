@@ -11,7 +11,11 @@ use crate::config::ServerConfig;
 use crate::logging::{log, LogLevel};
 use crate::game::GameRegistry;
 use crate::client::ClientRegistry;
-use crate::api_handlers::{handle_global_clientinfo, handle_global_clientinfo_by_id, handle_global_register, handle_global_gameslist, handle_global_newgame, handle_join, handle_generatecards, handle_listassignedcards, handle_getassignedcard, handle_board, handle_pouch, handle_scoremap, handle_status, handle_extract, handle_dumpgame, handle_players};
+use crate::persistence::{run_snapshots, Persistence};
+use crate::scheduler::run_cleanup;
+use crate::autorun::start_autorun;
+use crate::store::{open_store, MemoryStore};
+use crate::api_handlers::{handle_global_clientinfo, handle_global_clientinfo_by_id, handle_global_register, handle_rotatekey, handle_revokekey, handle_global_gameslist, handle_global_newgame, handle_join, handle_generatecards, handle_listassignedcards, handle_getassignedcard, handle_verify_card, handle_importcards, handle_assigncards, handle_board, handle_pouch, handle_scoremap, handle_status, handle_verify, handle_extract, handle_claim, handle_undo, handle_dumpgame, handle_setrole, handle_players, handle_ws, handle_events, handle_snapshot};
 
 const MODULE_NAME: &str = "server";
 
@@ -19,38 +23,75 @@ pub struct AppState {
     pub game_registry: GameRegistry,
     pub global_client_registry: ClientRegistry,
     pub config: ServerConfig,
+    pub persistence: Persistence,
 }
 
 #[must_use] pub fn start_server(config: ServerConfig) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
-        // Create the GameRegistry (no initial game)
+        // Create the registries and restore the games and clients of the last snapshot
         let game_registry = GameRegistry::new();
+        let global_client_registry = ClientRegistry::new();
+        let store = open_store(&config).unwrap_or_else(|e| {
+            log(LogLevel::Error, MODULE_NAME, &format!("Failed to open the {:?} store, keeping games in memory: {e}", config.store));
+            Box::new(MemoryStore::new())
+        });
+        let persistence = Persistence::new(store);
+        match persistence.load_snapshot(&game_registry, &global_client_registry) {
+            Ok((game_count, client_count)) => {
+                log(LogLevel::Info, MODULE_NAME, &format!("Restored {game_count} games and {client_count} clients from {}", persistence.store().description()));
+            }
+            Err(e) => {
+                log(LogLevel::Error, MODULE_NAME, &format!("Failed to restore snapshot from {}: {e}", persistence.store().description()));
+            }
+        }
 
         let app_state = Arc::new(AppState {
             game_registry,
-            global_client_registry: ClientRegistry::new(),
+            global_client_registry,
             config: config.clone(),
+            persistence,
         });
 
+        // Save snapshots on every change and periodically
+        tokio::spawn(run_snapshots(app_state.clone(), config.snapshot_interval));
+
+        // Archive and remove closed games after their retention time, expire abandoned new games
+        tokio::spawn(run_cleanup(app_state.clone(), config.cleanup_interval));
+
+        // Resume the unattended games, and open the one of server.conf if enabled
+        start_autorun(&app_state);
+
         let app = Router::new()
             // Client & Game Management routes
             .route("/clientinfo", get(handle_global_clientinfo))
             .route("/clientinfo/{client_id}", get(handle_global_clientinfo_by_id))
             .route("/register", post(handle_global_register))
+            .route("/rotatekey", post(handle_rotatekey))
+            .route("/revokekey", post(handle_revokekey))
             .route("/gameslist", get(handle_global_gameslist))
             .route("/newgame", post(handle_global_newgame))
+            .route("/cards/verify/{serial}", get(handle_verify_card))
             // Game Functions routes
             .route("/{game_id}/join", post(handle_join))
             .route("/{game_id}/generatecards", post(handle_generatecards))
             .route("/{game_id}/listassignedcards", get(handle_listassignedcards))
             .route("/{game_id}/getassignedcard/{card_id}", get(handle_getassignedcard))
+            .route("/{game_id}/importcards", post(handle_importcards))
+            .route("/{game_id}/assigncards", post(handle_assigncards))
             .route("/{game_id}/board", get(handle_board))
             .route("/{game_id}/pouch", get(handle_pouch))
             .route("/{game_id}/scoremap", get(handle_scoremap))
             .route("/{game_id}/status", get(handle_status))
+            .route("/{game_id}/verify", get(handle_verify))
             .route("/{game_id}/players", get(handle_players))
+            .route("/{game_id}/ws", get(handle_ws))
+            .route("/{game_id}/snapshot", get(handle_snapshot))
+            .route("/{game_id}/events", get(handle_events))
             .route("/{game_id}/extract", post(handle_extract))
+            .route("/{game_id}/claim", post(handle_claim))
+            .route("/{game_id}/undo", post(handle_undo))
             .route("/{game_id}/dumpgame", post(handle_dumpgame))
+            .route("/{game_id}/setrole", post(handle_setrole))
             .layer(CorsLayer::permissive())
             .with_state(app_state);
 
@@ -0,0 +1,782 @@
+// src/store.rs
+// Storage backends for games and clients: in memory, a directory of JSON files
+// or an embedded SQLite database, chosen with `store` in server.conf.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::client::ClientInfo;
+use crate::config::{ServerConfig, StoreKind};
+use crate::game::{GameStatus, SerializableGameState};
+use crate::journal::JournalEntry;
+
+const GAMES_DIR: &str = "games";
+const JOURNAL_DIR: &str = "journal";
+const CLIENTS_FILE: &str = "clients.json";
+const DATABASE_FILE: &str = "tombola.db";
+
+/// Summary of a stored game, used to query past games and players
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub game_id: String,
+    pub created_at: SystemTime,
+    pub owner: Option<String>,
+    pub status: GameStatus,
+    pub extracted: usize,
+    pub players: Vec<String>,
+    pub card_count: usize,
+}
+
+impl GameRecord {
+    #[must_use] pub fn from_state(state: &SerializableGameState) -> Self {
+        let mut players: Vec<String> = state.registered_clients.iter().cloned().collect();
+        players.sort();
+        Self {
+            game_id: state.id.clone(),
+            created_at: state.created_at,
+            owner: state.owner.clone(),
+            status: state.status(),
+            extracted: state.board.len(),
+            players,
+            card_count: state.card_manager.get_all_assignments().len(),
+        }
+    }
+}
+
+/// Storage of the games and clients of the server
+pub trait GameStore: Send + Sync {
+    /// Where the data is kept, for log messages
+    fn description(&self) -> String;
+
+    /// Save a game, replacing its previous state
+    fn save_game(&self, state: &SerializableGameState) -> Result<(), String>;
+
+    /// Stop restoring a game on startup; stores keeping a history may still list it in `game_records`
+    fn remove_game(&self, game_id: &str) -> Result<(), String>;
+
+    /// IDs of the games restored on startup
+    fn game_ids(&self) -> Result<Vec<String>, String>;
+
+    /// Games restored on startup; a damaged game is returned as an error without hiding the others
+    fn load_games(&self) -> Result<Vec<Result<SerializableGameState, String>>, String>;
+
+    /// Save the global clients, replacing the previous list
+    fn save_clients(&self, clients: &[ClientInfo]) -> Result<(), String>;
+
+    fn load_clients(&self) -> Result<Vec<ClientInfo>, String>;
+
+    /// Append entries to the journal of a game; entries already stored are never rewritten
+    fn append_journal(&self, game_id: &str, entries: &[JournalEntry]) -> Result<(), String>;
+
+    /// Journal of a game in order, empty if none was stored
+    fn load_journal(&self, game_id: &str) -> Result<Vec<JournalEntry>, String>;
+
+    /// Summaries of the stored games, only those joined by `client_id` if given, oldest first
+    fn game_records(&self, client_id: Option<&str>) -> Result<Vec<GameRecord>, String> {
+        let mut records: Vec<GameRecord> = self.load_games()?
+            .iter()
+            .flatten()
+            .map(GameRecord::from_state)
+            .filter(|record| client_id.is_none_or(|id| record.players.iter().any(|player| player == id)))
+            .collect();
+        records.sort_by_key(|record| record.created_at);
+        Ok(records)
+    }
+}
+
+/// Open the store selected in the server configuration
+pub fn open_store(config: &ServerConfig) -> Result<Box<dyn GameStore>, String> {
+    Ok(match config.store {
+        StoreKind::Memory => Box::new(MemoryStore::new()),
+        StoreKind::Json => Box::new(JsonDirStore::new(&config.snapshot_path)),
+        StoreKind::Sqlite => Box::new(SqliteStore::open(Path::new(&config.snapshot_path).join(DATABASE_FILE))?),
+    })
+}
+
+/// Store kept in memory only, lost when the server stops
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    // Games are kept serialized, as the other stores do
+    games: Mutex<HashMap<String, String>>,
+    clients: Mutex<Vec<ClientInfo>>,
+    journals: Mutex<HashMap<String, Vec<JournalEntry>>>,
+}
+
+impl MemoryStore {
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl GameStore for MemoryStore {
+    fn description(&self) -> String {
+        "memory".to_string()
+    }
+
+    fn save_game(&self, state: &SerializableGameState) -> Result<(), String> {
+        let json_content = serde_json::to_string(state)
+            .map_err(|e| format!("Failed to serialize game {}: {e}", state.id))?;
+        self.games.lock()
+            .map_err(|_| "Failed to lock memory store")?
+            .insert(state.id.clone(), json_content);
+        Ok(())
+    }
+
+    fn remove_game(&self, game_id: &str) -> Result<(), String> {
+        self.games.lock()
+            .map_err(|_| "Failed to lock memory store")?
+            .remove(game_id);
+        Ok(())
+    }
+
+    fn game_ids(&self) -> Result<Vec<String>, String> {
+        let games = self.games.lock()
+            .map_err(|_| "Failed to lock memory store")?;
+        Ok(games.keys().cloned().collect())
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<SerializableGameState, String>>, String> {
+        let games = self.games.lock()
+            .map_err(|_| "Failed to lock memory store")?;
+        Ok(games.iter()
+            .map(|(game_id, json_content)| serde_json::from_str(json_content)
+                .map_err(|e| format!("Failed to parse game {game_id}: {e}")))
+            .collect())
+    }
+
+    fn save_clients(&self, clients: &[ClientInfo]) -> Result<(), String> {
+        *self.clients.lock().map_err(|_| "Failed to lock memory store")? = clients.to_vec();
+        Ok(())
+    }
+
+    fn load_clients(&self) -> Result<Vec<ClientInfo>, String> {
+        Ok(self.clients.lock().map_err(|_| "Failed to lock memory store")?.clone())
+    }
+
+    fn append_journal(&self, game_id: &str, entries: &[JournalEntry]) -> Result<(), String> {
+        let mut journals = self.journals.lock()
+            .map_err(|_| "Failed to lock memory store")?;
+        let journal = journals.entry(game_id.to_string()).or_default();
+        let stored = journal.len();
+        journal.extend(entries.iter().filter(|entry| entry.seq >= stored).cloned());
+        Ok(())
+    }
+
+    fn load_journal(&self, game_id: &str) -> Result<Vec<JournalEntry>, String> {
+        let journals = self.journals.lock()
+            .map_err(|_| "Failed to lock memory store")?;
+        Ok(journals.get(game_id).cloned().unwrap_or_default())
+    }
+}
+
+/// Store in a directory: `games/<game_id>.json` holds each game, `clients.json` the global clients
+/// and `journal/<game_id>.jsonl` the journal of each game, one entry per line.
+/// Journals are kept when a game is removed, as its audit trail.
+#[derive(Debug)]
+pub struct JsonDirStore {
+    path: PathBuf,
+}
+
+impl JsonDirStore {
+    #[must_use] pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+
+    fn games_dir(&self) -> PathBuf {
+        self.path.join(GAMES_DIR)
+    }
+
+    fn journal_path(&self, game_id: &str) -> PathBuf {
+        self.path.join(JOURNAL_DIR).join(format!("{game_id}.jsonl"))
+    }
+
+    fn game_files(&self) -> Result<Vec<PathBuf>, String> {
+        let games_dir = self.games_dir();
+        if !games_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&games_dir)
+            .map_err(|e| format!("Failed to read directory {games_dir:?}: {e}"))?;
+        Ok(entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect())
+    }
+}
+
+impl GameStore for JsonDirStore {
+    fn description(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn save_game(&self, state: &SerializableGameState) -> Result<(), String> {
+        let games_dir = self.games_dir();
+        fs::create_dir_all(&games_dir)
+            .map_err(|e| format!("Failed to create directory {games_dir:?}: {e}"))?;
+        write_json(&games_dir.join(format!("{}.json", state.id)), state)
+    }
+
+    fn remove_game(&self, game_id: &str) -> Result<(), String> {
+        let path = self.games_dir().join(format!("{game_id}.json"));
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove file {path:?}: {e}"))?;
+        }
+        Ok(())
+    }
+
+    fn game_ids(&self) -> Result<Vec<String>, String> {
+        Ok(self.game_files()?
+            .iter()
+            .filter_map(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .collect())
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<SerializableGameState, String>>, String> {
+        Ok(self.game_files()?.iter().map(|path| read_json(path)).collect())
+    }
+
+    fn save_clients(&self, clients: &[ClientInfo]) -> Result<(), String> {
+        fs::create_dir_all(&self.path)
+            .map_err(|e| format!("Failed to create directory {:?}: {e}", self.path))?;
+        write_json(&self.path.join(CLIENTS_FILE), clients)
+    }
+
+    fn load_clients(&self) -> Result<Vec<ClientInfo>, String> {
+        let clients_path = self.path.join(CLIENTS_FILE);
+        if clients_path.exists() {
+            read_json(&clients_path)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn append_journal(&self, game_id: &str, entries: &[JournalEntry]) -> Result<(), String> {
+        use std::io::Write;
+
+        let stored = self.load_journal(game_id)?.len();
+        let mut lines = String::new();
+        for entry in entries.iter().filter(|entry| entry.seq >= stored) {
+            let line = serde_json::to_string(entry)
+                .map_err(|e| format!("Failed to serialize journal entry {}: {e}", entry.seq))?;
+            lines.push_str(&line);
+            lines.push('\n');
+        }
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.journal_path(game_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {parent:?}: {e}"))?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(|e| format!("Failed to append to file {path:?}: {e}"))
+    }
+
+    fn load_journal(&self, game_id: &str) -> Result<Vec<JournalEntry>, String> {
+        let path = self.journal_path(game_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read file {path:?}: {e}"))?;
+        content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| format!("Failed to parse {}: {e}", path.display())))
+            .collect()
+    }
+}
+
+// Write JSON through a temporary file so that a crash never leaves a truncated file
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), String> {
+    let json_content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {e}", path.display()))?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json_content)
+        .map_err(|e| format!("Failed to write file {temp_path:?}: {e}"))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Failed to replace file {path:?}: {e}"))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read file {path:?}: {e}"))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+}
+
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    id TEXT PRIMARY KEY,
+    created_at INTEGER NOT NULL,
+    owner TEXT,
+    status TEXT NOT NULL,
+    ruleset TEXT NOT NULL,
+    extracted INTEGER NOT NULL,
+    restorable INTEGER NOT NULL DEFAULT 1,
+    state TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS clients (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    client_type TEXT NOT NULL,
+    email TEXT NOT NULL,
+    registered_at INTEGER NOT NULL,
+    api_key_hash TEXT
+);
+CREATE TABLE IF NOT EXISTS game_clients (
+    game_id TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    client_type TEXT,
+    PRIMARY KEY (game_id, client_id)
+);
+CREATE TABLE IF NOT EXISTS card_assignments (
+    game_id TEXT NOT NULL,
+    card_id TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    card_data TEXT NOT NULL,
+    PRIMARY KEY (game_id, card_id)
+);
+CREATE TABLE IF NOT EXISTS extractions (
+    game_id TEXT NOT NULL,
+    draw_index INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    PRIMARY KEY (game_id, draw_index)
+);
+CREATE TABLE IF NOT EXISTS achievements (
+    game_id TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    client_id TEXT NOT NULL,
+    card_id TEXT NOT NULL,
+    numbers TEXT NOT NULL,
+    draw_index INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    game_id TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    event TEXT NOT NULL,
+    PRIMARY KEY (game_id, seq)
+);
+CREATE INDEX IF NOT EXISTS game_clients_by_client ON game_clients (client_id);
+";
+
+/// Store in an embedded SQLite database.
+/// Besides the full game state used for restoring, the clients, card assignments,
+/// extractions and achievements of each game are kept in their own tables for queries,
+/// the journal of each game in `events`, and removed games stay in the database as history.
+#[derive(Debug)]
+pub struct SqliteStore {
+    path: PathBuf,
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open or create the database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {parent:?}: {e}"))?;
+        }
+        let connection = Connection::open(&path)
+            .map_err(|e| format!("Failed to open database {}: {e}", path.display()))?;
+        Self::with_connection(path, connection)
+    }
+
+    /// Database in memory, for tests
+    pub fn open_in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open database in memory: {e}"))?;
+        Self::with_connection(PathBuf::from(":memory:"), connection)
+    }
+
+    fn with_connection(path: PathBuf, connection: Connection) -> Result<Self, String> {
+        connection.execute_batch(SQLITE_SCHEMA)
+            .map_err(|e| format!("Failed to create database schema: {e}"))?;
+        Self::migrate(&connection)
+            .map_err(|e| format!("Failed to migrate database schema: {e}"))?;
+        Ok(Self { path, connection: Mutex::new(connection) })
+    }
+
+    // Add the columns missing from the databases created by older versions
+    fn migrate(connection: &Connection) -> Result<(), String> {
+        let mut statement = connection.prepare("SELECT name FROM pragma_table_info('clients')").map_err(sql_error)?;
+        let columns = statement.query_map([], |row| row.get::<_, String>(0))
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+        if !columns.iter().any(|column| column == "api_key_hash") {
+            connection.execute("ALTER TABLE clients ADD COLUMN api_key_hash TEXT", []).map_err(sql_error)?;
+        }
+        Ok(())
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.connection.lock().map_err(|_| "Failed to lock database".to_string())
+    }
+}
+
+fn sql_error(e: rusqlite::Error) -> String {
+    format!("Database error: {e}")
+}
+
+fn to_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+}
+
+fn from_secs(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(u64::try_from(secs).unwrap_or(0))
+}
+
+fn to_json_text<T: Serialize + ?Sized>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| format!("Failed to serialize: {e}"))
+}
+
+fn parse_status(status: &str) -> GameStatus {
+    match status {
+        "Active" => GameStatus::Active,
+        "Closed" => GameStatus::Closed,
+        _ => GameStatus::New,
+    }
+}
+
+impl GameStore for SqliteStore {
+    fn description(&self) -> String {
+        format!("SQLite database {}", self.path.display())
+    }
+
+    fn save_game(&self, state: &SerializableGameState) -> Result<(), String> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(sql_error)?;
+        let game_id = state.id.as_str();
+        let ruleset = serde_json::to_value(state.ruleset)
+            .map_err(|e| format!("Failed to serialize ruleset: {e}"))?;
+
+        transaction.execute(
+            "INSERT OR REPLACE INTO games (id, created_at, owner, status, ruleset, extracted, restorable, state)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7)",
+            params![
+                game_id,
+                to_secs(state.created_at),
+                state.owner,
+                state.status().as_str(),
+                ruleset.as_str().unwrap_or_default(),
+                state.board.len(),
+                to_json_text(state)?,
+            ],
+        ).map_err(sql_error)?;
+
+        // The detail rows are rewritten with the game, so undone extractions and prizes disappear too
+        for table in ["game_clients", "card_assignments", "extractions", "achievements"] {
+            transaction.execute(&format!("DELETE FROM {table} WHERE game_id = ?1"), params![game_id])
+                .map_err(sql_error)?;
+        }
+
+        let client_types: HashMap<&str, &str> = state.role_registry.iter()
+            .map(|client_role| (client_role.client_id.as_str(), client_role.role.as_str()))
+            .collect();
+        for client_id in &state.registered_clients {
+            transaction.execute(
+                "INSERT INTO game_clients (game_id, client_id, client_type) VALUES (?1, ?2, ?3)",
+                params![game_id, client_id, client_types.get(client_id.as_str())],
+            ).map_err(sql_error)?;
+        }
+
+        for assignment in state.card_manager.get_all_assignments().values() {
+            transaction.execute(
+                "INSERT INTO card_assignments (game_id, card_id, client_id, card_data) VALUES (?1, ?2, ?3, ?4)",
+                params![game_id, assignment.card_id, assignment.client_id, to_json_text(&assignment.card_data)?],
+            ).map_err(sql_error)?;
+        }
+
+        for (draw_index, number) in state.board.get_numbers().iter().enumerate() {
+            transaction.execute(
+                "INSERT INTO extractions (game_id, draw_index, number) VALUES (?1, ?2, ?3)",
+                params![game_id, draw_index + 1, number],
+            ).map_err(sql_error)?;
+        }
+
+        for (score, achievements) in state.scorecard.get_scoremap() {
+            for achievement in achievements {
+                transaction.execute(
+                    "INSERT INTO achievements (game_id, score, client_id, card_id, numbers, draw_index)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        game_id,
+                        score,
+                        achievement.client_id,
+                        achievement.card_id,
+                        to_json_text(&achievement.numbers)?,
+                        achievement.draw_index,
+                    ],
+                ).map_err(sql_error)?;
+            }
+        }
+
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn remove_game(&self, game_id: &str) -> Result<(), String> {
+        self.connection()?
+            .execute("UPDATE games SET restorable = 0 WHERE id = ?1", params![game_id])
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    fn game_ids(&self) -> Result<Vec<String>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT id FROM games WHERE restorable = 1")
+            .map_err(sql_error)?;
+        statement.query_map([], |row| row.get(0))
+            .map_err(sql_error)?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(sql_error)
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<SerializableGameState, String>>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT id, state FROM games WHERE restorable = 1")
+            .map_err(sql_error)?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(sql_error)?;
+        Ok(rows
+            .map(|row| {
+                let (game_id, state) = row.map_err(sql_error)?;
+                serde_json::from_str(&state).map_err(|e| format!("Failed to parse game {game_id}: {e}"))
+            })
+            .collect())
+    }
+
+    fn save_clients(&self, clients: &[ClientInfo]) -> Result<(), String> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(sql_error)?;
+        transaction.execute("DELETE FROM clients", []).map_err(sql_error)?;
+        for client in clients {
+            transaction.execute(
+                "INSERT INTO clients (id, name, client_type, email, registered_at, api_key_hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![client.id, client.name, client.client_type, client.email, to_secs(client.registered_at), client.api_key_hash],
+            ).map_err(sql_error)?;
+        }
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn load_clients(&self) -> Result<Vec<ClientInfo>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT id, name, client_type, email, registered_at, api_key_hash FROM clients")
+            .map_err(sql_error)?;
+        statement.query_map([], |row| {
+            Ok(ClientInfo {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                client_type: row.get(2)?,
+                email: row.get(3)?,
+                registered_at: from_secs(row.get(4)?),
+                api_key_hash: row.get(5)?,
+            })
+        })
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)
+    }
+
+    fn append_journal(&self, game_id: &str, entries: &[JournalEntry]) -> Result<(), String> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(sql_error)?;
+        for entry in entries {
+            transaction.execute(
+                "INSERT OR IGNORE INTO events (game_id, seq, recorded_at, event) VALUES (?1, ?2, ?3, ?4)",
+                params![game_id, entry.seq, to_secs(entry.recorded_at), to_json_text(entry)?],
+            ).map_err(sql_error)?;
+        }
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn load_journal(&self, game_id: &str) -> Result<Vec<JournalEntry>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT event FROM events WHERE game_id = ?1 ORDER BY seq")
+            .map_err(sql_error)?;
+        let rows = statement.query_map(params![game_id], |row| row.get::<_, String>(0))
+            .map_err(sql_error)?;
+        rows.map(|row| {
+            let event = row.map_err(sql_error)?;
+            serde_json::from_str(&event).map_err(|e| format!("Failed to parse journal of game {game_id}: {e}"))
+        }).collect()
+    }
+
+    fn game_records(&self, client_id: Option<&str>) -> Result<Vec<GameRecord>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(
+            "SELECT id, created_at, owner, status, extracted,
+                    (SELECT COUNT(*) FROM card_assignments WHERE card_assignments.game_id = games.id)
+             FROM games
+             WHERE ?1 IS NULL OR id IN (SELECT game_id FROM game_clients WHERE client_id = ?1)
+             ORDER BY created_at, id",
+        ).map_err(sql_error)?;
+        let mut players_statement = connection.prepare(
+            "SELECT client_id FROM game_clients WHERE game_id = ?1 ORDER BY client_id",
+        ).map_err(sql_error)?;
+
+        let rows = statement.query_map(params![client_id], |row| {
+            Ok(GameRecord {
+                game_id: row.get(0)?,
+                created_at: from_secs(row.get(1)?),
+                owner: row.get(2)?,
+                status: parse_status(&row.get::<_, String>(3)?),
+                extracted: row.get(4)?,
+                players: Vec::new(),
+                card_count: row.get(5)?,
+            })
+        }).map_err(sql_error)?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            let mut record = row.map_err(sql_error)?;
+            record.players = players_statement.query_map(params![record.game_id], |row| row.get(0))
+                .map_err(sql_error)?
+                .collect::<Result<Vec<String>, _>>()
+                .map_err(sql_error)?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+impl SqliteStore {
+    /// Extracted numbers of a stored game, in draw order
+    pub fn extractions(&self, game_id: &str) -> Result<Vec<crate::defs::Number>, String> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT number FROM extractions WHERE game_id = ?1 ORDER BY draw_index")
+            .map_err(sql_error)?;
+        statement.query_map(params![game_id], |row| row.get(0))
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)
+    }
+
+    /// Number of prizes won by a client across all stored games
+    pub fn client_prize_count(&self, client_id: &str) -> Result<usize, String> {
+        self.connection()?
+            .query_row("SELECT COUNT(*) FROM achievements WHERE client_id = ?1", params![client_id], |row| row.get(0))
+            .map_err(sql_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::{DrawMode, ManualDraw};
+    use crate::game::Game;
+    use crate::role::Role;
+
+    fn sample_game() -> (Game, ClientInfo) {
+        let mut client = ClientInfo::new("player1", "player", "player1@example.com");
+        let _ = client.new_api_key();
+        let game = Game::new();
+        game.set_owner(&client.id).unwrap();
+        game.add_client(client.id.clone()).unwrap();
+        game.set_role(&client.id, Role::Player).unwrap();
+        game.set_draw_mode(DrawMode::Manual).unwrap();
+        game.card_manager().lock().unwrap().assign_cards(&client.id, 2).unwrap();
+        game.extract_number_from(&ManualDraw(42), 0, None).unwrap();
+        game.extract_number_from(&ManualDraw(7), 0, None).unwrap();
+        (game, client)
+    }
+
+    fn check_store(store: &dyn GameStore) -> Game {
+        let (game, client) = sample_game();
+        let state = game.create_serializable_state().unwrap();
+        store.save_game(&state).unwrap();
+        store.save_game(&state).unwrap();
+        store.save_clients(std::slice::from_ref(&client)).unwrap();
+
+        assert_eq!(store.game_ids().unwrap(), vec![game.id()]);
+        let loaded = store.load_games().unwrap().pop().unwrap().unwrap();
+        assert_eq!(loaded.id, game.id());
+        assert_eq!(loaded.board.get_numbers(), &vec![42, 7]);
+        let clients = store.load_clients().unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].id, client.id);
+        assert_eq!(clients[0].api_key_hash, client.api_key_hash);
+
+        let records = store.game_records(Some(&client.id)).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, GameStatus::Active);
+        assert_eq!(records[0].extracted, 2);
+        assert_eq!(records[0].players, vec![client.id.clone()]);
+        assert_eq!(records[0].card_count, 2);
+        assert!(store.game_records(Some("unknown")).unwrap().is_empty());
+
+        let journal = game.journal_since(0).unwrap();
+        store.append_journal(&game.id(), &journal[..3]).unwrap();
+        store.append_journal(&game.id(), &journal).unwrap();
+        let stored: Vec<usize> = store.load_journal(&game.id()).unwrap().iter().map(|entry| entry.seq).collect();
+        assert_eq!(stored, (0..journal.len()).collect::<Vec<_>>());
+        assert!(store.load_journal("unknown").unwrap().is_empty());
+
+        store.remove_game(&game.id()).unwrap();
+        assert!(store.game_ids().unwrap().is_empty());
+        assert!(store.load_games().unwrap().is_empty());
+        game
+    }
+
+    #[test]
+    fn test_memory_store() {
+        check_store(&MemoryStore::new());
+    }
+
+    #[test]
+    fn test_json_dir_store() {
+        let path = std::env::temp_dir().join(format!("tombola_store_json_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        check_store(&JsonDirStore::new(&path));
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_sqlite_store() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let game = check_store(&store);
+
+        // Removed games are kept as history with their extractions
+        let records = store.game_records(None).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(store.extractions(&records[0].game_id).unwrap(), vec![42, 7]);
+        // The random cards may win a prize with the two numbers
+        let prizes = game.scorecard().lock().unwrap().score_map.values()
+            .flatten()
+            .filter(|achievement| achievement.client_id == records[0].players[0])
+            .count();
+        assert_eq!(store.client_prize_count(&records[0].players[0]).unwrap(), prizes);
+    }
+
+    #[test]
+    fn test_sqlite_migration() {
+        // Database of a version storing the clients without their API key
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(
+            "CREATE TABLE clients (id TEXT PRIMARY KEY, name TEXT NOT NULL, client_type TEXT NOT NULL, email TEXT NOT NULL, registered_at INTEGER NOT NULL);
+             INSERT INTO clients VALUES ('C1', 'player1', 'player', '', 0);"
+        ).unwrap();
+
+        let store = SqliteStore::with_connection(PathBuf::from(":memory:"), connection).unwrap();
+        let clients = store.load_clients().unwrap();
+        assert_eq!(clients[0].id, "C1");
+        assert_eq!(clients[0].api_key_hash, None);
+    }
+}